@@ -1,7 +1,15 @@
+use rodio::source::{SineWave, Source, Zero};
 use rodio::{Decoder, OutputStream, Sink};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
+use std::time::Duration;
+
+/// 生成的提醒音：频率（Hz）、响几声、每声和间隔的时长（毫秒）
+const ALERT_TONE_HZ: f32 = 880.0;
+const ALERT_TONE_BEEPS: usize = 3;
+const ALERT_TONE_BEEP_MS: u64 = 180;
+const ALERT_TONE_GAP_MS: u64 = 120;
 
 #[allow(dead_code)] // AudioPlayer为将来扩展而保留
 
@@ -33,6 +41,15 @@ impl AudioPlayer {
         Ok(())
     }
 
+    /// 播放生成的提醒音（几声短促的正弦波），不依赖声音文件，音量由 `set_volume` 控制
+    pub fn play_tone(&self) {
+        for _ in 0..ALERT_TONE_BEEPS {
+            let beep = SineWave::new(ALERT_TONE_HZ).take_duration(Duration::from_millis(ALERT_TONE_BEEP_MS));
+            self.sink.append(beep.amplify(0.5));
+            self.sink.append(Zero::<f32>::new(1, 48000).take_duration(Duration::from_millis(ALERT_TONE_GAP_MS)));
+        }
+    }
+
     /// 播放系统警告音
     pub fn play_system_alert(&self) -> Result<(), Box<dyn std::error::Error>> {
         #[cfg(target_os = "windows")]
@@ -94,6 +111,11 @@ impl AudioPlayer {
     pub fn set_volume(&self, volume: f32) {
         self.sink.set_volume(volume.clamp(0.0, 1.0));
     }
+
+    /// 等待播放结束
+    pub fn wait_until_end(&self) {
+        self.sink.sleep_until_end();
+    }
 }
 
 #[allow(dead_code)] // 为将来扩展而保留的声音类型
@@ -136,6 +158,37 @@ impl AudioManager {
         self.play_system_alert()
     }
 
+    /// 以指定音量播放提醒音（重复提醒时逐步增大音量）
+    ///
+    /// 自定义音频文件存在时播放该文件，否则播放生成的提醒音，两者都按 `volume` 调节音量。
+    pub fn play_alert_at_volume(&self, sound_type: AlertSoundType, volume: f32) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let volume = volume.clamp(0.0, 1.0);
+        let custom_file = match sound_type {
+            AlertSoundType::CustomFile(path) if Path::new(&path).exists() => Some(path),
+            _ => None,
+        };
+        // 输出流不能跨线程传递，在独立线程中创建播放器并播放到结束
+        std::thread::spawn(move || {
+            let result = AudioPlayer::new().and_then(|player| {
+                player.set_volume(volume);
+                match custom_file {
+                    Some(ref path) => player.play_file(path)?,
+                    None => player.play_tone(),
+                }
+                player.wait_until_end();
+                Ok(())
+            });
+            if let Err(e) = result {
+                crate::log_error!("Failed to play alert sound: {}", e);
+            }
+        });
+        Ok(())
+    }
+
     /// 播放电源断开提醒音
     pub fn play_power_disconnected_alert(&self) -> Result<(), Box<dyn std::error::Error>> {
         self.play_alert(AlertSoundType::SystemWarning)
@@ -1,4 +1,5 @@
 use crate::config::{AppConfig, MonitoringConfig, UiConfig, SystemConfig};
+use crate::history::HistoryConfig;
 use std::sync::{Arc, Mutex};
 
 #[allow(dead_code)] // 许多配置方法为将来的完整性而保留
@@ -107,6 +108,11 @@ impl ConfigManager {
         self.config.lock().unwrap().system.clone()
     }
 
+    /// 获取历史记录配置
+    pub fn get_history_config(&self) -> HistoryConfig {
+        self.config.lock().unwrap().history.clone()
+    }
+
     /// 获取检测间隔（秒）
     pub fn get_check_interval(&self) -> u64 {
         self.config.lock().unwrap().monitoring.check_interval
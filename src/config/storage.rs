@@ -1,3 +1,6 @@
+use crate::history::HistoryConfig;
+use crate::policy::{AlertRule, CriticalActionConfig, EscalationConfig, QuietHours};
+use crate::power::{BatteryLevel, PowerEventConfig};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -9,6 +12,36 @@ pub struct MonitoringConfig {
     pub sound_enabled: bool,
     pub auto_close_alert: bool,
     pub low_battery_threshold: u8,
+    #[serde(default)]
+    pub battery_levels: Vec<BatteryLevel>,  // 多级电量提醒，按阈值从高到低排列；为空时使用 low_battery_threshold
+    #[serde(default)]
+    pub events: PowerEventConfig,  // 扩展电源事件参数
+    #[serde(default = "default_adaptive_interval")]
+    pub adaptive_interval: bool,  // 根据电源状态自动调整检测间隔
+    #[serde(default = "default_min_check_interval")]
+    pub min_check_interval: u64,  // 自适应检测间隔下限（秒）
+    #[serde(default = "default_max_check_interval")]
+    pub max_check_interval: u64,  // 自适应检测间隔上限（秒）
+    #[serde(default)]
+    pub quiet_hours: Vec<QuietHours>,  // 静音时段，时段内只显示托盘通知（silent）或不提醒（off）
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,  // 提醒规则，为空时使用内置规则
+    #[serde(default)]
+    pub escalation: EscalationConfig,  // 未确认的低电量提醒如何重复提醒
+    #[serde(default)]
+    pub critical_action: CriticalActionConfig,  // 电量即将耗尽时自动休眠、关机等
+}
+
+fn default_adaptive_interval() -> bool {
+    true
+}
+
+fn default_min_check_interval() -> u64 {
+    2
+}
+
+fn default_max_check_interval() -> u64 {
+    60
 }
 
 impl Default for MonitoringConfig {
@@ -18,6 +51,26 @@ impl Default for MonitoringConfig {
             sound_enabled: true,
             auto_close_alert: true,
             low_battery_threshold: 20,
+            battery_levels: Vec::new(),
+            events: PowerEventConfig::default(),
+            adaptive_interval: default_adaptive_interval(),
+            min_check_interval: default_min_check_interval(),
+            max_check_interval: default_max_check_interval(),
+            quiet_hours: Vec::new(),
+            rules: Vec::new(),
+            escalation: EscalationConfig::default(),
+            critical_action: CriticalActionConfig::default(),
+        }
+    }
+}
+
+impl MonitoringConfig {
+    /// 实际生效的电量提醒级别
+    pub fn effective_battery_levels(&self) -> Vec<BatteryLevel> {
+        if self.battery_levels.is_empty() {
+            vec![BatteryLevel::low(self.low_battery_threshold)]
+        } else {
+            self.battery_levels.clone()
         }
     }
 }
@@ -61,6 +114,8 @@ pub struct AppConfig {
     pub monitoring: MonitoringConfig,
     pub ui: UiConfig,
     pub system: SystemConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,  // 电源历史记录
 }
 
 impl Default for AppConfig {
@@ -69,6 +124,7 @@ impl Default for AppConfig {
             monitoring: MonitoringConfig::default(),
             ui: UiConfig::default(),
             system: SystemConfig::default(),
+            history: HistoryConfig::default(),
         }
     }
 }
@@ -125,10 +181,45 @@ impl AppConfig {
             return Err("检测间隔不能超过3600秒".to_string());
         }
 
+        if self.monitoring.min_check_interval == 0 {
+            return Err("最短检测间隔不能为0".to_string());
+        }
+
+        if self.monitoring.max_check_interval > 3600 {
+            return Err("最长检测间隔不能超过3600秒".to_string());
+        }
+
+        if self.monitoring.min_check_interval > self.monitoring.max_check_interval {
+            return Err("最短检测间隔不能大于最长检测间隔".to_string());
+        }
+
         if self.monitoring.low_battery_threshold > 100 {
             return Err("低电量阈值不能超过100%".to_string());
         }
 
+        BatteryLevel::validate_levels(&self.monitoring.battery_levels)?;
+
+        self.monitoring.events.validate()?;
+
+        for quiet_hours in &self.monitoring.quiet_hours {
+            quiet_hours.validate()?;
+        }
+
+        for rule in &self.monitoring.rules {
+            rule.validate()?;
+        }
+
+        self.monitoring.escalation.validate()?;
+
+        self.monitoring.critical_action.validate()?;
+
+        let highest_level = self.monitoring.effective_battery_levels()[0].threshold;
+        if self.monitoring.events.critical_threshold > highest_level {
+            return Err("严重低电量阈值不能高于低电量阈值".to_string());
+        }
+
+        self.history.validate()?;
+
         if self.ui.window_opacity < 0.0 || self.ui.window_opacity > 1.0 {
             return Err("窗口透明度必须在0.0到1.0之间".to_string());
         }
@@ -221,6 +312,106 @@ mod tests {
         assert_eq!(config.ui.alert_color, deserialized.ui.alert_color);
     }
 
+    #[test]
+    fn test_event_config_defaults_for_old_files() {
+        // 旧版本的配置文件没有 [monitoring.events]
+        let toml_str = r##"
+            [monitoring]
+            check_interval = 10
+            sound_enabled = true
+            auto_close_alert = true
+            low_battery_threshold = 20
+
+            [ui]
+            alert_color = "#FF6B35"
+            low_battery_color = "#FF0000"
+            window_opacity = 0.95
+            always_on_top = true
+
+            [system]
+            auto_startup = false
+            minimize_to_tray = true
+        "##;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.monitoring.events, PowerEventConfig::default());
+        assert!(config.monitoring.adaptive_interval);
+        assert_eq!(config.monitoring.min_check_interval, 2);
+        assert_eq!(config.monitoring.max_check_interval, 60);
+        assert!(config.monitoring.quiet_hours.is_empty());
+        assert!(config.monitoring.rules.is_empty());
+        assert_eq!(config.monitoring.escalation, EscalationConfig::default());
+        assert_eq!(config.monitoring.critical_action, CriticalActionConfig::default());
+        assert_eq!(config.history, HistoryConfig::default());
+
+        let mut config = AppConfig::default();
+        config.monitoring.events.rapid_drain_watts = Some(45.0);
+        let deserialized: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(deserialized.monitoring.events.rapid_drain_watts, Some(45.0));
+    }
+
+    #[test]
+    fn test_critical_threshold_validation() {
+        let mut config = AppConfig::default();
+        config.monitoring.events.critical_threshold = 30;
+        assert!(config.validate().is_err());
+
+        config.monitoring.low_battery_threshold = 40;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_check_interval_bounds_validation() {
+        let mut config = AppConfig::default();
+        config.monitoring.min_check_interval = 0;
+        assert!(config.validate().is_err());
+
+        config.monitoring.min_check_interval = 90;
+        assert!(config.validate().is_err());
+
+        config.monitoring.min_check_interval = 5;
+        config.monitoring.max_check_interval = 7200;
+        assert!(config.validate().is_err());
+
+        // check_interval 可以超出范围，自适应时范围自动扩大到包含它
+        config.monitoring.max_check_interval = 30;
+        config.monitoring.check_interval = 300;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_battery_levels_validation() {
+        let level = |name: &str, threshold: u8| BatteryLevel {
+            name: name.to_string(),
+            threshold,
+            ..Default::default()
+        };
+
+        let mut config = AppConfig::default();
+        assert_eq!(config.monitoring.effective_battery_levels(), vec![BatteryLevel::low(20)]);
+
+        config.monitoring.battery_levels = vec![level("warning", 30), level("low", 15), level("critical", 5)];
+        assert!(config.validate().is_ok());
+        assert_eq!(config.monitoring.effective_battery_levels()[0].threshold, 30);
+
+        config.monitoring.battery_levels = vec![level("low", 15), level("warning", 30)];
+        assert!(config.validate().is_err());
+
+        config.monitoring.battery_levels = vec![level("warning", 15), level("low", 15)];
+        assert!(config.validate().is_err());
+
+        let toml_str = toml::to_string_pretty(&AppConfig {
+            monitoring: MonitoringConfig {
+                battery_levels: vec![level("warning", 30), level("low", 15)],
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        let deserialized: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(deserialized.monitoring.battery_levels.len(), 2);
+        assert_eq!(deserialized.monitoring.battery_levels[1].name, "low");
+    }
+
     #[test]
     fn test_config_path() {
         let path = AppConfig::get_config_path();
@@ -0,0 +1,310 @@
+use crate::history::{EventRecord, PowerHistory, PowerSample};
+use crate::policy::EventKind;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::path::Path;
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,  // JSON Lines，每行一个 JSON 对象
+}
+
+impl ExportFormat {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" | "ndjson" => Ok(ExportFormat::Jsonl),
+            _ => Err(format!("未知的导出格式 \"{}\"，应为 csv 或 jsonl", text)),
+        }
+    }
+
+    /// 根据文件扩展名判断格式
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| Self::parse(extension).ok())
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// 采样中可以导出的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportColumn {
+    Percentage,        // 电量（%）
+    Watts,             // 功耗（瓦特）
+    AcConnected,       // 是否接通电源
+    RemainingMinutes,  // 剩余时间（分钟）
+    Charging,          // 是否正在充电
+    ChargeRateWatts,   // 充电/放电速率（瓦特）
+    CapacityMwh,       // 剩余容量（毫瓦时）
+}
+
+impl ExportColumn {
+    /// 未指定列时导出的列
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            ExportColumn::Percentage,
+            ExportColumn::Watts,
+            ExportColumn::AcConnected,
+            ExportColumn::RemainingMinutes,
+        ]
+    }
+
+    /// 解析逗号分隔的列名，例如 "percentage,watts"
+    pub fn parse_list(text: &str) -> Result<Vec<Self>, String> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                serde_json::from_value(Value::String(name.to_string()))
+                    .map_err(|_| format!("未知的导出列 \"{}\"", name))
+            })
+            .collect()
+    }
+
+    /// 表头和 JSON 字段名
+    pub fn name(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    fn sample_value(&self, sample: &PowerSample) -> Value {
+        match self {
+            ExportColumn::Percentage => json!(sample.battery_percentage),
+            ExportColumn::Watts => json!(sample.power_draw_watts),
+            ExportColumn::AcConnected => json!(sample.is_ac_connected),
+            ExportColumn::RemainingMinutes => json!(sample.remaining_time_minutes),
+            ExportColumn::Charging => json!(sample.is_charging),
+            ExportColumn::ChargeRateWatts => json!(sample.charge_rate_watts),
+            ExportColumn::CapacityMwh => json!(sample.battery_capacity_mwh),
+        }
+    }
+
+    /// 事件行只有电量一列有值
+    fn event_value(&self, event: &EventRecord) -> Value {
+        match self {
+            ExportColumn::Percentage => json!(event.battery_percentage),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// 导出选项
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub columns: Vec<ExportColumn>,  // 为空时使用默认列
+    pub include_samples: bool,
+    pub include_events: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Csv,
+            columns: ExportColumn::defaults(),
+            include_samples: true,
+            include_events: true,
+        }
+    }
+}
+
+enum Row<'a> {
+    Sample(&'a PowerSample),
+    Event(&'a EventRecord),
+}
+
+impl Row<'_> {
+    /// 一行导出数据：时间、类型、所选列，以及事件名称和说明（采样行为空），与 CSV 表头顺序一致
+    fn fields(&self, columns: &[ExportColumn]) -> Vec<(String, Value)> {
+        let (timestamp, kind) = match self {
+            Row::Sample(sample) => (sample.timestamp, "sample"),
+            Row::Event(event) => (event.timestamp, "event"),
+        };
+        let mut fields = vec![
+            ("timestamp".to_string(), json!(timestamp.to_rfc3339())),
+            ("type".to_string(), json!(kind)),
+        ];
+        for column in columns {
+            let value = match self {
+                Row::Sample(sample) => column.sample_value(sample),
+                Row::Event(event) => column.event_value(event),
+            };
+            fields.push((column.name(), value));
+        }
+        let (event, detail) = match self {
+            Row::Sample(_) => (Value::Null, Value::Null),
+            Row::Event(record) => (
+                json!(EventKind::of(&record.event).map(|kind| kind.name())),
+                json!(record.event.to_string()),
+            ),
+        };
+        fields.push(("event".to_string(), event));
+        fields.push(("detail".to_string(), detail));
+        fields
+    }
+}
+
+/// 把历史记录按时间顺序写出，返回写出的行数（不含表头）
+pub fn export_history<W: Write>(history: &PowerHistory, options: &ExportOptions, writer: &mut W) -> Result<usize, String> {
+    let columns = if options.columns.is_empty() {
+        ExportColumn::defaults()
+    } else {
+        options.columns.clone()
+    };
+
+    let mut rows: Vec<Row> = Vec::new();
+    if options.include_samples {
+        rows.extend(history.samples.iter().map(Row::Sample));
+    }
+    if options.include_events {
+        rows.extend(history.events.iter().map(Row::Event));
+    }
+    // 稳定排序：同一时刻的采样排在事件之前
+    rows.sort_by_key(|row| match row {
+        Row::Sample(sample) => sample.timestamp,
+        Row::Event(event) => event.timestamp,
+    });
+
+    let write_error = |e: std::io::Error| format!("Failed to write export: {}", e);
+    if options.format == ExportFormat::Csv {
+        let header: Vec<String> = ["timestamp".to_string(), "type".to_string()]
+            .into_iter()
+            .chain(columns.iter().map(ExportColumn::name))
+            .chain(["event".to_string(), "detail".to_string()])
+            .collect();
+        writeln!(writer, "{}", header.join(",")).map_err(write_error)?;
+    }
+
+    for row in &rows {
+        let fields = row.fields(&columns);
+        match options.format {
+            ExportFormat::Csv => {
+                let values: Vec<String> = fields.iter().map(|(_, value)| csv_field(value)).collect();
+                writeln!(writer, "{}", values.join(",")).map_err(write_error)?;
+            }
+            ExportFormat::Jsonl => {
+                // JSON Lines 中省略空值
+                let object: Map<String, Value> = fields.into_iter().filter(|(_, value)| !value.is_null()).collect();
+                writeln!(writer, "{}", Value::Object(object)).map_err(write_error)?;
+            }
+        }
+    }
+    writer.flush().map_err(write_error)?;
+    Ok(rows.len())
+}
+
+/// 导出到文件，返回写出的行数
+pub fn export_to_file(history: &PowerHistory, options: &ExportOptions, path: &Path) -> Result<usize, String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    export_history(history, options, &mut std::io::BufWriter::new(file))
+}
+
+/// CSV 字段：空值为空字符串，包含逗号、引号或换行时加引号
+fn csv_field(value: &Value) -> String {
+    let text = match value {
+        Value::Null => return String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::at;
+    use crate::power::{BatteryLevel, PowerEvent};
+
+    fn history() -> PowerHistory {
+        let sample = |minute, battery_percentage, power_draw_watts| PowerSample {
+            timestamp: at(1, 10, minute),
+            battery_percentage,
+            is_ac_connected: false,
+            is_charging: false,
+            power_draw_watts,
+            charge_rate_watts: None,
+            remaining_time_minutes: Some(120),
+            battery_capacity_mwh: None,
+        };
+        PowerHistory {
+            samples: vec![sample(0, 80, Some(12.5)), sample(5, 79, None)],
+            events: vec![EventRecord {
+                timestamp: at(1, 10, 3),
+                event: PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 79 },
+                battery_percentage: 79,
+            }],
+        }
+    }
+
+    fn export(options: &ExportOptions) -> Vec<String> {
+        let mut output = Vec::new();
+        export_history(&history(), options, &mut output).unwrap();
+        String::from_utf8(output).unwrap().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let options = ExportOptions {
+            columns: vec![ExportColumn::Percentage, ExportColumn::Watts],
+            ..Default::default()
+        };
+        let lines = export(&options);
+        assert_eq!(lines[0], "timestamp,type,percentage,watts,event,detail");
+        assert!(lines[1].ends_with(",sample,80,12.5,,"));
+        assert!(lines[2].contains(",event,79,,battery_low,"));
+        assert!(lines[3].ends_with(",sample,79,,,"));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_jsonl_export() {
+        let options = ExportOptions {
+            format: ExportFormat::Jsonl,
+            columns: Vec::new(),
+            include_events: false,
+            ..Default::default()
+        };
+        let lines = export(&options);
+        assert_eq!(lines.len(), 2);
+
+        let first: Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(first["type"], "sample");
+        assert_eq!(first["percentage"], 80);
+        assert_eq!(first["ac_connected"], false);
+        assert_eq!(first["remaining_minutes"], 120);
+        // 空值省略
+        let second: Value = serde_json::from_str(&lines[1]).unwrap();
+        assert!(second.get("watts").is_none());
+    }
+
+    #[test]
+    fn test_parse_columns_and_format() {
+        assert_eq!(
+            ExportColumn::parse_list("percentage, ac_connected").unwrap(),
+            vec![ExportColumn::Percentage, ExportColumn::AcConnected]
+        );
+        assert!(ExportColumn::parse_list("percentage,volts").is_err());
+        assert_eq!(ExportFormat::from_path(Path::new("out.JSONL")), Some(ExportFormat::Jsonl));
+        assert_eq!(ExportFormat::from_path(Path::new("out.txt")), None);
+        assert_eq!(csv_field(&json!("a,\"b\"")), "\"a,\"\"b\"\"\"");
+    }
+}
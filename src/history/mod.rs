@@ -0,0 +1,13 @@
+pub mod export;
+pub mod record;
+pub mod report;
+pub mod sessions;
+pub mod store;
+pub mod wear;
+
+pub use export::*;
+pub use record::*;
+pub use report::*;
+pub use sessions::*;
+pub use store::*;
+pub use wear::*;
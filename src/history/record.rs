@@ -0,0 +1,99 @@
+use crate::power::{BatteryStatus, MonitorEvent, PowerEvent};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// 一次电源状态采样
+///
+/// 只记录实测或计算得到的数值，估算值记录为 `None`。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerSample {
+    pub timestamp: DateTime<Local>,
+    pub battery_percentage: u8,
+    pub is_ac_connected: bool,
+    pub is_charging: bool,
+    pub power_draw_watts: Option<f32>,        // 当前功耗（瓦特）
+    pub charge_rate_watts: Option<f32>,       // 充电/放电速率（瓦特）
+    pub remaining_time_minutes: Option<u32>,  // 剩余时间（分钟）
+    pub battery_capacity_mwh: Option<u32>,    // 电池剩余容量（毫瓦时）
+}
+
+impl PowerSample {
+    pub fn from_status(status: &BatteryStatus, timestamp: DateTime<Local>) -> Self {
+        let status = status.without_estimates();
+        Self {
+            timestamp,
+            battery_percentage: status.battery_percentage,
+            is_ac_connected: status.is_ac_connected,
+            is_charging: status.is_charging,
+            power_draw_watts: status.power_draw_watts,
+            charge_rate_watts: status.charge_rate_watts,
+            remaining_time_minutes: status.remaining_time_minutes,
+            battery_capacity_mwh: status.battery_capacity_mwh,
+        }
+    }
+
+    /// 与上一次采样相比电源状态或电量是否变化（变化时不受采样间隔限制）
+    pub fn differs_from(&self, other: &PowerSample) -> bool {
+        self.battery_percentage != other.battery_percentage
+            || self.is_ac_connected != other.is_ac_connected
+            || self.is_charging != other.is_charging
+    }
+}
+
+/// 一次电源事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub timestamp: DateTime<Local>,
+    pub event: PowerEvent,
+    pub battery_percentage: u8,  // 事件发生时的电量
+}
+
+/// 历史文件中的一行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryRecord {
+    Sample(PowerSample),
+    Event(EventRecord),
+}
+
+impl HistoryRecord {
+    pub fn timestamp(&self) -> DateTime<Local> {
+        match self {
+            HistoryRecord::Sample(sample) => sample.timestamp,
+            HistoryRecord::Event(event) => event.timestamp,
+        }
+    }
+
+    /// 监控事件对应的记录：电源事件记录为事件，状态更新不单独记录
+    pub fn event(event: &MonitorEvent, timestamp: DateTime<Local>) -> Option<Self> {
+        if event.power_event == PowerEvent::StatusUpdate {
+            return None;
+        }
+        Some(HistoryRecord::Event(EventRecord {
+            timestamp,
+            event: event.power_event.clone(),
+            battery_percentage: event.current_status.battery_percentage,
+        }))
+    }
+}
+
+/// 一个时间范围内的历史记录，按时间排序
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PowerHistory {
+    pub samples: Vec<PowerSample>,
+    pub events: Vec<EventRecord>,
+}
+
+impl PowerHistory {
+    pub fn push(&mut self, record: HistoryRecord) {
+        match record {
+            HistoryRecord::Sample(sample) => self.samples.push(sample),
+            HistoryRecord::Event(event) => self.events.push(event),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty() && self.events.is_empty()
+    }
+}
@@ -0,0 +1,622 @@
+use crate::history::{sessions, HealthReport, HistoryStore, PowerHistory, PowerSession, SessionKind, WearTracker};
+use crate::power::BatteryHealth;
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// 短于该时长的放电不参与续航估算，避免短暂拔掉电源带来的偏差
+const MIN_RUNTIME_SESSION_MINUTES: u32 = 10;
+/// 图表最多绘制的点数，采样更多时等间隔抽取
+const MAX_CHART_POINTS: usize = 1000;
+
+/// 某一天的使用情况
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub battery_minutes: u32,        // 使用电池供电的时间
+    pub charge_minutes: u32,         // 充电时间
+    pub energy_wh: Option<f32>,      // 放电消耗的能量（按时长分摊跨天的放电）
+    pub min_percentage: Option<u8>,  // 当天采样中的最低电量
+    pub max_percentage: Option<u8>,  // 当天采样中的最高电量
+}
+
+/// 根据历史放电估算的续航
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeEstimate {
+    pub session_count: usize,                  // 参与估算的放电次数
+    pub average_watts: Option<f32>,            // 按时长加权的平均放电功率
+    pub drain_percent_per_hour: Option<f32>,   // 按时长加权的每小时耗电（%）
+    pub full_charge_hours: Option<f32>,        // 当前满充容量可用的小时数
+    pub design_capacity_hours: Option<f32>,    // 设计容量可用的小时数
+}
+
+/// 电池报告：容量历史、近期使用、充放电记录、续航估算和健康信息
+///
+/// 与 Windows 的 `powercfg /batteryreport` 类似，但只使用应用自己记录的历史，各平台通用。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryReport {
+    pub generated_at: DateTime<Local>,
+    pub from: DateTime<Local>,
+    pub to: DateTime<Local>,
+    pub health: HealthReport,
+    pub usage: Vec<DailyUsage>,        // 按日期排序，只包含有记录的日期
+    pub sessions: Vec<PowerSession>,
+    pub runtime: RuntimeEstimate,
+    #[serde(skip)]
+    pub levels: Vec<(DateTime<Local>, u8)>,  // 电量曲线，只用于 HTML 图表
+}
+
+impl BatteryReport {
+    /// 从 `[from, to)` 范围内的历史生成报告
+    pub fn build(
+        history: &PowerHistory,
+        health: HealthReport,
+        (from, to): (DateTime<Local>, DateTime<Local>),
+        generated_at: DateTime<Local>,
+    ) -> Self {
+        let sessions = sessions(history);
+        Self {
+            generated_at,
+            from,
+            to,
+            usage: daily_usage(history, &sessions),
+            runtime: estimate_runtime(&sessions, &health),
+            levels: history.samples.iter().map(|sample| (sample.timestamp, sample.battery_percentage)).collect(),
+            health,
+            sessions,
+        }
+    }
+
+    /// 从历史存储和容量记录生成报告，预测阈值和损耗提醒阈值使用 `wear` 的配置
+    pub fn from_stores(
+        history: &HistoryStore,
+        wear: &WearTracker,
+        current: Option<BatteryHealth>,
+        (from, to): (DateTime<Local>, DateTime<Local>),
+        generated_at: DateTime<Local>,
+    ) -> Result<Self, String> {
+        let health = HealthReport::new(current, wear.records()?, &wear.config());
+        Ok(Self::build(&history.query(from, to)?, health, (from, to), generated_at))
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize battery report: {}", e))
+    }
+
+    /// 独立的 HTML 页面（样式和图表内嵌，不引用外部资源）
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"UTF-8\">\n<title>isBattery 电池报告</title>\n<style>{}</style>\n</head>\n<body>\n",
+            REPORT_STYLE
+        );
+        let _ = writeln!(html, "<h1>isBattery 电池报告</h1>");
+        let _ = writeln!(
+            html,
+            "<p class=\"meta\">生成时间：{}<br>统计范围：{} 至 {}</p>",
+            self.generated_at.format("%Y-%m-%d %H:%M"),
+            self.from.format("%Y-%m-%d %H:%M"),
+            self.to.format("%Y-%m-%d %H:%M")
+        );
+
+        self.write_health(&mut html);
+        self.write_capacity_history(&mut html);
+        self.write_usage(&mut html);
+        self.write_sessions(&mut html);
+        self.write_runtime(&mut html);
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn write_health(&self, html: &mut String) {
+        let current = self.health.current.clone().unwrap_or_default();
+        let wear = current.wear_level_percent;
+        html.push_str("<h2>电池健康</h2>\n<table>\n");
+        table_row(html, "制造商", &optional(current.manufacturer.as_deref().map(escape_html)));
+        table_row(html, "序列号", &optional(current.serial.as_deref().map(escape_html)));
+        table_row(html, "化学类型", &optional(current.chemistry.as_deref().map(escape_html)));
+        table_row(html, "设计容量", &optional(current.design_capacity_mwh.map(format_capacity)));
+        table_row(html, "满充容量", &optional(current.full_charge_capacity_mwh.map(format_capacity)));
+        table_row(html, "循环次数", &optional(current.cycle_count.map(|count| count.to_string())));
+        table_row(html, "健康度", &optional(wear.map(|wear| format!("{:.1}%", 100.0 - wear))));
+        let wear_text = optional(wear.map(|wear| format!("{:.1}%", wear)));
+        if self.health.degraded {
+            let text = format!("<span class=\"warning\">{}（已达到 {}% 提醒阈值）</span>", wear_text, self.health.degraded_wear_percent);
+            table_row(html, "损耗", &text);
+        } else {
+            table_row(html, "损耗", &wear_text);
+        }
+
+        if let Some(trend) = &self.health.trend {
+            table_row(html, "容量变化", &format!("{:.1}% / 年", trend.health_percent_per_year));
+            if let Some(cycles) = trend.cycles_per_month {
+                table_row(html, "循环次数增长", &format!("{:.1} 次 / 月", cycles));
+            }
+            for forecast in &trend.forecasts {
+                let value = match (forecast.reached, forecast.date) {
+                    (true, Some(date)) => format!("已于 {} 达到", date),
+                    (false, Some(date)) => format!("预计 {}", date),
+                    _ => "暂无下降趋势".to_string(),
+                };
+                table_row(html, &format!("容量降到 {}%", forecast.threshold_percent), &value);
+            }
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn write_capacity_history(&self, html: &mut String) {
+        html.push_str("<h2>容量历史</h2>\n");
+        let records = &self.health.records;
+        if records.is_empty() {
+            html.push_str("<p class=\"empty\">还没有容量记录</p>\n");
+            return;
+        }
+
+        let points: Vec<(f64, f64)> = records
+            .iter()
+            .filter_map(|record| Some((date_value(record.date), record.health_percent()? as f64)))
+            .collect();
+        let low = points.iter().map(|(_, y)| *y).fold(100.0, f64::min);
+        line_chart(html, &points, ((low - 5.0).floor().max(0.0), 100.0), "%");
+
+        html.push_str("<table>\n<tr><th>日期</th><th>满充容量</th><th>设计容量</th><th>健康度</th><th>循环次数</th></tr>\n");
+        for record in records.iter().rev() {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                record.date,
+                format_capacity(record.full_charge_capacity_mwh),
+                optional(record.design_capacity_mwh.map(format_capacity)),
+                optional(record.health_percent().map(|health| format!("{:.1}%", health))),
+                optional(record.cycle_count.map(|count| count.to_string()))
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn write_usage(&self, html: &mut String) {
+        html.push_str("<h2>近期使用</h2>\n");
+        if self.levels.len() >= 2 {
+            let points: Vec<(f64, f64)> = self
+                .levels
+                .iter()
+                .map(|(timestamp, percentage)| (timestamp.timestamp() as f64, *percentage as f64))
+                .collect();
+            line_chart(html, &points, (0.0, 100.0), "%");
+        }
+        if self.usage.is_empty() {
+            html.push_str("<p class=\"empty\">统计范围内没有电源历史</p>\n");
+            return;
+        }
+
+        html.push_str("<table>\n<tr><th>日期</th><th>电池供电</th><th>充电</th><th>消耗能量</th><th>电量范围</th></tr>\n");
+        for day in self.usage.iter().rev() {
+            let range = match (day.min_percentage, day.max_percentage) {
+                (Some(min), Some(max)) => format!("{}% – {}%", min, max),
+                _ => "-".to_string(),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                day.date,
+                format_minutes(day.battery_minutes),
+                format_minutes(day.charge_minutes),
+                optional(day.energy_wh.map(|energy| format!("{:.1} Wh", energy))),
+                range
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn write_sessions(&self, html: &mut String) {
+        html.push_str("<h2>充放电记录</h2>\n");
+        if self.sessions.is_empty() {
+            html.push_str("<p class=\"empty\">统计范围内没有充放电记录</p>\n");
+            return;
+        }
+
+        html.push_str(
+            "<table>\n<tr><th>开始</th><th>类型</th><th>电量</th><th>时长</th><th>能量</th><th>平均功率</th><th>峰值功率</th><th>每小时</th></tr>\n",
+        );
+        for session in self.sessions.iter().rev() {
+            let kind = if session.ongoing {
+                format!("{}（进行中）", session.kind.label())
+            } else {
+                session.kind.label().to_string()
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}% → {}%</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                session.start.format("%Y-%m-%d %H:%M"),
+                kind,
+                session.start_percentage,
+                session.end_percentage,
+                format_minutes(session.duration_minutes),
+                optional(session.energy_wh.map(|energy| format!("{:.1} Wh", energy))),
+                optional(session.average_watts.map(|watts| format!("{:.1} W", watts))),
+                optional(session.peak_watts.map(|watts| format!("{:.1} W", watts))),
+                optional(session.percent_per_hour.map(|rate| format!("{:.1}%", rate)))
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn write_runtime(&self, html: &mut String) {
+        html.push_str("<h2>续航估算</h2>\n");
+        let runtime = &self.runtime;
+        if runtime.session_count == 0 {
+            html.push_str("<p class=\"empty\">没有足够的放电记录</p>\n");
+            return;
+        }
+
+        let hours = |hours: Option<f32>| optional(hours.map(|hours| format_minutes((hours * 60.0).round() as u32)));
+        html.push_str("<table>\n");
+        table_row(html, "参与估算的放电次数", &runtime.session_count.to_string());
+        table_row(html, "平均放电功率", &optional(runtime.average_watts.map(|watts| format!("{:.1} W", watts))));
+        table_row(html, "平均每小时耗电", &optional(runtime.drain_percent_per_hour.map(|rate| format!("{:.1}%", rate))));
+        table_row(html, "当前满充容量续航", &hours(runtime.full_charge_hours));
+        table_row(html, "设计容量续航", &hours(runtime.design_capacity_hours));
+        html.push_str("</table>\n");
+    }
+
+    /// 写出 HTML 报告，并在同一目录写出同名的 JSON 文件，返回两个文件的路径
+    pub fn write_files(&self, path: &Path) -> Result<(PathBuf, PathBuf), String> {
+        let html_path = path.with_extension("html");
+        let json_path = path.with_extension("json");
+        std::fs::write(&html_path, self.to_html()).map_err(|e| format!("Failed to write {}: {}", html_path.display(), e))?;
+        std::fs::write(&json_path, self.to_json()?).map_err(|e| format!("Failed to write {}: {}", json_path.display(), e))?;
+        Ok((html_path, json_path))
+    }
+}
+
+/// 按日期汇总采样和充放电，跨天的充放电按时长分到各天
+fn daily_usage(history: &PowerHistory, sessions: &[PowerSession]) -> Vec<DailyUsage> {
+    let mut dates: Vec<NaiveDate> = history.samples.iter().map(|sample| sample.timestamp.date_naive()).collect();
+    for session in sessions {
+        let mut date = session.start.date_naive();
+        while date <= session.end.date_naive() {
+            dates.push(date);
+            date = date.succ_opt().unwrap_or(NaiveDate::MAX);
+        }
+    }
+    dates.sort();
+    dates.dedup();
+
+    dates
+        .into_iter()
+        .map(|date| {
+            let (day_start, day_end) = (start_of_day(date), start_of_day(date + Duration::days(1)));
+            let mut usage = DailyUsage {
+                date,
+                battery_minutes: 0,
+                charge_minutes: 0,
+                energy_wh: None,
+                min_percentage: None,
+                max_percentage: None,
+            };
+
+            for session in sessions {
+                let overlap = (session.end.min(day_end) - session.start.max(day_start)).num_seconds();
+                if overlap <= 0 {
+                    continue;
+                }
+                let minutes = (overlap / 60) as u32;
+                match session.kind {
+                    SessionKind::Discharge => {
+                        usage.battery_minutes += minutes;
+                        let total = (session.end - session.start).num_seconds() as f32;
+                        if let Some(energy) = session.energy_wh {
+                            *usage.energy_wh.get_or_insert(0.0) += energy * overlap as f32 / total;
+                        }
+                    }
+                    SessionKind::Charge => usage.charge_minutes += minutes,
+                }
+            }
+
+            let percentages = history
+                .samples
+                .iter()
+                .filter(|sample| sample.timestamp.date_naive() == date)
+                .map(|sample| sample.battery_percentage);
+            for percentage in percentages {
+                usage.min_percentage = Some(usage.min_percentage.map_or(percentage, |min| min.min(percentage)));
+                usage.max_percentage = Some(usage.max_percentage.map_or(percentage, |max| max.max(percentage)));
+            }
+            usage
+        })
+        .collect()
+}
+
+/// 用已结束的放电估算续航：功率和耗电速度按放电时长加权
+fn estimate_runtime(sessions: &[PowerSession], health: &HealthReport) -> RuntimeEstimate {
+    let discharges: Vec<&PowerSession> = sessions
+        .iter()
+        .filter(|session| {
+            session.kind == SessionKind::Discharge
+                && !session.ongoing
+                && session.duration_minutes >= MIN_RUNTIME_SESSION_MINUTES
+        })
+        .collect();
+
+    let weighted = |value: fn(&PowerSession) -> Option<f32>| {
+        let (sum, weight) = discharges
+            .iter()
+            .filter_map(|session| Some((value(session)?, session.duration_minutes as f32)))
+            .fold((0.0, 0.0), |(sum, weight), (value, minutes)| (sum + value * minutes, weight + minutes));
+        (weight > 0.0).then(|| sum / weight)
+    };
+    let average_watts = weighted(|session| session.average_watts).filter(|watts| *watts > 0.0);
+    let drain_percent_per_hour = weighted(|session| session.percent_per_hour).filter(|rate| *rate > 0.0);
+
+    let latest = health.records.last();
+    let current = health.current.as_ref();
+    let full_charge = current
+        .and_then(|health| health.full_charge_capacity_mwh)
+        .or(latest.map(|record| record.full_charge_capacity_mwh));
+    let design = current
+        .and_then(|health| health.design_capacity_mwh)
+        .or(latest.and_then(|record| record.design_capacity_mwh));
+    let hours = |capacity_mwh: Option<u32>| Some(capacity_mwh? as f32 / 1000.0 / average_watts?);
+
+    RuntimeEstimate {
+        session_count: discharges.len(),
+        average_watts,
+        drain_percent_per_hour,
+        full_charge_hours: hours(full_charge).or(drain_percent_per_hour.map(|rate| 100.0 / rate)),
+        design_capacity_hours: hours(design),
+    }
+}
+
+/// 当天 0 点（夏令时跳过 0 点时取之后最早的时间）
+fn start_of_day(date: NaiveDate) -> DateTime<Local> {
+    let midnight = date.and_time(Default::default());
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
+fn date_value(date: NaiveDate) -> f64 {
+    start_of_day(date).timestamp() as f64
+}
+
+/// 简单的 SVG 折线图，横轴为时间
+fn line_chart(html: &mut String, points: &[(f64, f64)], (low, high): (f64, f64), unit: &str) {
+    const WIDTH: f64 = 720.0;
+    const HEIGHT: f64 = 200.0;
+    const MARGIN: f64 = 36.0;
+
+    if points.len() < 2 || high <= low {
+        return;
+    }
+    let (first, last) = (points[0].0, points[points.len() - 1].0);
+    let span = (last - first).max(1.0);
+    let x = |value: f64| MARGIN + (value - first) / span * (WIDTH - 2.0 * MARGIN);
+    let y = |value: f64| HEIGHT - MARGIN - (value.clamp(low, high) - low) / (high - low) * (HEIGHT - 2.0 * MARGIN);
+
+    let step = points.len().div_ceil(MAX_CHART_POINTS);
+    let polyline: Vec<String> = points
+        .iter()
+        .step_by(step)
+        .chain((!(points.len() - 1).is_multiple_of(step)).then(|| &points[points.len() - 1]))  // 保留最后一个点
+        .map(|(px, py)| format!("{:.1},{:.1}", x(*px), y(*py)))
+        .collect();
+    let _ = writeln!(
+        html,
+        "<svg class=\"chart\" viewBox=\"0 0 {w} {h}\" xmlns=\"http://www.w3.org/2000/svg\">\
+         <line x1=\"{m}\" y1=\"{top}\" x2=\"{m}\" y2=\"{bottom}\" class=\"axis\"/>\
+         <line x1=\"{m}\" y1=\"{bottom}\" x2=\"{right}\" y2=\"{bottom}\" class=\"axis\"/>\
+         <text x=\"4\" y=\"{top_label}\">{high}{unit}</text><text x=\"4\" y=\"{bottom}\">{low}{unit}</text>\
+         <polyline points=\"{points}\"/></svg>",
+        w = WIDTH,
+        h = HEIGHT,
+        m = MARGIN,
+        top = MARGIN,
+        bottom = HEIGHT - MARGIN,
+        right = WIDTH - MARGIN,
+        top_label = MARGIN + 4.0,
+        high = high,
+        low = low,
+        unit = unit,
+        points = polyline.join(" ")
+    );
+}
+
+fn table_row(html: &mut String, name: &str, value: &str) {
+    let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", name, value);
+}
+
+fn optional(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".to_string())
+}
+
+fn format_capacity(mwh: u32) -> String {
+    format!("{:.1} Wh", mwh as f32 / 1000.0)
+}
+
+fn format_minutes(minutes: u32) -> String {
+    if minutes >= 60 {
+        format!("{}h{}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const REPORT_STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", Roboto, sans-serif; color: #333; max-width: 960px; margin: 24px auto; padding: 0 16px; }\
+h1 { color: #667eea; } h2 { margin-top: 32px; border-bottom: 2px solid #667eea; padding-bottom: 4px; }\
+.meta, .empty { color: #6c757d; }\
+table { border-collapse: collapse; width: 100%; font-size: 14px; }\
+th, td { text-align: left; padding: 6px 8px; border-bottom: 1px solid #eee; }\
+th { color: #555; font-weight: 600; }\
+.warning { color: #dc3545; font-weight: 600; }\
+.chart { width: 100%; height: auto; margin: 8px 0 16px; }\
+.chart polyline { fill: none; stroke: #667eea; stroke-width: 2; }\
+.chart .axis { stroke: #ccc; } .chart text { font-size: 11px; fill: #6c757d; }";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{EventRecord, HistoryConfig, PowerSample, WearConfig, WearRecord};
+    use crate::power::PowerEvent;
+    use crate::power::test_support::at;
+
+    /// 两天的合成历史：9 月 1 日 20 点到 9 月 2 日 0 点放电 4 小时（10W），之后充电 1 小时
+    fn synthetic_history() -> PowerHistory {
+        let sample = |timestamp, battery_percentage, is_ac_connected: bool| PowerSample {
+            timestamp,
+            battery_percentage,
+            is_ac_connected,
+            is_charging: is_ac_connected,
+            power_draw_watts: (!is_ac_connected).then_some(10.0),
+            charge_rate_watts: is_ac_connected.then_some(30.0),
+            remaining_time_minutes: None,
+            battery_capacity_mwh: None,
+        };
+        let event = |timestamp, event, battery_percentage| EventRecord { timestamp, event, battery_percentage };
+        PowerHistory {
+            samples: vec![
+                sample(at(1, 20, 0), 100, false),
+                sample(at(1, 22, 0), 80, false),
+                sample(at(2, 0, 0), 60, true),
+                sample(at(2, 1, 0), 90, true),
+            ],
+            events: vec![
+                event(at(1, 20, 0), PowerEvent::AcDisconnected, 100),
+                event(at(2, 0, 0), PowerEvent::AcConnected, 60),
+                event(at(2, 1, 0), PowerEvent::FullyCharged, 90),
+            ],
+        }
+    }
+
+    fn synthetic_health() -> HealthReport {
+        let current = BatteryHealth {
+            design_capacity_mwh: Some(50000),
+            full_charge_capacity_mwh: Some(40000),
+            cycle_count: Some(420),
+            manufacturer: Some("ACME <Power>".to_string()),
+            ..Default::default()
+        }
+        .with_wear_level();
+        let records = (0..10)
+            .map(|i| WearRecord {
+                date: NaiveDate::from_ymd_opt(2025, 8, 20).unwrap() + Duration::days(i),
+                design_capacity_mwh: Some(50000),
+                full_charge_capacity_mwh: 40100 - i as u32 * 10,
+                cycle_count: Some(410 + i as u32),
+            })
+            .collect();
+        HealthReport::new(Some(current), records, &WearConfig::default())
+    }
+
+    fn report() -> BatteryReport {
+        BatteryReport::build(&synthetic_history(), synthetic_health(), (at(1, 0, 0), at(3, 0, 0)), at(3, 0, 0))
+    }
+
+    #[test]
+    fn test_usage_and_runtime() {
+        let report = report();
+        assert_eq!(report.sessions.len(), 2);
+
+        // 放电在 9 月 1 日，充电在 9 月 2 日
+        assert_eq!(report.usage.len(), 2);
+        assert_eq!(report.usage[0].battery_minutes, 240);
+        assert_eq!(report.usage[0].energy_wh, Some(40.0));
+        assert_eq!((report.usage[0].min_percentage, report.usage[0].max_percentage), (Some(80), Some(100)));
+        assert_eq!(report.usage[1].charge_minutes, 60);
+        assert_eq!(report.usage[1].energy_wh, None);
+
+        let runtime = &report.runtime;
+        assert_eq!(runtime.session_count, 1);
+        assert_eq!(runtime.average_watts, Some(10.0));
+        assert_eq!(runtime.drain_percent_per_hour, Some(10.0));
+        assert_eq!(runtime.full_charge_hours, Some(4.0));
+        assert_eq!(runtime.design_capacity_hours, Some(5.0));
+    }
+
+    #[test]
+    fn test_html_report() {
+        let html = report().to_html();
+        for section in ["电池健康", "容量历史", "近期使用", "充放电记录", "续航估算"] {
+            assert!(html.contains(&format!("<h2>{}</h2>", section)), "missing {}", section);
+        }
+        // 外部数据需要转义，页面不引用外部资源
+        assert!(html.contains("ACME &lt;Power&gt;"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src=\""));
+        assert!(html.contains("<polyline"));
+        assert!(html.contains("放电"));
+        assert!(html.contains("<span class=\"warning\">20.0%"));
+    }
+
+    #[test]
+    fn test_json_report_and_files() {
+        let report = report();
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["sessions"][0]["kind"], "discharge");
+        assert_eq!(json["runtime"]["session_count"], 1);
+        assert_eq!(json["health"]["current"]["cycle_count"], 420);
+        assert!(json.get("levels").is_none());
+
+        let dir = std::env::temp_dir().join(format!("isbattery-report-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (html_path, json_path) = report.write_files(&dir.join("battery-report.html")).unwrap();
+        assert_eq!(json_path, dir.join("battery-report.json"));
+        assert!(std::fs::read_to_string(html_path).unwrap().starts_with("<!DOCTYPE html>"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_report_uses_configured_wear_thresholds() {
+        let dir = std::env::temp_dir().join(format!("isbattery-report-config-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = HistoryConfig {
+            wear: WearConfig {
+                forecast_thresholds: vec![90, 85],
+                degraded_wear_percent: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let wear = WearTracker::open(&dir, config.wear.clone()).unwrap();
+        let store = HistoryStore::open(dir.clone(), config).unwrap();
+        for i in 0..10 {
+            let health = BatteryHealth {
+                design_capacity_mwh: Some(50000),
+                full_charge_capacity_mwh: Some(44000 - i * 10),
+                ..Default::default()
+            };
+            wear.update(&health, NaiveDate::from_ymd_opt(2025, 8, 20).unwrap() + Duration::days(i as i64)).unwrap();
+        }
+
+        let report = BatteryReport::from_stores(&store, &wear, None, (at(1, 0, 0), at(3, 0, 0)), at(3, 0, 0)).unwrap();
+        // 损耗约 12%，默认阈值 20% 不会提醒
+        assert_eq!(report.health.degraded_wear_percent, 10);
+        assert!(report.health.degraded);
+        let thresholds: Vec<u8> = report.health.trend.as_ref().unwrap().forecasts.iter().map(|f| f.threshold_percent).collect();
+        assert_eq!(thresholds, vec![90, 85]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_empty_history() {
+        let health = HealthReport::new(None, Vec::new(), &WearConfig::default());
+        let report = BatteryReport::build(&PowerHistory::default(), health, (at(1, 0, 0), at(2, 0, 0)), at(2, 0, 0));
+        assert!(report.usage.is_empty());
+        assert_eq!(report.runtime, RuntimeEstimate::default());
+        let html = report.to_html();
+        assert!(html.contains("还没有容量记录"));
+        assert!(html.contains("没有足够的放电记录"));
+    }
+}
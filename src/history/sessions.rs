@@ -0,0 +1,278 @@
+use crate::history::{HistoryStore, PowerHistory, PowerSample};
+use crate::power::PowerEvent;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+/// 查找最近一次充放电时回溯的天数
+pub const SESSION_LOOKBACK_DAYS: i64 = 7;
+
+/// 充放电类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    Charge,     // 接通电源到拔掉电源或充满
+    Discharge,  // 拔掉电源到接通电源
+}
+
+impl SessionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionKind::Charge => "充电",
+            SessionKind::Discharge => "放电",
+        }
+    }
+}
+
+/// 一次充电或放电
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerSession {
+    pub kind: SessionKind,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub start_percentage: u8,
+    pub end_percentage: u8,
+    pub duration_minutes: u32,
+    pub energy_wh: Option<f32>,         // 放电消耗或充电充入的能量（瓦时）
+    pub average_watts: Option<f32>,     // 平均功率（瓦特）
+    pub peak_watts: Option<f32>,        // 峰值功率（瓦特）
+    pub percent_per_hour: Option<f32>,  // 平均每小时电量变化（%），放电为耗电速度
+    pub ongoing: bool,                  // 仍在进行（还没有结束事件），结束时间为最后一次采样
+}
+
+impl PowerSession {
+    /// 托盘菜单中显示的摘要，例如 "上次放电：100% → 45%，3h12m，平均 8.5W"
+    pub fn summary(&self) -> String {
+        let hours = self.duration_minutes / 60;
+        let minutes = self.duration_minutes % 60;
+        let duration = if hours > 0 {
+            format!("{}h{}m", hours, minutes)
+        } else {
+            format!("{}m", minutes)
+        };
+
+        let mut text = format!(
+            "上次{}：{}% → {}%，{}",
+            self.kind.label(),
+            self.start_percentage,
+            self.end_percentage,
+            duration
+        );
+        if let Some(watts) = self.average_watts {
+            text.push_str(&format!("，平均 {:.1}W", watts));
+        }
+        text
+    }
+}
+
+/// 按电源事件把历史划分为充放电：拔掉电源开始放电，接通电源开始充电，
+/// 充满（`FullyCharged`）结束充电；历史开头处于某次充放电中间时从第一次采样开始。
+pub fn sessions(history: &PowerHistory) -> Vec<PowerSession> {
+    let mut result = Vec::new();
+    let mut open: Option<(SessionKind, DateTime<Local>, u8)> = history.samples.first().and_then(|first| {
+        let before_first_event = history.events.first().is_none_or(|event| first.timestamp < event.timestamp);
+        let kind = match (first.is_ac_connected, first.is_charging) {
+            (false, _) => Some(SessionKind::Discharge),
+            (true, true) => Some(SessionKind::Charge),
+            (true, false) => None,  // 接通电源但未充电（已充满）
+        };
+        kind.filter(|_| before_first_event)
+            .map(|kind| (kind, first.timestamp, first.battery_percentage))
+    });
+
+    for record in &history.events {
+        let next = match record.event {
+            PowerEvent::AcDisconnected => Some(SessionKind::Discharge),
+            PowerEvent::AcConnected => Some(SessionKind::Charge),
+            PowerEvent::FullyCharged if matches!(open, Some((SessionKind::Charge, ..))) => None,
+            _ => continue,
+        };
+        if let Some((kind, start, start_percentage)) = open.take() {
+            result.push(summarize(
+                kind,
+                (start, record.timestamp),
+                (start_percentage, record.battery_percentage),
+                &history.samples,
+                false,
+            ));
+        }
+        open = next.map(|kind| (kind, record.timestamp, record.battery_percentage));
+    }
+
+    if let Some((kind, start, start_percentage)) = open {
+        let last = history.samples.last().filter(|sample| sample.timestamp >= start);
+        let (end, end_percentage) = last.map_or((start, start_percentage), |sample| (sample.timestamp, sample.battery_percentage));
+        result.push(summarize(kind, (start, end), (start_percentage, end_percentage), &history.samples, true));
+    }
+    result
+}
+
+/// 最近 `SESSION_LOOKBACK_DAYS` 天内最后一次已经结束的充放电
+pub fn last_completed_session(store: &HistoryStore, now: DateTime<Local>) -> Result<Option<PowerSession>, String> {
+    let history = store.query(now - Duration::days(SESSION_LOOKBACK_DAYS), now + Duration::seconds(1))?;
+    Ok(sessions(&history).into_iter().rev().find(|session| !session.ongoing))
+}
+
+/// 计算一次充放电的统计值，`samples` 为全部采样（按时间排序），功率只统计 `[start, end)` 内的采样
+fn summarize(
+    kind: SessionKind,
+    (start, end): (DateTime<Local>, DateTime<Local>),
+    (start_percentage, end_percentage): (u8, u8),
+    samples: &[PowerSample],
+    ongoing: bool,
+) -> PowerSession {
+    let first = samples.partition_point(|sample| sample.timestamp < start);
+    let last = samples.partition_point(|sample| sample.timestamp < end).max(first);
+    // 结束时刻的采样属于下一次充放电，但可以用来计算容量变化
+    let boundary = samples.partition_point(|sample| sample.timestamp <= end).max(last);
+    let capacity_samples = &samples[first..boundary];
+    let samples = &samples[first..last];
+
+    // 放电取系统功耗，充电取充电速率
+    let watts = |sample: &PowerSample| match kind {
+        SessionKind::Discharge => sample.power_draw_watts.or(sample.charge_rate_watts.map(f32::abs)),
+        SessionKind::Charge => sample.charge_rate_watts.map(f32::abs).or(sample.power_draw_watts),
+    };
+
+    // 按采样持续时间加权：每个采样的功率持续到下一次采样（最后一个持续到结束）
+    let mut energy_wh = 0.0;
+    let mut measured_hours = 0.0;
+    let mut peak_watts: Option<f32> = None;
+    for (index, sample) in samples.iter().enumerate() {
+        let Some(watts) = watts(sample) else { continue };
+        let until = samples.get(index + 1).map_or(end, |next| next.timestamp);
+        let hours = (until - sample.timestamp).num_seconds().max(0) as f32 / 3600.0;
+        energy_wh += watts * hours;
+        measured_hours += hours;
+        peak_watts = Some(peak_watts.map_or(watts, |peak| peak.max(watts)));
+    }
+
+    // 剩余容量可用时按容量变化计算能量，比功率积分更准确
+    let capacity_change = match (capacity_samples.first(), capacity_samples.last()) {
+        (Some(first), Some(last)) => match (first.battery_capacity_mwh, last.battery_capacity_mwh) {
+            (Some(from), Some(to)) if from != to => Some(from.abs_diff(to) as f32 / 1000.0),
+            _ => None,
+        },
+        _ => None,
+    };
+    let integrated = (measured_hours > 0.0).then_some(energy_wh);
+
+    let duration_hours = (end - start).num_seconds().max(0) as f32 / 3600.0;
+    PowerSession {
+        kind,
+        start,
+        end,
+        start_percentage,
+        end_percentage,
+        duration_minutes: ((end - start).num_seconds().max(0) / 60) as u32,
+        energy_wh: capacity_change.or(integrated),
+        average_watts: integrated.map(|energy| energy / measured_hours),
+        peak_watts,
+        percent_per_hour: (duration_hours > 0.0)
+            .then(|| start_percentage.abs_diff(end_percentage) as f32 / duration_hours),
+        ongoing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::EventRecord;
+    use crate::power::test_support::at;
+
+    fn sample(timestamp: DateTime<Local>, battery_percentage: u8, is_ac_connected: bool, watts: f32) -> PowerSample {
+        PowerSample {
+            timestamp,
+            battery_percentage,
+            is_ac_connected,
+            is_charging: is_ac_connected && battery_percentage < 100,
+            power_draw_watts: (!is_ac_connected).then_some(watts),
+            charge_rate_watts: is_ac_connected.then_some(watts),
+            remaining_time_minutes: None,
+            battery_capacity_mwh: None,
+        }
+    }
+
+    fn event(timestamp: DateTime<Local>, event: PowerEvent, battery_percentage: u8) -> EventRecord {
+        EventRecord { timestamp, event, battery_percentage }
+    }
+
+    #[test]
+    fn test_discharge_and_charge_sessions() {
+        let history = PowerHistory {
+            samples: vec![
+                sample(at(1, 9, 0), 100, false, 10.0),
+                sample(at(1, 10, 0), 90, false, 20.0),
+                sample(at(1, 11, 0), 70, true, 30.0),
+                sample(at(1, 11, 30), 100, true, 0.0),
+            ],
+            events: vec![
+                event(at(1, 9, 0), PowerEvent::AcDisconnected, 100),
+                event(at(1, 11, 0), PowerEvent::AcConnected, 70),
+                event(at(1, 11, 30), PowerEvent::FullyCharged, 100),
+            ],
+        };
+
+        let sessions = sessions(&history);
+        assert_eq!(sessions.len(), 2);
+
+        let discharge = &sessions[0];
+        assert_eq!(discharge.kind, SessionKind::Discharge);
+        assert_eq!((discharge.start_percentage, discharge.end_percentage), (100, 70));
+        assert_eq!(discharge.duration_minutes, 120);
+        // 10W 持续 1 小时 + 20W 持续 1 小时
+        assert_eq!(discharge.energy_wh, Some(30.0));
+        assert_eq!(discharge.average_watts, Some(15.0));
+        assert_eq!(discharge.peak_watts, Some(20.0));
+        assert_eq!(discharge.percent_per_hour, Some(15.0));
+        assert!(!discharge.ongoing);
+
+        let charge = &sessions[1];
+        assert_eq!(charge.kind, SessionKind::Charge);
+        assert_eq!((charge.start_percentage, charge.end_percentage), (70, 100));
+        assert_eq!(charge.energy_wh, Some(15.0));
+        assert_eq!(charge.percent_per_hour, Some(60.0));
+        assert_eq!(charge.summary(), "上次充电：70% → 100%，30m，平均 30.0W");
+    }
+
+    #[test]
+    fn test_ongoing_session_from_first_sample() {
+        // 历史从放电中途开始，且还没有接通电源
+        let history = PowerHistory {
+            samples: vec![sample(at(1, 9, 0), 80, false, 12.0), sample(at(1, 10, 30), 65, false, 12.0)],
+            events: Vec::new(),
+        };
+
+        let sessions = sessions(&history);
+        assert_eq!(sessions.len(), 1);
+        assert!(sessions[0].ongoing);
+        assert_eq!(sessions[0].end, at(1, 10, 30));
+        assert_eq!(sessions[0].end_percentage, 65);
+        assert_eq!(sessions[0].summary(), "上次放电：80% → 65%，1h30m，平均 12.0W");
+    }
+
+    #[test]
+    fn test_capacity_change_preferred_for_energy() {
+        let mut samples = vec![sample(at(1, 9, 0), 80, false, 10.0), sample(at(1, 10, 0), 60, false, 10.0)];
+        samples[0].battery_capacity_mwh = Some(40_000);
+        samples[1].battery_capacity_mwh = Some(28_000);
+        let history = PowerHistory {
+            samples,
+            events: vec![event(at(1, 9, 0), PowerEvent::AcDisconnected, 80), event(at(1, 10, 0), PowerEvent::AcConnected, 60)],
+        };
+
+        let sessions = sessions(&history);
+        assert_eq!(sessions[0].energy_wh, Some(12.0));
+        assert_eq!(sessions[0].average_watts, Some(10.0));
+        // 接通电源后的充电还在进行
+        assert!(sessions[1].ongoing);
+    }
+
+    #[test]
+    fn test_idle_on_ac_has_no_session() {
+        let history = PowerHistory {
+            samples: vec![sample(at(1, 9, 0), 100, true, 0.0)],
+            events: Vec::new(),
+        };
+        assert!(sessions(&history).is_empty());
+    }
+}
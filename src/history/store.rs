@@ -0,0 +1,364 @@
+use crate::history::{HistoryRecord, PowerHistory, PowerSample, WearConfig};
+use crate::power::MonitorEvent;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 历史记录参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    pub enabled: bool,
+    pub sample_interval_secs: u64,  // 电量和电源状态不变时两次采样的最短间隔（秒）
+    pub full_resolution_days: u32,  // 保留全部采样的天数，更早的采样按 downsample_minutes 降采样
+    pub downsample_minutes: u32,    // 降采样后每多少分钟保留一个采样
+    pub retention_days: u32,        // 保留历史的天数，0 表示永久保留
+    pub wear: WearConfig,           // 电池损耗跟踪（[history.wear]）
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_interval_secs: 60,
+            full_resolution_days: 7,
+            downsample_minutes: 15,
+            retention_days: 365,
+            wear: WearConfig::default(),
+        }
+    }
+}
+
+impl HistoryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sample_interval_secs == 0 {
+            return Err("历史采样间隔不能为0".to_string());
+        }
+        if self.downsample_minutes == 0 || self.downsample_minutes > 24 * 60 {
+            return Err("历史降采样间隔必须在1到1440分钟之间".to_string());
+        }
+        if self.retention_days != 0 && self.retention_days < self.full_resolution_days {
+            return Err("历史保留天数不能少于完整采样保留天数".to_string());
+        }
+        self.wear.validate()
+    }
+}
+
+/// 一次清理的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionSummary {
+    pub removed_days: usize,      // 超过保留天数被删除的天数
+    pub downsampled_days: usize,  // 本次降采样的天数
+}
+
+/// 电源历史记录
+///
+/// 历史保存在配置目录的 `history/` 下，每天一个 JSON Lines 文件（`YYYY-MM-DD.jsonl`），
+/// 每行是一条采样或事件，只追加写入；文件名即按日期的索引，按时间范围查询时只读取相关日期的文件。
+/// 写入中断导致的不完整行在读取时跳过。
+pub struct HistoryStore {
+    dir: PathBuf,
+    config: Mutex<HistoryConfig>,
+    last_sample: Mutex<Option<PowerSample>>,  // 上一次写入的采样，用于按采样间隔节流
+    write_lock: Mutex<()>,                    // 追加写入与降采样改写互斥
+}
+
+impl HistoryStore {
+    pub fn open(dir: PathBuf, config: HistoryConfig) -> Result<Self, String> {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create history directory {}: {}", dir.display(), e))?;
+        Ok(Self {
+            dir,
+            config: Mutex::new(config),
+            last_sample: Mutex::new(None),
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    /// 历史目录的默认位置（与配置文件同目录）
+    pub fn default_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(crate::config::AppConfig::get_config_path()?.with_file_name("history"))
+    }
+
+    pub fn config(&self) -> HistoryConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: HistoryConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// 记录一个监控事件：电源事件总是记录，状态采样按 `sample_interval_secs` 节流
+    pub fn record(&self, event: &MonitorEvent, now: DateTime<Local>) -> Result<(), String> {
+        let config = self.config();
+        if !config.enabled {
+            return Ok(());
+        }
+
+        if let Some(record) = HistoryRecord::event(event, now) {
+            self.append(&record)?;
+        }
+
+        let sample = PowerSample::from_status(&event.current_status, now);
+        let mut last_sample = self.last_sample.lock().unwrap();
+        let due = match last_sample.as_ref() {
+            Some(last) => {
+                sample.differs_from(last)
+                    || now - last.timestamp >= Duration::seconds(config.sample_interval_secs as i64)
+            }
+            None => true,
+        };
+        if due {
+            self.append(&HistoryRecord::Sample(sample.clone()))?;
+            *last_sample = Some(sample);
+        }
+        Ok(())
+    }
+
+    /// 追加一条记录到所在日期的文件
+    pub fn append(&self, record: &HistoryRecord) -> Result<(), String> {
+        let line = serde_json::to_string(record).map_err(|e| format!("Failed to serialize history record: {}", e))?;
+        let path = self.day_path(record.timestamp().date_naive());
+
+        let _guard = self.write_lock.lock().unwrap();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// 查询 `[from, to)` 范围内的采样和事件
+    pub fn query(&self, from: DateTime<Local>, to: DateTime<Local>) -> Result<PowerHistory, String> {
+        let mut history = PowerHistory::default();
+        if from >= to {
+            return Ok(history);
+        }
+
+        let last_day = to.date_naive();
+        for date in from.date_naive().iter_days().take_while(|date| *date <= last_day) {
+            for record in self.read_day(date)? {
+                let timestamp = record.timestamp();
+                if timestamp >= from && timestamp < to {
+                    history.push(record);
+                }
+            }
+        }
+        Ok(history)
+    }
+
+    /// 删除超过保留天数的历史，并对超过完整采样保留天数的历史降采样（事件全部保留）
+    pub fn apply_retention(&self, now: DateTime<Local>) -> Result<RetentionSummary, String> {
+        let config = self.config();
+        let today = now.date_naive();
+        let bucket = Duration::minutes(config.downsample_minutes as i64);
+        let mut summary = RetentionSummary::default();
+
+        for (date, path) in self.day_files()? {
+            let age = (today - date).num_days();
+            if config.retention_days > 0 && age >= config.retention_days as i64 {
+                fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+                summary.removed_days += 1;
+            } else if age >= config.full_resolution_days as i64 {
+                let _guard = self.write_lock.lock().unwrap();
+                let records = Self::read_file(&path)?;
+                let count = records.len();
+                let records = downsample(records, bucket);
+                if records.len() < count {
+                    Self::rewrite(&path, &records)?;
+                    summary.downsampled_days += 1;
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    fn day_path(&self, date: NaiveDate) -> PathBuf {
+        self.dir.join(format!("{}.jsonl", date.format("%Y-%m-%d")))
+    }
+
+    /// 历史目录中的日期文件，按日期排序
+    fn day_files(&self) -> Result<Vec<(NaiveDate, PathBuf)>, String> {
+        let entries = fs::read_dir(&self.dir).map_err(|e| format!("Failed to read {}: {}", self.dir.display(), e))?;
+        let mut files: Vec<(NaiveDate, PathBuf)> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "jsonl"))
+            .filter_map(|path| {
+                let stem = path.file_stem()?.to_str()?;
+                let date = NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok()?;
+                Some((date, path))
+            })
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
+    fn read_day(&self, date: NaiveDate) -> Result<Vec<HistoryRecord>, String> {
+        let path = self.day_path(date);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let _guard = self.write_lock.lock().unwrap();
+        Self::read_file(&path)
+    }
+
+    fn read_file(path: &Path) -> Result<Vec<HistoryRecord>, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut skipped = 0;
+        let records = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                let record = serde_json::from_str::<HistoryRecord>(line).ok();
+                skipped += record.is_none() as usize;
+                record
+            })
+            .collect();
+        if skipped > 0 {
+            crate::log_error!("Skipped {} invalid history records in {}", skipped, path.display());
+        }
+        Ok(records)
+    }
+
+    fn rewrite(path: &Path, records: &[HistoryRecord]) -> Result<(), String> {
+        let mut content = String::new();
+        for record in records {
+            let line = serde_json::to_string(record).map_err(|e| format!("Failed to serialize history record: {}", e))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+        // 先写入临时文件再替换，改写中断时不会丢失原有历史
+        let temp_path = path.with_extension("jsonl.tmp");
+        fs::write(&temp_path, content).map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+}
+
+/// 每个 `bucket` 时间段只保留第一个采样；电源或充电状态变化的采样和所有事件都保留
+pub fn downsample(records: Vec<HistoryRecord>, bucket: Duration) -> Vec<HistoryRecord> {
+    let bucket_secs = bucket.num_seconds().max(1);
+    let mut last_kept: Option<(i64, bool, bool)> = None;
+
+    records
+        .into_iter()
+        .filter(|record| match record {
+            HistoryRecord::Event(_) => true,
+            HistoryRecord::Sample(sample) => {
+                let key = (
+                    sample.timestamp.timestamp().div_euclid(bucket_secs),
+                    sample.is_ac_connected,
+                    sample.is_charging,
+                );
+                let keep = last_kept != Some(key);
+                if keep {
+                    last_kept = Some(key);
+                }
+                keep
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::{at, event, status};
+    use crate::power::PowerEvent;
+
+    fn temp_store(name: &str, config: HistoryConfig) -> HistoryStore {
+        let dir = std::env::temp_dir().join(format!("isbattery-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        HistoryStore::open(dir, config).unwrap()
+    }
+
+    fn sample(timestamp: DateTime<Local>, is_ac_connected: bool) -> HistoryRecord {
+        HistoryRecord::Sample(PowerSample::from_status(&status(is_ac_connected, 50), timestamp))
+    }
+
+    #[test]
+    fn test_record_and_query() {
+        let store = temp_store("record", HistoryConfig::default());
+
+        let mut disconnected = event(PowerEvent::AcDisconnected, false, 80);
+        disconnected.current_status.power_draw_watts = Some(12.5);
+        store.record(&disconnected, at(1, 10, 0)).unwrap();
+        // 电量不变时按采样间隔节流，电量变化时立即采样
+        store.record(&event(PowerEvent::StatusUpdate, false, 80), at(1, 10, 0) + Duration::seconds(20)).unwrap();
+        store.record(&event(PowerEvent::StatusUpdate, false, 79), at(1, 10, 0) + Duration::seconds(40)).unwrap();
+        store.record(&event(PowerEvent::StatusUpdate, false, 79), at(1, 10, 2)).unwrap();
+        store.record(&event(PowerEvent::AcConnected, true, 60), at(2, 9, 0)).unwrap();
+
+        let history = store.query(at(1, 0, 0), at(3, 0, 0)).unwrap();
+        assert_eq!(history.samples.len(), 4);
+        assert_eq!(history.samples[0].power_draw_watts, Some(12.5));
+        assert_eq!(history.events.len(), 2);
+        assert_eq!(history.events[1].event, PowerEvent::AcConnected);
+        assert_eq!(history.events[1].battery_percentage, 60);
+
+        // 时间范围包含开始、不包含结束
+        let history = store.query(at(1, 10, 2), at(2, 9, 0)).unwrap();
+        assert_eq!(history.samples.len(), 1);
+        assert!(history.events.is_empty());
+        assert!(store.query(at(2, 9, 0), at(1, 0, 0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_disabled_store_records_nothing() {
+        let config = HistoryConfig { enabled: false, ..Default::default() };
+        let store = temp_store("disabled", config);
+        store.record(&event(PowerEvent::AcDisconnected, false, 80), at(1, 10, 0)).unwrap();
+        assert!(store.query(at(1, 0, 0), at(2, 0, 0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_lines_skipped() {
+        let store = temp_store("invalid", HistoryConfig::default());
+        store.append(&sample(at(1, 10, 0), false)).unwrap();
+
+        let path = store.day_path(at(1, 10, 0).date_naive());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"sample\":{{\"timestamp\":").unwrap();
+
+        assert_eq!(store.query(at(1, 0, 0), at(2, 0, 0)).unwrap().samples.len(), 1);
+    }
+
+    #[test]
+    fn test_retention_and_downsampling() {
+        let config = HistoryConfig { full_resolution_days: 2, retention_days: 10, ..Default::default() };
+        let store = temp_store("retention", config);
+
+        // 9 月 1 日超过保留天数；9 月 5 日降采样；9 月 10 日保留全部采样
+        for day in [1, 5, 10] {
+            for minute in 0..30 {
+                store.append(&sample(at(day, 10, minute), minute >= 20)).unwrap();
+            }
+        }
+        let disconnected = HistoryRecord::event(&event(PowerEvent::AcDisconnected, false, 50), at(5, 10, 3)).unwrap();
+        store.append(&disconnected).unwrap();
+
+        let summary = store.apply_retention(at(11, 12, 0)).unwrap();
+        assert_eq!(summary, RetentionSummary { removed_days: 1, downsampled_days: 1 });
+
+        assert!(store.query(at(1, 0, 0), at(2, 0, 0)).unwrap().is_empty());
+        // 10:00、10:15 各保留一个，10:20 接通电源的采样单独保留
+        let history = store.query(at(5, 0, 0), at(6, 0, 0)).unwrap();
+        let minutes: Vec<_> = history.samples.iter().map(|sample| sample.timestamp).collect();
+        assert_eq!(minutes, vec![at(5, 10, 0), at(5, 10, 15), at(5, 10, 20)]);
+        assert_eq!(history.events.len(), 1);
+        assert_eq!(store.query(at(10, 0, 0), at(11, 0, 0)).unwrap().samples.len(), 30);
+
+        // 已经降采样的文件不会再次改写
+        assert_eq!(store.apply_retention(at(11, 12, 0)).unwrap(), RetentionSummary::default());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(HistoryConfig::default().validate().is_ok());
+        assert!(HistoryConfig { downsample_minutes: 0, ..Default::default() }.validate().is_err());
+        assert!(HistoryConfig { retention_days: 3, ..Default::default() }.validate().is_err());
+        assert!(HistoryConfig { retention_days: 0, ..Default::default() }.validate().is_ok());
+    }
+}
@@ -0,0 +1,349 @@
+use crate::power::{BatteryHealth, PowerEvent};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 拟合趋势至少需要覆盖的天数，天数太少时日常波动会掩盖损耗
+pub const MIN_TREND_DAYS: i64 = 7;
+/// 预测日期的最远范围（年），更远的视为不会达到
+const MAX_FORECAST_YEARS: i64 = 20;
+
+/// 电池损耗跟踪参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WearConfig {
+    pub enabled: bool,
+    pub forecast_thresholds: Vec<u8>,  // 预测满充容量降到设计容量的这些百分比的日期
+    pub degraded_wear_percent: u8,     // 损耗达到该值时触发 BatteryDegraded 事件
+}
+
+impl Default for WearConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            forecast_thresholds: vec![80, 60],
+            degraded_wear_percent: 20,
+        }
+    }
+}
+
+impl WearConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.forecast_thresholds.iter().any(|threshold| *threshold == 0 || *threshold >= 100) {
+            return Err("损耗预测阈值必须在1到99%之间".to_string());
+        }
+        if self.degraded_wear_percent == 0 || self.degraded_wear_percent >= 100 {
+            return Err("电池损耗提醒阈值必须在1到99%之间".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 某一天的电池容量记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WearRecord {
+    pub date: NaiveDate,
+    pub design_capacity_mwh: Option<u32>,  // 设计容量（毫瓦时）
+    pub full_charge_capacity_mwh: u32,     // 满充容量（毫瓦时）
+    pub cycle_count: Option<u32>,          // 循环次数
+}
+
+impl WearRecord {
+    /// 没有满充容量时无法记录
+    pub fn from_health(health: &BatteryHealth, date: NaiveDate) -> Option<Self> {
+        Some(Self {
+            date,
+            design_capacity_mwh: health.design_capacity_mwh,
+            full_charge_capacity_mwh: health.full_charge_capacity_mwh?,
+            cycle_count: health.cycle_count,
+        })
+    }
+
+    /// 损耗百分比，没有设计容量时为 `None`
+    pub fn wear_percent(&self) -> Option<f32> {
+        BatteryHealth::wear_level(self.design_capacity_mwh, Some(self.full_charge_capacity_mwh))
+    }
+
+    /// 满充容量占设计容量的百分比
+    pub fn health_percent(&self) -> Option<f32> {
+        self.wear_percent().map(|wear| 100.0 - wear)
+    }
+}
+
+/// 满充容量降到某个百分比的预测
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WearForecast {
+    pub threshold_percent: u8,
+    pub date: Option<NaiveDate>,  // 已达到时为首次达到的日期；容量没有下降趋势时为 None
+    pub reached: bool,
+}
+
+/// 容量和循环次数的变化趋势（最小二乘直线拟合）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WearTrend {
+    pub health_percent_per_year: f32,     // 每年满充容量百分比的变化，损耗时为负数
+    pub cycles_per_month: Option<f32>,    // 每月增加的循环次数
+    pub forecasts: Vec<WearForecast>,
+}
+
+/// 电池健康报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub current: Option<BatteryHealth>,  // 当前读取的健康信息
+    pub records: Vec<WearRecord>,        // 每天的容量记录，按日期排序
+    pub trend: Option<WearTrend>,        // 记录不足 MIN_TREND_DAYS 天时为 None
+    pub degraded_wear_percent: u8,
+    pub degraded: bool,                  // 当前损耗是否已达到提醒阈值
+}
+
+impl HealthReport {
+    pub fn new(current: Option<BatteryHealth>, records: Vec<WearRecord>, config: &WearConfig) -> Self {
+        let wear = current
+            .as_ref()
+            .and_then(|health| health.wear_level_percent)
+            .or_else(|| records.last().and_then(WearRecord::wear_percent));
+        Self {
+            trend: fit_trend(&records, &config.forecast_thresholds),
+            current,
+            records,
+            degraded_wear_percent: config.degraded_wear_percent,
+            degraded: wear.is_some_and(|wear| wear >= config.degraded_wear_percent as f32),
+        }
+    }
+}
+
+/// 按天记录满充容量和循环次数
+///
+/// 记录保存在历史目录的 `wear.jsonl` 中，每天一行。这些数据很少，
+/// 不参与 `[history]` 的保留和降采样，以便观察多年的损耗趋势。
+pub struct WearTracker {
+    path: PathBuf,
+    config: Mutex<WearConfig>,
+    write_lock: Mutex<()>,
+}
+
+impl WearTracker {
+    pub fn open(dir: &Path, config: WearConfig) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create history directory {}: {}", dir.display(), e))?;
+        Ok(Self {
+            path: dir.join("wear.jsonl"),
+            config: Mutex::new(config),
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    pub fn config(&self) -> WearConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: WearConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// 所有记录，按日期排序；无法解析的行被跳过
+    pub fn records(&self) -> Result<Vec<WearRecord>, String> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {}", self.path.display(), e)),
+        };
+        let mut records: Vec<WearRecord> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    crate::log_error!("Skipping invalid wear record in {}: {}", self.path.display(), e);
+                    None
+                }
+            })
+            .collect();
+        records.sort_by_key(|record| record.date);
+        Ok(records)
+    }
+
+    /// 记录当天的健康信息（每天只记录第一次），损耗向上越过提醒阈值时返回 `BatteryDegraded`
+    pub fn update(&self, health: &BatteryHealth, today: NaiveDate) -> Result<Option<PowerEvent>, String> {
+        let config = self.config();
+        if !config.enabled {
+            return Ok(None);
+        }
+        let Some(record) = WearRecord::from_health(health, today) else {
+            return Ok(None);
+        };
+
+        let _guard = self.write_lock.lock().unwrap();
+        let records = self.records()?;
+        let previous = records.last();
+        if previous.is_some_and(|previous| previous.date >= today) {
+            return Ok(None);
+        }
+
+        let line = serde_json::to_string(&record).map_err(|e| format!("Failed to serialize wear record: {}", e))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+
+        Ok(degradation_event(previous, &record, config.degraded_wear_percent))
+    }
+}
+
+/// 损耗从低于阈值变为达到阈值时的事件；第一次记录就已达到阈值时也提醒一次
+fn degradation_event(previous: Option<&WearRecord>, current: &WearRecord, limit: u8) -> Option<PowerEvent> {
+    let limit = limit as f32;
+    let wear_percent = current.wear_percent().filter(|wear| *wear >= limit)?;
+    let previously_degraded = previous
+        .and_then(WearRecord::wear_percent)
+        .is_some_and(|wear| wear >= limit);
+    (!previously_degraded).then_some(PowerEvent::BatteryDegraded { wear_percent })
+}
+
+/// 拟合满充容量百分比随时间的直线，并预测降到各阈值的日期
+pub fn fit_trend(records: &[WearRecord], thresholds: &[u8]) -> Option<WearTrend> {
+    let first = records.first()?.date;
+    let points: Vec<(f64, f64)> = records
+        .iter()
+        .filter_map(|record| Some(((record.date - first).num_days() as f64, record.health_percent()? as f64)))
+        .collect();
+    let (slope, intercept) = least_squares(&points)?;
+    let span = points.last()?.0 - points.first()?.0;
+    if span < MIN_TREND_DAYS as f64 {
+        return None;
+    }
+
+    let forecasts = thresholds
+        .iter()
+        .map(|&threshold| {
+            let reached_on = records
+                .iter()
+                .find(|record| record.health_percent().is_some_and(|health| health <= threshold as f32))
+                .map(|record| record.date);
+            let date = reached_on.or_else(|| {
+                if slope >= 0.0 {
+                    return None;
+                }
+                let days = ((threshold as f64 - intercept) / slope).round() as i64;
+                (days <= MAX_FORECAST_YEARS * 365)
+                    .then(|| first.checked_add_signed(Duration::days(days)))
+                    .flatten()
+            });
+            WearForecast {
+                threshold_percent: threshold,
+                date,
+                reached: reached_on.is_some(),
+            }
+        })
+        .collect();
+
+    let cycles: Vec<(f64, f64)> = records
+        .iter()
+        .filter_map(|record| Some(((record.date - first).num_days() as f64, record.cycle_count? as f64)))
+        .collect();
+
+    Some(WearTrend {
+        health_percent_per_year: (slope * 365.0) as f32,
+        cycles_per_month: least_squares(&cycles).map(|(slope, _)| (slope * 30.0) as f32),
+        forecasts,
+    })
+}
+
+/// 最小二乘直线 y = slope * x + intercept，至少需要两个不同的 x
+fn least_squares(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let n = points.len() as f64;
+    if points.len() < 2 {
+        return None;
+    }
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let slope = covariance / variance;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(offset: i64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap() + Duration::days(offset)
+    }
+
+    fn record(offset: i64, full_charge_capacity_mwh: u32, cycle_count: u32) -> WearRecord {
+        WearRecord {
+            date: day(offset),
+            design_capacity_mwh: Some(50000),
+            full_charge_capacity_mwh,
+            cycle_count: Some(cycle_count),
+        }
+    }
+
+    #[test]
+    fn test_trend_forecast() {
+        // 每 100 天损失 1%（500 mWh），每 10 天 3 次循环
+        let records: Vec<WearRecord> = (0..=4)
+            .map(|i| record(i * 100, 45000 - i as u32 * 500, 300 + i as u32 * 30))
+            .collect();
+        let trend = fit_trend(&records, &[90, 80, 60]).unwrap();
+
+        assert!((trend.health_percent_per_year + 3.65).abs() < 0.01);
+        assert!((trend.cycles_per_month.unwrap() - 9.0).abs() < 0.01);
+
+        // 第一天即为 90%
+        assert_eq!(trend.forecasts[0].date, Some(day(0)));
+        assert!(trend.forecasts[0].reached);
+        // 降到 80% 还需要 1000 天
+        assert_eq!(trend.forecasts[1].date, Some(day(1000)));
+        assert!(!trend.forecasts[1].reached);
+        assert_eq!(trend.forecasts[2].date, Some(day(3000)));
+    }
+
+    #[test]
+    fn test_no_trend_without_enough_days_or_decline() {
+        assert!(fit_trend(&[record(0, 45000, 1), record(3, 44900, 2)], &[80]).is_none());
+
+        let stable = [record(0, 45000, 1), record(30, 45100, 2)];
+        let trend = fit_trend(&stable, &[80]).unwrap();
+        assert_eq!(trend.forecasts[0].date, None);
+        assert!(!trend.forecasts[0].reached);
+    }
+
+    #[test]
+    fn test_daily_records_and_degradation_event() {
+        let dir = std::env::temp_dir().join(format!("isbattery-wear-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let tracker = WearTracker::open(&dir, WearConfig::default()).unwrap();
+        let health = |full| BatteryHealth {
+            design_capacity_mwh: Some(50000),
+            full_charge_capacity_mwh: Some(full),
+            ..Default::default()
+        };
+
+        assert_eq!(tracker.update(&health(41000), day(0)).unwrap(), None);
+        // 同一天只记录一次
+        assert_eq!(tracker.update(&health(39000), day(0)).unwrap(), None);
+        assert_eq!(tracker.records().unwrap().len(), 1);
+
+        // 损耗越过 20% 时提醒一次
+        assert_eq!(
+            tracker.update(&health(39500), day(1)).unwrap(),
+            Some(PowerEvent::BatteryDegraded { wear_percent: 21.0 })
+        );
+        assert_eq!(tracker.update(&health(39000), day(2)).unwrap(), None);
+
+        let report = HealthReport::new(None, tracker.records().unwrap(), &tracker.config());
+        assert!(report.degraded);
+        assert_eq!(report.records.len(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
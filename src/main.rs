@@ -6,63 +6,116 @@ mod config;
 mod audio;
 mod utils;
 mod ui;
+mod policy;
+mod history;
 
 use config::ConfigManager;
-use power::{PowerMonitor, MonitorEvent, PowerEvent, PowerDetector};
-use audio::AudioManager;
+use power::{PowerMonitor, MonitorEvent, MonitorHandle, EventBus, PowerEvent, PowerDetector, PowerSource, SimulatedPowerSource, LevelSound, default_power_source};
+use audio::{AudioManager, AlertSoundType};
 use ui::{TrayManager, AlertManager};
-use utils::{StartupManager, init_logger};
+use utils::{StartupManager, init_logger, attach_parent_console, CliOptions, ExportRequest, ReportRequest};
+use history::{
+    BatteryReport, ExportFormat, ExportOptions, HealthReport, HistoryConfig, HistoryStore, PowerHistory, PowerSession, WearTracker,
+    SESSION_LOOKBACK_DAYS, export_history, export_to_file, last_completed_session, sessions,
+};
+use policy::{AlertPolicy, CriticalActionController, CriticalDecision, EventKind, RuleAction, Snooze, SnoozeDuration, SnoozeManager, SystemActionExecutor, run_external_action};
 
+use chrono::{DateTime, Local};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{
-    AppHandle, Manager, WindowEvent, State
+    Manager, WindowEvent, State
 };
-use tokio::sync::mpsc;
 
 #[derive(Clone)]
 struct AppState {
     config_manager: ConfigManager,
+    detector: PowerDetector,
     audio_manager: Arc<Mutex<AudioManager>>,
     tray_manager: Arc<Mutex<TrayManager>>,
     alert_manager: Arc<Mutex<AlertManager>>,
     startup_manager: Arc<Mutex<StartupManager>>,
-    monitoring_receiver: Arc<Mutex<Option<mpsc::Receiver<MonitorEvent>>>>,
+    monitor: Arc<Mutex<Option<MonitorHandle>>>,
+    event_bus: EventBus,  // 监控事件总线，重启监控后订阅者保持不变
+    snooze_manager: Arc<SnoozeManager>,
+    alert_policy: Arc<AlertPolicy>,  // 静音时段与暂停提醒，决定事件使用哪些提醒渠道
+    critical_actions: Arc<Mutex<CriticalActionController>>,  // 电量即将耗尽时的自动操作
+    history: Arc<HistoryStore>,  // 电源历史记录
+    wear: Arc<WearTracker>,  // 每天的电池容量记录
 }
 
+/// 刷新托盘中暂停提醒剩余时间的间隔
+const SNOOZE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// 检查到期重复提醒的间隔
+const ESCALATION_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+/// 检查自动操作倒计时是否到期的间隔
+const CRITICAL_ACTION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// 清理过期历史、降采样的间隔
+const HISTORY_RETENTION_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 读取电池健康信息、记录当天容量的间隔（每天只记录一次）
+const WEAR_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// 导出电源历史时未指定开始时间默认导出的天数
+const EXPORT_DEFAULT_DAYS: i64 = 7;
+/// 生成电池报告时未指定开始时间默认统计的天数
+const REPORT_DEFAULT_DAYS: i64 = 30;
+
 impl AppState {
-    fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    fn new(detector: PowerDetector) -> Result<Self, Box<dyn std::error::Error>> {
         let config_manager = ConfigManager::new()?;
         let startup_manager = StartupManager::new()?;
         
         let monitoring_config = config_manager.get_monitoring_config();
         let audio_manager = AudioManager::new(monitoring_config.sound_enabled);
+        let snooze_manager = Arc::new(SnoozeManager::load(SnoozeManager::default_path()?));
+        let alert_policy = AlertPolicy::new(Arc::clone(&snooze_manager), &monitoring_config);
+        let mut alert_manager = AlertManager::new();
+        alert_manager.set_escalation_config(monitoring_config.escalation.clone());
+        let critical_actions = CriticalActionController::new(
+            monitoring_config.critical_action.clone(),
+            Arc::new(SystemActionExecutor)
+        );
+        let history_config = config_manager.get_history_config();
+        let history_dir = HistoryStore::default_dir()?;
+        let wear = WearTracker::open(&history_dir, history_config.wear.clone())?;
+        let history = HistoryStore::open(history_dir, history_config)?;
 
         Ok(Self {
             config_manager,
+            detector,
             audio_manager: Arc::new(Mutex::new(audio_manager)),
             tray_manager: Arc::new(Mutex::new(TrayManager::new())),
-            alert_manager: Arc::new(Mutex::new(AlertManager::new())),
+            alert_manager: Arc::new(Mutex::new(alert_manager)),
             startup_manager: Arc::new(Mutex::new(startup_manager)),
-            monitoring_receiver: Arc::new(Mutex::new(None)),
+            monitor: Arc::new(Mutex::new(None)),
+            event_bus: EventBus::default(),
+            snooze_manager,
+            alert_policy: Arc::new(alert_policy),
+            critical_actions: Arc::new(Mutex::new(critical_actions)),
+            history: Arc::new(history),
+            wear: Arc::new(wear),
         })
     }
 
-    async fn start_monitoring(&self, app_handle: AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    async fn start_monitoring(&self) -> Result<(), Box<dyn std::error::Error>> {
         // 获取监控配置
         let monitoring_config = self.config_manager.get_monitoring_config();
+
+        // 同一时间只运行一个监控循环：先停止之前的循环
+        let previous = self.monitor.lock().unwrap().take();
+        if let Some(previous) = previous {
+            previous.stop().await;
+        }
         
         // 创建新的监控器
-        let monitor = PowerMonitor::new(
-            monitoring_config.check_interval,
-            monitoring_config.low_battery_threshold
-        );
+        let monitor = PowerMonitor::from_config(self.detector.clone(), &monitoring_config)
+            .with_event_bus(self.event_bus.clone());
 
-        // 启动监控并获取接收器
-        let receiver = monitor.start_monitoring().await;
+        // 启动监控
+        let handle = monitor.start().await;
         
         {
-            let mut receiver_guard = self.monitoring_receiver.lock().unwrap();
-            *receiver_guard = Some(receiver);
+            let mut monitor_guard = self.monitor.lock().unwrap();
+            *monitor_guard = Some(handle);
         }
 
         // 更新托盘状态
@@ -71,17 +124,13 @@ impl AppState {
             tray_manager.update_monitoring_status(true);
         }
 
-        // 启动事件处理任务
-        self.spawn_event_handler(app_handle).await;
-
         Ok(())
     }
 
-    async fn stop_monitoring(&self) {
-        // 清除接收器
-        {
-            let mut receiver_guard = self.monitoring_receiver.lock().unwrap();
-            *receiver_guard = None;
+    async fn pause_monitoring(&self) {
+        // 暂停监控循环（保留检测状态，恢复后立即检测一次）
+        if let Some(handle) = self.monitor.lock().unwrap().as_ref() {
+            handle.pause();
         }
 
         // 更新托盘状态
@@ -90,6 +139,9 @@ impl AppState {
             tray_manager.update_monitoring_status(false);
         }
 
+        // 暂停监控后不再更新电量，取消正在进行的自动操作倒计时
+        self.critical_actions.lock().unwrap().cancel();
+
         // 关闭所有提醒窗口
         {
             let mut alert_manager = self.alert_manager.lock().unwrap();
@@ -97,177 +149,605 @@ impl AppState {
         }
     }
 
-    async fn spawn_event_handler(&self, app_handle: AppHandle) {
-        let audio_manager = Arc::clone(&self.audio_manager);
+    async fn resume_monitoring(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let handle = self.monitor.lock().unwrap().clone();
+        match handle {
+            Some(handle) if handle.is_running() => {
+                handle.resume();
+
+                // 更新托盘状态
+                let tray_manager = self.tray_manager.lock().unwrap();
+                tray_manager.update_monitoring_status(true);
+                Ok(())
+            }
+            // 监控循环已经结束（或从未启动），重新启动
+            _ => self.start_monitoring().await,
+        }
+    }
+
+    /// 暂停提醒，同时关闭已经打开的提醒窗口
+    fn snooze_alerts(&self, duration: SnoozeDuration) -> Snooze {
+        let snooze = self.snooze_manager.snooze(duration, Local::now());
+        log_info!("Alerts snoozed: {:?}", snooze.until);
+
+        {
+            let mut alert_manager = self.alert_manager.lock().unwrap();
+            let _ = alert_manager.close_all_alerts();
+        }
+
+        let tray_manager = self.tray_manager.lock().unwrap();
+        tray_manager.update_snooze(Some(snooze));
+        snooze
+    }
+
+    /// 取消暂停提醒
+    fn cancel_snooze(&self) {
+        if self.snooze_manager.cancel() {
+            log_info!("Alert snooze cancelled");
+        }
+
+        let tray_manager = self.tray_manager.lock().unwrap();
+        tray_manager.update_snooze(None);
+    }
+
+    /// 托盘、提醒窗口和声音各自订阅监控事件，某一个处理缓慢时不会阻塞其他订阅者
+    ///
+    /// 每个订阅者向提醒策略查询事件应该执行的动作（提醒规则 + 静音时段 + 暂停提醒），只执行自己负责的部分。
+    fn spawn_event_subscribers(&self) {
+        let mut tray_events = self.event_bus.subscribe("tray");
         let tray_manager = Arc::clone(&self.tray_manager);
+        let alert_policy = Arc::clone(&self.alert_policy);
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = tray_events.recv().await {
+                let actions = alert_policy.actions(&event, Local::now());
+                Self::handle_tray_event(&event, &tray_manager, &actions);
+            }
+        });
+
+        let mut alert_events = self.event_bus.subscribe("alerts");
         let alert_manager = Arc::clone(&self.alert_manager);
-        let monitoring_receiver = Arc::clone(&self.monitoring_receiver);
         let config_manager = self.config_manager.clone();
+        let alert_policy = Arc::clone(&self.alert_policy);
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = alert_events.recv().await {
+                let actions = alert_policy.actions(&event, Local::now());
+                Self::handle_alert_event(&event, &config_manager, &alert_manager, &actions);
+            }
+        });
 
-        tokio::spawn(async move {
-            while let Some(mut receiver) = {
-                let mut guard = monitoring_receiver.lock().unwrap();
-                guard.take()
-            } {
-                while let Some(event) = receiver.recv().await {
-                    Self::handle_power_event(
-                        &event,
-                        &config_manager,
-                        &audio_manager,
-                        &tray_manager,
-                        &alert_manager,
-                        &app_handle
-                    ).await;
+        let mut audio_events = self.event_bus.subscribe("audio");
+        let audio_manager = Arc::clone(&self.audio_manager);
+        let alert_policy = Arc::clone(&self.alert_policy);
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = audio_events.recv().await {
+                if alert_policy.actions(&event, Local::now()).contains(&RuleAction::Sound) {
+                    Self::handle_audio_event(&event, &audio_manager);
                 }
+            }
+        });
 
-                // 将接收器放回去
-                {
-                    let mut guard = monitoring_receiver.lock().unwrap();
-                    *guard = Some(receiver);
+        // 提醒规则中的外部命令和 Webhook，每个动作单独运行，不阻塞后续事件
+        let mut rule_events = self.event_bus.subscribe("rules");
+        let alert_policy = Arc::clone(&self.alert_policy);
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = rule_events.recv().await {
+                let actions = alert_policy.actions(&event, Local::now());
+                for action in actions.into_iter().filter(RuleAction::is_external) {
+                    let event = event.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = run_external_action(&action, &event).await {
+                            log_error!("Alert rule action failed: {}", e);
+                        }
+                    });
                 }
             }
         });
-    }
 
-    async fn handle_power_event(
-        event: &MonitorEvent,
-        config_manager: &ConfigManager,
-        audio_manager: &Arc<Mutex<AudioManager>>,
-        tray_manager: &Arc<Mutex<TrayManager>>,
-        alert_manager: &Arc<Mutex<AlertManager>>,
-        _app_handle: &AppHandle
-    ) {
-        let current_status = &event.current_status;
-        let power_event = &event.power_event;
+        // 电源历史：记录状态采样和电源事件，定期清理过期历史；充放电结束时更新托盘中的摘要
+        let mut history_events = self.event_bus.subscribe("history");
+        let history = Arc::clone(&self.history);
+        let tray_manager = Arc::clone(&self.tray_manager);
+        tauri::async_runtime::spawn(async move {
+            let mut retention = tokio::time::interval(HISTORY_RETENTION_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = retention.tick() => match history.apply_retention(Local::now()) {
+                        Ok(summary) if summary.removed_days + summary.downsampled_days > 0 => {
+                            log_info!("History retention: removed {} days, downsampled {} days", summary.removed_days, summary.downsampled_days);
+                        }
+                        Ok(_) => {}
+                        Err(e) => log_error!("Failed to apply history retention: {}", e),
+                    },
+                    event = history_events.recv() => match event {
+                        Some(event) => {
+                            if let Err(e) = history.record(&event, Local::now()) {
+                                log_error!("Failed to record power history: {}", e);
+                            }
+                            if matches!(event.power_event, PowerEvent::AcConnected | PowerEvent::AcDisconnected | PowerEvent::FullyCharged) {
+                                Self::refresh_last_session(&history, &tray_manager);
+                            }
+                        }
+                        None => break,
+                    },
+                }
+            }
+        });
 
-        // 更新托盘状态
-        {
-            let tray_manager = tray_manager.lock().unwrap();
-            tray_manager.update_status(current_status);
-        }
+        // 暂停提醒：接通电源时结束“直到接通电源”的暂停，定时刷新托盘中的剩余时间
+        let mut snooze_events = self.event_bus.subscribe("snooze");
+        let tray_manager = Arc::clone(&self.tray_manager);
+        let snooze_manager = Arc::clone(&self.snooze_manager);
+        tauri::async_runtime::spawn(async move {
+            let mut refresh = tokio::time::interval(SNOOZE_REFRESH_INTERVAL);
+            let mut previous: Option<Snooze> = None;
 
-        // 更新已打开的提醒窗口中的电量信息
-        {
-            let alert_manager = alert_manager.lock().unwrap();
-            if let Err(e) = alert_manager.update_battery_percentage(current_status.battery_percentage) {
-                log_error!("Failed to update battery percentage in alert windows: {}", e);
+            loop {
+                let ended_by_plug_in = tokio::select! {
+                    _ = refresh.tick() => false,
+                    event = snooze_events.recv() => match event {
+                        Some(event) => snooze_manager.on_power_event(&event.power_event),
+                        None => break,
+                    },
+                };
+
+                let now = Local::now();
+                let snooze = snooze_manager.current(now);
+                // 用户手动取消时不提示，只有到期或接通电源结束暂停时提示
+                let expired = previous.is_some_and(|previous| previous.is_expired(now)) && snooze.is_none();
+
+                let tray = tray_manager.lock().unwrap();
+                tray.update_snooze(snooze);
+                if ended_by_plug_in || expired {
+                    log_info!("Alert snooze ended");
+                    tray.show_notification("电源提醒", "暂停提醒已结束，恢复电源提醒");
+                }
+                previous = snooze;
             }
-        }
+        });
+    }
+
+    /// 未确认的低电量提醒按升级参数重复响铃并提到最前
+    fn spawn_escalation_task(&self) {
+        let alert_manager = Arc::clone(&self.alert_manager);
+        let audio_manager = Arc::clone(&self.audio_manager);
+        let alert_policy = Arc::clone(&self.alert_policy);
+        tauri::async_runtime::spawn(async move {
+            let mut check = tokio::time::interval(ESCALATION_CHECK_INTERVAL);
+            loop {
+                check.tick().await;
+                let now = Local::now();
+                let mut alert_manager = alert_manager.lock().unwrap();
+
+                for step in alert_manager.due_escalations(now) {
+                    // 静音时段和暂停提醒同样限制重复提醒，严重低电量除外
+                    let channels = alert_policy.channels(&step.event, now);
+                    log_info!("Escalating alert {} (repeat {}, volume {:.2})", step.alert_id, step.step, step.volume);
 
-        // 处理不同类型的电源事件
-        match power_event {
-            PowerEvent::AcDisconnected => {
-                log_info!("AC power disconnected, battery: {}%", current_status.battery_percentage);
-                
-                // 显示电源断开提醒
-                {
-                    let mut alert_manager = alert_manager.lock().unwrap();
-                    if let Err(e) = alert_manager.show_power_disconnected_alert(current_status) {
-                        log_error!("Failed to show power disconnected alert: {}", e);
+                    if channels.window {
+                        alert_manager.focus_alert(&step.alert_id);
+                    }
+                    if channels.sound {
+                        let sound_type = match &step.event {
+                            PowerEvent::BatteryLow { level, .. } => match &level.sound {
+                                LevelSound::None => continue,
+                                LevelSound::System => AlertSoundType::SystemWarning,
+                                LevelSound::File(path) => AlertSoundType::CustomFile(path.clone()),
+                            },
+                            _ => AlertSoundType::SystemWarning,
+                        };
+                        let audio_manager = audio_manager.lock().unwrap();
+                        if let Err(e) = audio_manager.play_alert_at_volume(sound_type, step.volume) {
+                            log_error!("Failed to play escalation sound: {}", e);
+                        }
                     }
                 }
+            }
+        });
+    }
 
-                // 播放提醒音
-                {
-                    let audio_manager = audio_manager.lock().unwrap();
-                    if let Err(e) = audio_manager.play_power_disconnected_alert() {
-                        log_error!("Failed to play alert sound: {}", e);
+    /// 每天记录一次满充容量和循环次数，损耗越过提醒阈值时发布 `BatteryDegraded` 事件
+    fn spawn_wear_task(&self) {
+        let detector = self.detector.clone();
+        let wear = Arc::clone(&self.wear);
+        let event_bus = self.event_bus.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut check = tokio::time::interval(WEAR_CHECK_INTERVAL);
+            loop {
+                check.tick().await;
+                let health = match detector.battery_health() {
+                    Ok(Some(health)) => health,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log_error!("Failed to read battery health: {}", e);
+                        continue;
                     }
+                };
+                match wear.update(&health, Local::now().date_naive()) {
+                    Ok(Some(power_event)) => {
+                        log_info!("Battery degraded: {}", power_event);
+                        match detector.get_power_status() {
+                            Ok(current_status) => {
+                                event_bus.publish(MonitorEvent { power_event, current_status });
+                            }
+                            Err(e) => log_error!("Failed to get power status for {}: {}", power_event, e),
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => log_error!("Failed to record battery wear: {}", e),
                 }
+            }
+        });
+    }
 
-                // 显示托盘通知
-                {
-                    let tray_manager = tray_manager.lock().unwrap();
-                    tray_manager.show_notification(
-                        "电源提醒",
-                        &format!("电源已断开，当前电量：{}%", current_status.battery_percentage)
-                    );
+    /// 电量即将耗尽时倒计时执行自动操作（休眠、关机等），不受静音时段和暂停提醒限制
+    fn spawn_critical_action_task(&self) {
+        let mut critical_events = self.event_bus.subscribe("critical");
+        let critical_actions = Arc::clone(&self.critical_actions);
+        let alert_manager = Arc::clone(&self.alert_manager);
+        let tray_manager = Arc::clone(&self.tray_manager);
+        tauri::async_runtime::spawn(async move {
+            let mut check = tokio::time::interval(CRITICAL_ACTION_CHECK_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = check.tick() => {
+                        let result = critical_actions.lock().unwrap().tick(Local::now());
+                        match result {
+                            Some(Ok(action)) => {
+                                log_info!("Critical battery action executed: {:?}", action);
+                                let _ = alert_manager.lock().unwrap().close_alert("critical_action");
+                            }
+                            Some(Err(e)) => {
+                                log_error!("Critical battery action failed: {}", e);
+                                let _ = alert_manager.lock().unwrap().close_alert("critical_action");
+                                tray_manager.lock().unwrap().show_notification("自动操作失败", &e);
+                            }
+                            None => {}
+                        }
+                    }
+                    event = critical_events.recv() => {
+                        let Some(event) = event else { break };
+                        let now = Local::now();
+                        let decision = critical_actions.lock().unwrap().on_status(&event.current_status, now);
+                        match decision {
+                            CriticalDecision::CountdownStarted { action, deadline } => {
+                                log_info!("Critical battery action {:?} scheduled at {}", action, deadline.format("%H:%M:%S"));
+                                let countdown_secs = (deadline - now).num_seconds().max(0) as u32;
+                                let mut alert_manager = alert_manager.lock().unwrap();
+                                if let Err(e) = alert_manager.show_critical_action_countdown(&event.current_status, &action, countdown_secs) {
+                                    log_error!("Failed to show critical action countdown: {}", e);
+                                }
+                            }
+                            CriticalDecision::CountdownCancelled => {
+                                log_info!("Critical battery action cancelled: power restored");
+                                let _ = alert_manager.lock().unwrap().close_alert("critical_action");
+                            }
+                            CriticalDecision::Idle => {}
+                        }
+                    }
                 }
             }
+        });
+    }
+
+    /// 在托盘中显示最近一次已结束的充放电
+    fn refresh_last_session(history: &HistoryStore, tray_manager: &Arc<Mutex<TrayManager>>) {
+        match last_completed_session(history, Local::now()) {
+            Ok(session) => tray_manager.lock().unwrap().update_last_session(session.as_ref()),
+            Err(e) => log_error!("Failed to load last power session: {}", e),
+        }
+    }
+
+    fn handle_tray_event(event: &MonitorEvent, tray_manager: &Arc<Mutex<TrayManager>>, actions: &[RuleAction]) {
+        let current_status = &event.current_status;
+        let tray_manager = tray_manager.lock().unwrap();
+
+        // 更新托盘状态
+        tray_manager.update_status(current_status);
+
+        if event.power_event == PowerEvent::StatusUpdate {
+            return;
+        }
+        log_info!("Power event: {}, battery: {}%", event.power_event, current_status.battery_percentage);
+
+        // 暂停提醒或静音时段（off）内只记录日志，不显示托盘通知
+        if actions.contains(&RuleAction::Notification) {
+            if let Some((title, message)) = Self::notification_text(event) {
+                tray_manager.show_notification(title, &message);
+            }
+        }
+    }
+
+    /// 事件的通知标题和内容，同时用于规则触发的通用提醒窗口
+    fn notification_text(event: &MonitorEvent) -> Option<(&'static str, String)> {
+        let current_status = &event.current_status;
+        let text = match &event.power_event {
+            PowerEvent::AcDisconnected => (
+                "电源提醒",
+                format!("电源已断开，当前电量：{}%", current_status.battery_percentage)
+            ),
+            PowerEvent::AcConnected => ("电源提醒", "电源已连接".to_string()),
+            PowerEvent::BatteryLow { level, percentage } => (
+                "电量不足",
+                format!("{}（当前电量：{}%）", level.message, percentage)
+            ),
+            PowerEvent::BatteryNormal(percentage) => (
+                "电源提醒",
+                format!("电池电量恢复正常：{}%", percentage)
+            ),
+            PowerEvent::CriticalBattery(percentage) => (
+                "电量严重不足",
+                format!("电池电量仅剩 {}%，请立即连接电源！", percentage)
+            ),
+            PowerEvent::RapidDrain { watts_or_pct_per_min } => (
+                "耗电过快",
+                format!("当前耗电速度：{}，当前电量：{}%", watts_or_pct_per_min, current_status.battery_percentage)
+            ),
+            PowerEvent::ChargingStalled(percentage) => (
+                "充电异常",
+                format!("电源已连接但电池未在充电，当前电量：{}%", percentage)
+            ),
+            PowerEvent::FullyCharged => ("电池已充满", "电池已充满，可以断开电源".to_string()),
+            PowerEvent::BatteryRemoved => ("电源提醒", "检测不到电池".to_string()),
+            PowerEvent::BatteryInserted => (
+                "电源提醒",
+                format!("已检测到电池，当前电量：{}%", current_status.battery_percentage)
+            ),
+            PowerEvent::DeviceBatteryLow { name, percentage, .. } => (
+                "外设电量不足",
+                format!("{} 电量：{}%，请及时充电", name, percentage)
+            ),
+            PowerEvent::DeviceBatteryNormal { name, percentage, .. } => (
+                "外设电量恢复",
+                format!("{} 电量：{}%", name, percentage)
+            ),
+            PowerEvent::BatteryDegraded { wear_percent } => (
+                "电池损耗",
+                format!("电池损耗已达 {:.1}%，建议安排更换电池", wear_percent)
+            ),
+            PowerEvent::StatusUpdate => return None,
+        };
+        Some(text)
+    }
+
+    fn handle_alert_event(
+        event: &MonitorEvent,
+        config_manager: &ConfigManager,
+        alert_manager: &Arc<Mutex<AlertManager>>,
+        actions: &[RuleAction]
+    ) {
+        let current_status = &event.current_status;
+        let mut alert_manager = alert_manager.lock().unwrap();
+
+        // 更新已打开的提醒窗口中的电量信息
+        if let Err(e) = alert_manager.update_battery_percentage(current_status.battery_percentage) {
+            log_error!("Failed to update battery percentage in alert windows: {}", e);
+        }
+
+        // 关闭已经不再需要的提醒窗口（不受提醒规则影响）
+        match &event.power_event {
             PowerEvent::AcConnected => {
-                log_info!("AC power connected, battery: {}%", current_status.battery_percentage);
-                
+                // 接通电源后不再重复提醒
+                alert_manager.stop_escalations();
+
                 // 如果设置了自动关闭提醒，则关闭相关提醒窗口
                 let monitoring_config = config_manager.get_monitoring_config();
                 if monitoring_config.auto_close_alert {
-                    let mut alert_manager = alert_manager.lock().unwrap();
                     let _ = alert_manager.close_alert("power_disconnected");
                 }
-
-                // 显示托盘通知
-                {
-                    let tray_manager = tray_manager.lock().unwrap();
-                    tray_manager.show_notification(
-                        "电源提醒",
-                        "电源已连接"
-                    );
-                }
             }
-            PowerEvent::BatteryLow(percentage) => {
-                log_info!("Low battery warning: {}%", percentage);
-                
-                // 显示低电量提醒（优先级高，即使连接电源也显示）
-                {
-                    let mut alert_manager = alert_manager.lock().unwrap();
-                    if let Err(e) = alert_manager.show_low_battery_alert(current_status) {
-                        log_error!("Failed to show low battery alert: {}", e);
-                    }
-                }
+            PowerEvent::BatteryNormal(_) => {
+                // 关闭低电量提醒
+                let _ = alert_manager.close_alert("low_battery");
+            }
+            _ => {}
+        }
 
-                // 播放提醒音
-                {
-                    let audio_manager = audio_manager.lock().unwrap();
-                    if let Err(e) = audio_manager.play_low_battery_alert() {
-                        log_error!("Failed to play alert sound: {}", e);
-                    }
-                }
+        // 暂停提醒或静音时段内规则的窗口动作已被去掉
+        if !actions.contains(&RuleAction::Window) {
+            return;
+        }
 
-                // 显示托盘通知
-                {
-                    let tray_manager = tray_manager.lock().unwrap();
-                    tray_manager.show_notification(
-                        "电量不足",
-                        &format!("电池电量不足：{}%，请及时充电！", percentage)
-                    );
-                }
+        let result = match &event.power_event {
+            PowerEvent::AcDisconnected => alert_manager.show_power_disconnected_alert(current_status),
+            PowerEvent::BatteryLow { level, .. } => alert_manager.show_battery_level_alert(current_status, level),
+            // 严重不足时再次弹出低电量提醒（可全屏），即使之前的提醒已被关闭
+            PowerEvent::CriticalBattery(_) => alert_manager.show_critical_battery_alert(current_status),
+            power_event => match (EventKind::of(power_event), Self::notification_text(event)) {
+                (Some(kind), Some((_, message))) => alert_manager.show_event_alert(&kind.name(), current_status, &message),
+                _ => Ok(()),
+            },
+        };
+        match result {
+            // 低电量提醒在用户确认或接通电源之前重复提醒
+            Ok(()) if matches!(event.power_event, PowerEvent::BatteryLow { .. } | PowerEvent::CriticalBattery(_)) => {
+                alert_manager.escalate("low_battery", &event.power_event);
             }
-            PowerEvent::BatteryNormal(percentage) => {
-                log_info!("Battery level normal: {}%", percentage);
-                
-                // 关闭低电量提醒
-                {
-                    let mut alert_manager = alert_manager.lock().unwrap();
-                    let _ = alert_manager.close_alert("low_battery");
-                }
+            Ok(()) => {}
+            Err(e) => log_error!("Failed to show alert for {}: {}", event.power_event, e),
+        }
+    }
 
-                // 显示托盘通知
-                {
-                    let tray_manager = tray_manager.lock().unwrap();
-                    tray_manager.show_notification(
-                        "电源提醒",
-                        &format!("电池电量恢复正常：{}%", percentage)
-                    );
-                }
-            }
-            PowerEvent::StatusUpdate => {
-                // 状态更新事件，不需要特殊处理，因为托盘和提醒窗口已经更新
-                // log_info!("Status update: battery {}%", current_status.battery_percentage);
-            }
+    fn handle_audio_event(event: &MonitorEvent, audio_manager: &Arc<Mutex<AudioManager>>) {
+        let audio_manager = audio_manager.lock().unwrap();
+
+        // 播放提醒音
+        let result = match &event.power_event {
+            PowerEvent::AcDisconnected => audio_manager.play_power_disconnected_alert(),
+            // 播放该级别的提醒音
+            PowerEvent::BatteryLow { level, .. } => match &level.sound {
+                LevelSound::None => Ok(()),
+                LevelSound::System => audio_manager.play_low_battery_alert(),
+                LevelSound::File(path) => audio_manager.play_alert(AlertSoundType::CustomFile(path.clone())),
+            },
+            PowerEvent::CriticalBattery(_) => audio_manager.play_low_battery_alert(),
+            _ => audio_manager.play_alert(AlertSoundType::SystemWarning),
+        };
+        if let Err(e) = result {
+            log_error!("Failed to play alert sound: {}", e);
         }
     }
 }
 
 // Tauri 命令
+#[tauri::command]
+fn snooze_alerts(app_state: State<'_, AppState>, duration: SnoozeDuration) -> Result<Snooze, String> {
+    Ok(app_state.snooze_alerts(duration))
+}
+
+#[tauri::command]
+fn cancel_snooze(app_state: State<'_, AppState>) -> Result<(), String> {
+    app_state.cancel_snooze();
+    Ok(())
+}
+
+#[tauri::command]
+fn get_snooze_status(app_state: State<'_, AppState>) -> Result<Option<Snooze>, String> {
+    Ok(app_state.snooze_manager.current(Local::now()))
+}
+
+/// 查询 `[from, to)` 范围内的电源历史，默认最近 24 小时
+#[tauri::command]
+async fn get_power_history(
+    app_state: State<'_, AppState>,
+    from: Option<DateTime<Local>>,
+    to: Option<DateTime<Local>>
+) -> Result<PowerHistory, String> {
+    let to = to.unwrap_or_else(Local::now);
+    let from = from.unwrap_or(to - chrono::Duration::hours(24));
+    app_state.history.query(from, to)
+}
+
+/// 查询 `[from, to)` 范围内的充放电，默认最近 7 天
+#[tauri::command]
+async fn get_sessions(
+    app_state: State<'_, AppState>,
+    from: Option<DateTime<Local>>,
+    to: Option<DateTime<Local>>
+) -> Result<Vec<PowerSession>, String> {
+    let to = to.unwrap_or_else(Local::now);
+    let from = from.unwrap_or(to - chrono::Duration::days(SESSION_LOOKBACK_DAYS));
+    Ok(sessions(&app_state.history.query(from, to)?))
+}
+
+/// 选择保存位置并导出 `[from, to)` 范围内的电源历史（默认最近 7 天），格式以所选文件的扩展名为准；
+/// 返回保存的文件路径，用户取消时返回 `None`
+#[tauri::command]
+async fn export_power_history(
+    app_state: State<'_, AppState>,
+    from: Option<DateTime<Local>>,
+    to: Option<DateTime<Local>>,
+    options: Option<ExportOptions>
+) -> Result<Option<String>, String> {
+    let to = to.unwrap_or_else(Local::now);
+    let from = from.unwrap_or(to - chrono::Duration::days(EXPORT_DEFAULT_DAYS));
+    let mut options = options.unwrap_or_default();
+
+    let file_name = format!("isbattery-history-{}.{}", to.format("%Y%m%d"), options.format.extension());
+    let path = tauri::api::dialog::blocking::FileDialogBuilder::new()
+        .set_title("导出电源历史")
+        .set_file_name(&file_name)
+        .add_filter("CSV", &["csv"])
+        .add_filter("JSON Lines", &["jsonl"])
+        .save_file();
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    if let Some(format) = ExportFormat::from_path(&path) {
+        options.format = format;
+    }
+
+    let history = app_state.history.query(from, to)?;
+    let rows = export_to_file(&history, &options, &path)?;
+    log_info!("Exported {} history rows to {}", rows, path.display());
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+/// 选择保存位置并生成 `[from, to)` 范围内（默认最近 30 天）的电池报告，同名的 JSON 文件保存在同一目录；
+/// 返回 HTML 报告的路径，用户取消时返回 `None`
+#[tauri::command]
+async fn generate_battery_report(
+    app_state: State<'_, AppState>,
+    from: Option<DateTime<Local>>,
+    to: Option<DateTime<Local>>
+) -> Result<Option<String>, String> {
+    let to = to.unwrap_or_else(Local::now);
+    let from = from.unwrap_or(to - chrono::Duration::days(REPORT_DEFAULT_DAYS));
+
+    let file_name = format!("isbattery-report-{}.html", to.format("%Y%m%d"));
+    let path = tauri::api::dialog::blocking::FileDialogBuilder::new()
+        .set_title("生成电池报告")
+        .set_file_name(&file_name)
+        .add_filter("HTML", &["html"])
+        .save_file();
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    let report = build_battery_report(&app_state.history, &app_state.wear, &app_state.detector, from, to)?;
+    let (html_path, json_path) = report.write_files(&path)?;
+    log_info!("Battery report written to {} and {}", html_path.display(), json_path.display());
+    Ok(Some(html_path.to_string_lossy().to_string()))
+}
+
+/// 根据电源历史、容量记录和当前健康信息生成电池报告
+fn build_battery_report(
+    history: &HistoryStore,
+    wear: &WearTracker,
+    detector: &PowerDetector,
+    from: DateTime<Local>,
+    to: DateTime<Local>
+) -> Result<BatteryReport, String> {
+    let current = detector.battery_health().unwrap_or_else(|e| {
+        log_error!("Failed to read battery health for report: {}", e);
+        None
+    });
+    BatteryReport::from_stores(history, wear, current, (from, to), Local::now())
+}
+
+/// 命令行生成电池报告（`--report`），不启动界面，返回 HTML 报告的路径
+fn run_report(request: &ReportRequest) -> Result<std::path::PathBuf, String> {
+    let dir = HistoryStore::default_dir().map_err(|e| e.to_string())?;
+    let config = ConfigManager::new().map_err(|e| e.to_string())?.get_history_config();
+    let wear = WearTracker::open(&dir, config.wear.clone())?;
+    let store = HistoryStore::open(dir, config)?;
+
+    let to = request.to.unwrap_or_else(Local::now);
+    let from = request.from.unwrap_or(to - chrono::Duration::days(REPORT_DEFAULT_DAYS));
+    let report = build_battery_report(&store, &wear, &PowerDetector::new(), from, to)?;
+    report.write_files(&request.path).map(|(html_path, _)| html_path)
+}
+
+/// 命令行导出电源历史（`--export`），不启动界面
+fn run_export(request: &ExportRequest) -> Result<usize, String> {
+    let dir = HistoryStore::default_dir().map_err(|e| e.to_string())?;
+    let store = HistoryStore::open(dir, HistoryConfig::default())?;
+
+    let to = request.to.unwrap_or_else(Local::now);
+    let from = request.from.unwrap_or(to - chrono::Duration::days(EXPORT_DEFAULT_DAYS));
+    let history = store.query(from, to)?;
+
+    let options = ExportOptions {
+        format: request.format(),
+        columns: request.columns.clone(),
+        ..Default::default()
+    };
+    if request.is_stdout() {
+        export_history(&history, &options, &mut std::io::stdout().lock())
+    } else {
+        export_to_file(&history, &options, &request.path)
+    }
+}
+
 #[tauri::command]
 async fn pause_monitoring(app_state: State<'_, AppState>) -> Result<(), String> {
-    app_state.stop_monitoring().await;
+    app_state.pause_monitoring().await;
     log_info!("Monitoring paused by user");
     Ok(())
 }
 
 #[tauri::command]
-async fn resume_monitoring(app_handle: AppHandle, app_state: State<'_, AppState>) -> Result<(), String> {
-    app_state.start_monitoring(app_handle).await.map_err(|e| e.to_string())?;
+async fn resume_monitoring(app_state: State<'_, AppState>) -> Result<(), String> {
+    app_state.resume_monitoring().await.map_err(|e| e.to_string())?;
     log_info!("Monitoring resumed by user");
     Ok(())
 }
@@ -291,15 +771,30 @@ async fn toggle_startup(app_state: State<'_, AppState>) -> Result<bool, String>
 }
 
 #[tauri::command]
-async fn get_current_power_status() -> Result<power::BatteryStatus, String> {
-    let detector = PowerDetector::new();
-    detector.get_power_status()
+async fn get_current_power_status(app_state: State<'_, AppState>) -> Result<power::BatteryStatus, String> {
+    app_state.detector.get_power_status()
+}
+
+#[tauri::command]
+async fn get_power_devices(app_state: State<'_, AppState>) -> Result<Vec<power::PowerDevice>, String> {
+    app_state.detector.list_devices()
+}
+
+#[tauri::command]
+async fn get_battery_health(app_state: State<'_, AppState>) -> Result<Option<power::BatteryHealth>, String> {
+    app_state.detector.battery_health()
+}
+
+/// 电池健康报告：当前健康信息、每天的容量记录、损耗趋势和预测
+#[tauri::command]
+async fn get_health_report(app_state: State<'_, AppState>) -> Result<HealthReport, String> {
+    let current = app_state.detector.battery_health()?;
+    Ok(HealthReport::new(current, app_state.wear.records()?, &app_state.wear.config()))
 }
 
 #[tauri::command]
-async fn debug_power_status() -> Result<String, String> {
-    let detector = PowerDetector::new();
-    match detector.get_power_status() {
+async fn debug_power_status(app_state: State<'_, AppState>) -> Result<String, String> {
+    match app_state.detector.get_power_status() {
         Ok(status) => {
             let mut debug_info = format!(
                 "调试信息:\n电池存在: {}\n电源连接: {}\n充电状态: {}\n电池电量: {}%",
@@ -309,27 +804,66 @@ async fn debug_power_status() -> Result<String, String> {
                 status.battery_percentage
             );
             
-            // 添加功率信息
+            // 添加功率信息（括号中为数值来源）
+            let provenance = status.provenance;
             if let Some(power_watts) = status.power_draw_watts {
-                debug_info.push_str(&format!("\n当前功耗: {:.1}W", power_watts));
+                debug_info.push_str(&format!("\n当前功耗: {:.1}W ({})", power_watts, provenance.power_draw_watts.label()));
             }
             
             if let Some(capacity_mwh) = status.battery_capacity_mwh {
-                debug_info.push_str(&format!("\n电池容量: {:.1}Wh", capacity_mwh as f32 / 1000.0));
+                debug_info.push_str(&format!("\n电池容量: {:.1}Wh ({})", capacity_mwh as f32 / 1000.0, provenance.battery_capacity_mwh.label()));
             }
             
             if let Some(remaining_min) = status.remaining_time_minutes {
                 if remaining_min > 0 {
                     let hours = remaining_min / 60;
                     let minutes = remaining_min % 60;
-                    debug_info.push_str(&format!("\n剩余时间: {}h{}m", hours, minutes));
+                    debug_info.push_str(&format!("\n剩余时间: {}h{}m ({})", hours, minutes, provenance.remaining_time_minutes.label()));
                 }
             }
             
             if let Some(charge_rate) = status.charge_rate_watts {
                 if charge_rate != 0.0 {
-                    debug_info.push_str(&format!("\n充电速率: {:.1}W", charge_rate));
+                    debug_info.push_str(&format!("\n充电速率: {:.1}W ({})", charge_rate, provenance.charge_rate_watts.label()));
+                }
+            }
+
+            for device in &status.devices {
+                if let Some(percentage) = device.percentage {
+                    debug_info.push_str(&format!(
+                        "\n设备 {} ({:?}): {}% {:?}",
+                        device.display_name(), device.kind, percentage, device.state
+                    ));
+                }
+            }
+
+            // 电池健康信息（读取失败不影响其它调试信息）
+            match app_state.detector.battery_health() {
+                Ok(Some(health)) => {
+                    if let Some(design) = health.design_capacity_mwh {
+                        debug_info.push_str(&format!("\n设计容量: {:.1}Wh", design as f32 / 1000.0));
+                    }
+                    if let Some(full) = health.full_charge_capacity_mwh {
+                        debug_info.push_str(&format!("\n满充容量: {:.1}Wh", full as f32 / 1000.0));
+                    }
+                    if let Some(wear) = health.wear_level_percent {
+                        debug_info.push_str(&format!("\n电池损耗: {:.1}%", wear));
+                    }
+                    if let Some(cycles) = health.cycle_count {
+                        debug_info.push_str(&format!("\n循环次数: {}", cycles));
+                    }
+                    if let Some(chemistry) = &health.chemistry {
+                        debug_info.push_str(&format!("\n电池类型: {}", chemistry));
+                    }
+                    if let Some(manufacturer) = &health.manufacturer {
+                        debug_info.push_str(&format!("\n制造商: {}", manufacturer));
+                    }
+                    if let Some(serial) = &health.serial {
+                        debug_info.push_str(&format!("\n序列号: {}", serial));
+                    }
                 }
+                Ok(None) => {}
+                Err(e) => debug_info.push_str(&format!("\n电池健康信息获取失败: {}", e)),
             }
             
             Ok(debug_info)
@@ -338,11 +872,16 @@ async fn debug_power_status() -> Result<String, String> {
     }
 }
 
+/// 获取电源信息；`exclude_estimates` 为 true 时估算值返回为 null
 #[tauri::command]
-async fn get_power_info() -> Result<serde_json::Value, String> {
-    let detector = PowerDetector::new();
-    match detector.get_power_status() {
+async fn get_power_info(app_state: State<'_, AppState>, exclude_estimates: Option<bool>) -> Result<serde_json::Value, String> {
+    match app_state.detector.get_power_status() {
         Ok(status) => {
+            let status = if exclude_estimates.unwrap_or(false) {
+                status.without_estimates()
+            } else {
+                status
+            };
             let power_info = serde_json::json!({
                 "battery_percentage": status.battery_percentage,
                 "is_charging": status.is_charging,
@@ -350,7 +889,10 @@ async fn get_power_info() -> Result<serde_json::Value, String> {
                 "power_draw_watts": status.power_draw_watts,
                 "battery_capacity_mwh": status.battery_capacity_mwh,
                 "remaining_time_minutes": status.remaining_time_minutes,
-                "charge_rate_watts": status.charge_rate_watts
+                "charge_rate_watts": status.charge_rate_watts,
+                "provenance": status.provenance,
+                "prediction": status.prediction,
+                "has_estimates": status.provenance.has_estimates()
             });
             Ok(power_info)
         }
@@ -360,9 +902,8 @@ async fn get_power_info() -> Result<serde_json::Value, String> {
 
 /// 测试WMI查询命令
 #[tauri::command]
-fn test_wmi_query() -> Result<String, String> {
-    let detector = PowerDetector::new();
-    match detector.get_power_status() {
+fn test_wmi_query(app_state: State<'_, AppState>) -> Result<String, String> {
+    match app_state.detector.get_power_status() {
         Ok(status) => {
             Ok(format!("电池状态: 电量 {}%, 功耗 {:?}W, 剩余时间 {:?}分钟", 
                       status.battery_percentage, 
@@ -381,8 +922,72 @@ fn main() {
 
     log_info!("isBattery application starting");
 
+    // --export/--report 从终端运行：Windows 发布版没有控制台，先连接到终端，参数错误和导出结果才能输出
+    let headless = std::env::args().skip(1).any(|arg| arg.starts_with("--export") || arg.starts_with("--report"));
+    let has_stdout = !headless || attach_parent_console();
+
+    // 解析命令行参数
+    let cli_options = match CliOptions::from_env() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    // --export：导出电源历史后退出
+    if let Some(ref request) = cli_options.export {
+        if request.is_stdout() && !has_stdout {
+            log_error!("Failed to export power history: no console for --export -");
+            eprintln!("导出失败: 没有可用的标准输出，请在终端中运行或导出到文件");
+            std::process::exit(1);
+        }
+        match run_export(request) {
+            Ok(rows) => {
+                eprintln!("已导出 {} 条记录", rows);
+                std::process::exit(0);
+            }
+            Err(e) => {
+                log_error!("Failed to export power history: {}", e);
+                eprintln!("导出失败: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // --report：生成电池报告后退出
+    if let Some(ref request) = cli_options.report {
+        match run_report(request) {
+            Ok(path) => {
+                eprintln!("电池报告已保存到 {}", path.display());
+                std::process::exit(0);
+            }
+            Err(e) => {
+                log_error!("Failed to generate battery report: {}", e);
+                eprintln!("生成报告失败: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // 选择电源后端：--simulate 时使用场景文件回放，否则使用当前平台的真实后端
+    let power_source = match cli_options.simulate {
+        Some(ref scenario_path) => match SimulatedPowerSource::from_file(scenario_path) {
+            Ok(source) => {
+                log_info!("Simulation mode: replaying scenario {}", scenario_path.display());
+                Arc::new(source) as Arc<dyn PowerSource>
+            }
+            Err(e) => {
+                log_error!("Failed to load simulation scenario: {}", e);
+                eprintln!("Failed to load simulation scenario: {}", e);
+                std::process::exit(2);
+            }
+        },
+        None => default_power_source(),
+    };
+
     // 创建应用状态
-    let app_state = match AppState::new() {
+    let app_state = match AppState::new(PowerDetector::with_source(power_source)) {
         Ok(state) => state,
         Err(e) => {
             log_error!("Failed to initialize app state: {}", e);
@@ -434,8 +1039,7 @@ fn main() {
 
             // 初始化电源状态显示
             {
-                let detector = PowerDetector::new();
-                if let Ok(current_status) = detector.get_power_status() {
+                if let Ok(current_status) = app_state_setup.detector.get_power_status() {
                     let tray_manager = app_state_setup.tray_manager.lock().unwrap();
                     tray_manager.update_status(&current_status);
                     log_info!("Initial power status: AC connected: {}, Battery: {}%", 
@@ -443,15 +1047,21 @@ fn main() {
                 } else {
                     log_error!("Failed to get initial power status");
                 }
+                AppState::refresh_last_session(&app_state_setup.history, &app_state_setup.tray_manager);
             }
 
+            // 订阅监控事件（先于监控启动，不会错过首次检测的提醒）
+            app_state_setup.spawn_event_subscribers();
+            app_state_setup.spawn_escalation_task();
+            app_state_setup.spawn_critical_action_task();
+            app_state_setup.spawn_wear_task();
+
             // 启动电源监控
-            let app_handle_clone = app_handle.clone();
             let app_state_clone = app_state_setup.clone();
             
-            // 使用 app.app_handle() 来确保在 Tauri 运行时上下文中启动
+            // 在 Tauri 运行时上下文中启动
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = app_state_clone.start_monitoring(app_handle_clone).await {
+                if let Err(e) = app_state_clone.start_monitoring().await {
                     log_error!("Failed to start monitoring: {}", e);
                 }
             });
@@ -461,22 +1071,56 @@ fn main() {
             app.listen_global("pause-monitoring", move |_| {
                 let app_state = app_state_clone.clone();
                 tauri::async_runtime::spawn(async move {
-                    app_state.stop_monitoring().await;
+                    app_state.pause_monitoring().await;
                 });
             });
 
-            let app_handle_clone = app_handle.clone();
             let app_state_clone = app_state_setup.clone();
             app.listen_global("resume-monitoring", move |_| {
-                let app_handle = app_handle_clone.clone();
                 let app_state = app_state_clone.clone();
                 tauri::async_runtime::spawn(async move {
-                    if let Err(e) = app_state.start_monitoring(app_handle).await {
+                    if let Err(e) = app_state.resume_monitoring().await {
                         log_error!("Failed to resume monitoring: {}", e);
                     }
                 });
             });
 
+            let app_state_clone = app_state_setup.clone();
+            app.listen_global("snooze-alerts", move |event| {
+                match event.payload().map(serde_json::from_str::<SnoozeDuration>) {
+                    Some(Ok(duration)) => {
+                        app_state_clone.snooze_alerts(duration);
+                    }
+                    _ => log_error!("Invalid snooze duration: {:?}", event.payload()),
+                }
+            });
+
+            let app_state_clone = app_state_setup.clone();
+            app.listen_global("cancel-snooze", move |_| {
+                app_state_clone.cancel_snooze();
+            });
+
+            let app_state_clone = app_state_setup.clone();
+            app.listen_global("acknowledge-alert", move |event| {
+                match event.payload().map(serde_json::from_str::<String>) {
+                    Some(Ok(alert_id)) => {
+                        let mut alert_manager = app_state_clone.alert_manager.lock().unwrap();
+                        if let Err(e) = alert_manager.acknowledge_alert(&alert_id) {
+                            log_error!("Failed to acknowledge alert {}: {}", alert_id, e);
+                        }
+                    }
+                    _ => log_error!("Invalid alert id: {:?}", event.payload()),
+                }
+            });
+
+            let app_state_clone = app_state_setup.clone();
+            app.listen_global("cancel-critical-action", move |_| {
+                if app_state_clone.critical_actions.lock().unwrap().cancel() {
+                    log_info!("Critical battery action cancelled by user");
+                }
+                let _ = app_state_clone.alert_manager.lock().unwrap().close_alert("critical_action");
+            });
+
             let app_state_clone = app_state_setup.clone();
             app.listen_global("toggle-startup", move |_| {
                 let app_state = app_state_clone.clone();
@@ -507,6 +1151,19 @@ fn main() {
                         let mut audio_manager = app_state.audio_manager.lock().unwrap();
                         audio_manager.set_enabled(config.sound_enabled);
                     }
+
+                    // 更新提醒规则、静音时段、提醒升级参数和自动操作
+                    app_state.alert_policy.update_config(&config);
+                    app_state.alert_manager.lock().unwrap().set_escalation_config(config.escalation.clone());
+                    app_state.critical_actions.lock().unwrap().set_config(config.critical_action.clone());
+                    let history_config = app_state.config_manager.get_history_config();
+                    app_state.wear.set_config(history_config.wear.clone());
+                    app_state.history.set_config(history_config);
+
+                    // 电量级别、事件参数和检测间隔直接应用到正在运行的监控循环，无需重启监控
+                    if let Some(handle) = app_state.monitor.lock().unwrap().as_ref() {
+                        handle.update_config(config.clone());
+                    }
                     
                     log_info!("Configuration updated");
                 });
@@ -535,11 +1192,23 @@ fn main() {
             ui::settings::test_audio_alert,
             ui::alert::close_alert_window,
             ui::alert::pause_monitoring_from_alert,
+            ui::alert::acknowledge_alert,
+            ui::alert::cancel_critical_action,
             ui::alert::get_alert_config,
             pause_monitoring,
             resume_monitoring,
+            snooze_alerts,
+            cancel_snooze,
+            get_snooze_status,
+            get_power_history,
+            get_sessions,
+            export_power_history,
             toggle_startup,
             get_current_power_status,
+            get_power_devices,
+            get_battery_health,
+            get_health_report,
+            generate_battery_report,
             debug_power_status,
             get_power_info,
             test_wmi_query
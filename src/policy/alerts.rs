@@ -0,0 +1,161 @@
+use crate::config::MonitoringConfig;
+use crate::policy::{scheduled_mode, AlertMode, QuietHours, RuleAction, RuleEngine, SnoozeManager};
+use crate::power::{MonitorEvent, PowerEvent};
+use chrono::{DateTime, Local};
+use std::sync::{Arc, Mutex};
+
+/// 一个事件应该使用的提醒渠道
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlertChannels {
+    pub window: bool,        // 置顶提醒窗口
+    pub sound: bool,         // 提示音
+    pub notification: bool,  // 托盘通知
+}
+
+impl AlertChannels {
+    fn for_mode(mode: AlertMode) -> Self {
+        Self {
+            window: mode == AlertMode::Normal,
+            sound: mode == AlertMode::Normal,
+            notification: mode != AlertMode::Off,
+        }
+    }
+}
+
+/// 提醒策略：在显示提醒窗口、播放提示音和显示托盘通知之前决定是否提醒
+///
+/// 提醒规则（`[[monitoring.rules]]`）决定事件触发哪些动作；静音时段（`[[monitoring.quiet_hours]]`）
+/// 和暂停提醒再从中去掉被限制的提醒渠道，取限制程度最高的方式。严重低电量（`CriticalBattery`，
+/// 以及阈值不高于严重低电量阈值的电量级别）始终正常提醒。
+pub struct AlertPolicy {
+    snooze: Arc<SnoozeManager>,
+    settings: Mutex<PolicySettings>,
+}
+
+struct PolicySettings {
+    rules: RuleEngine,
+    quiet_hours: Vec<QuietHours>,
+    critical_threshold: u8,
+}
+
+impl PolicySettings {
+    fn from_config(config: &MonitoringConfig) -> Self {
+        Self {
+            rules: RuleEngine::new(config.rules.clone()),
+            quiet_hours: config.quiet_hours.clone(),
+            critical_threshold: config.events.critical_threshold,
+        }
+    }
+}
+
+impl AlertPolicy {
+    pub fn new(snooze: Arc<SnoozeManager>, config: &MonitoringConfig) -> Self {
+        Self {
+            snooze,
+            settings: Mutex::new(PolicySettings::from_config(config)),
+        }
+    }
+
+    /// 配置更新后重新读取提醒规则和静音时段
+    pub fn update_config(&self, config: &MonitoringConfig) {
+        *self.settings.lock().unwrap() = PolicySettings::from_config(config);
+    }
+
+    /// 当前的提醒方式（不考虑事件本身）
+    pub fn mode(&self, now: DateTime<Local>) -> AlertMode {
+        if self.snooze.is_active(now) {
+            return AlertMode::Off;
+        }
+        scheduled_mode(&self.settings.lock().unwrap().quiet_hours, now)
+    }
+
+    /// 该事件应该使用的提醒渠道
+    pub fn channels(&self, event: &PowerEvent, now: DateTime<Local>) -> AlertChannels {
+        if self.is_critical(event) {
+            return AlertChannels::for_mode(AlertMode::Normal);
+        }
+        AlertChannels::for_mode(self.mode(now))
+    }
+
+    /// 该事件应该执行的动作：匹配规则的动作中去掉当前被限制的提醒渠道（外部动作不受限制）
+    pub fn actions(&self, event: &MonitorEvent, now: DateTime<Local>) -> Vec<RuleAction> {
+        let channels = self.channels(&event.power_event, now);
+        let actions = self.settings.lock().unwrap().rules.actions(event);
+        actions
+            .into_iter()
+            .filter(|action| match action {
+                RuleAction::Window => channels.window,
+                RuleAction::Sound => channels.sound,
+                RuleAction::Notification => channels.notification,
+                _ => true,
+            })
+            .collect()
+    }
+
+    fn is_critical(&self, event: &PowerEvent) -> bool {
+        match event {
+            PowerEvent::CriticalBattery(_) => true,
+            PowerEvent::BatteryLow { level, .. } => level.threshold <= self.settings.lock().unwrap().critical_threshold,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use crate::policy::SnoozeDuration;
+    use crate::power::BatteryLevel;
+    use crate::power::test_support::{at, event};
+
+    fn policy(mode: AlertMode) -> AlertPolicy {
+        let config = MonitoringConfig {
+            quiet_hours: vec![QuietHours {
+                days: Vec::new(),
+                start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+                mode,
+            }],
+            ..MonitoringConfig::default()
+        };
+        AlertPolicy::new(Arc::new(SnoozeManager::in_memory()), &config)
+    }
+
+    #[test]
+    fn test_quiet_hours_modes() {
+        let silent = policy(AlertMode::Silent);
+        let channels = silent.channels(&PowerEvent::AcDisconnected, at(1, 23, 0));
+        assert_eq!(channels, AlertChannels { window: false, sound: false, notification: true });
+        assert_eq!(silent.channels(&PowerEvent::AcDisconnected, at(1, 12, 0)), AlertChannels::for_mode(AlertMode::Normal));
+
+        let off = policy(AlertMode::Off);
+        assert!(!off.channels(&PowerEvent::AcDisconnected, at(1, 23, 0)).notification);
+    }
+
+    #[test]
+    fn test_critical_alerts_bypass_policy() {
+        let off = policy(AlertMode::Off);
+        let normal = AlertChannels::for_mode(AlertMode::Normal);
+        assert_eq!(off.channels(&PowerEvent::CriticalBattery(4), at(1, 23, 0)), normal);
+
+        // 默认严重低电量阈值为 5%
+        let critical = PowerEvent::BatteryLow { level: BatteryLevel::low(5), percentage: 5 };
+        assert_eq!(off.channels(&critical, at(1, 23, 0)), normal);
+        let low = PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 20 };
+        assert!(!off.channels(&low, at(1, 23, 0)).window);
+
+        // 暂停提醒同样不影响严重低电量
+        off.snooze.snooze(SnoozeDuration::UntilPlugIn, at(1, 12, 0));
+        assert_eq!(off.mode(at(1, 12, 0)), AlertMode::Off);
+        assert_eq!(off.channels(&PowerEvent::CriticalBattery(4), at(1, 12, 0)), normal);
+    }
+
+    #[test]
+    fn test_actions_filtered_by_quiet_hours() {
+        let silent = policy(AlertMode::Silent);
+        let disconnected = event(PowerEvent::AcDisconnected, false, 80);
+        assert_eq!(silent.actions(&disconnected, at(1, 12, 0)), vec![RuleAction::Window, RuleAction::Sound, RuleAction::Notification]);
+        assert_eq!(silent.actions(&disconnected, at(1, 23, 0)), vec![RuleAction::Notification]);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::power::BatteryStatus;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// 电量严重不足时自动执行的系统操作
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CriticalAction {
+    #[default]
+    None,
+    Hibernate,
+    Suspend,
+    Shutdown,
+    Lock,
+    Command {  // 用户指定的命令
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl CriticalAction {
+    /// 倒计时窗口中显示的名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            CriticalAction::None => "无操作",
+            CriticalAction::Hibernate => "休眠",
+            CriticalAction::Suspend => "睡眠",
+            CriticalAction::Shutdown => "关机",
+            CriticalAction::Lock => "锁定屏幕",
+            CriticalAction::Command { .. } => "执行命令",
+        }
+    }
+
+    /// 当前平台上执行该操作的命令，`None` 表示不执行任何操作
+    pub fn system_command(&self) -> Option<(String, Vec<String>)> {
+        let (program, args): (&str, &[&str]) = match self {
+            CriticalAction::None => return None,
+            CriticalAction::Command { program, args } => return Some((program.clone(), args.clone())),
+            #[cfg(target_os = "windows")]
+            CriticalAction::Hibernate => ("shutdown", &["/h"]),
+            #[cfg(target_os = "windows")]
+            CriticalAction::Suspend => ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"]),
+            #[cfg(target_os = "windows")]
+            CriticalAction::Shutdown => ("shutdown", &["/s", "/t", "0"]),
+            #[cfg(target_os = "windows")]
+            CriticalAction::Lock => ("rundll32.exe", &["user32.dll,LockWorkStation"]),
+            #[cfg(not(target_os = "windows"))]
+            CriticalAction::Hibernate => ("systemctl", &["hibernate"]),
+            #[cfg(not(target_os = "windows"))]
+            CriticalAction::Suspend => ("systemctl", &["suspend"]),
+            #[cfg(not(target_os = "windows"))]
+            CriticalAction::Shutdown => ("systemctl", &["poweroff"]),
+            #[cfg(not(target_os = "windows"))]
+            CriticalAction::Lock => ("loginctl", &["lock-session"]),
+        };
+        Some((program.to_string(), args.iter().map(|arg| arg.to_string()).collect()))
+    }
+}
+
+/// 严重低电量自动操作参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CriticalActionConfig {
+    pub action: CriticalAction,  // 默认不执行任何操作
+    pub threshold: u8,           // 使用电池供电且电量不高于该值时开始倒计时（%）
+    pub countdown_secs: u32,     // 执行前的倒计时（秒），期间可以在提醒窗口中取消
+}
+
+impl Default for CriticalActionConfig {
+    fn default() -> Self {
+        Self {
+            action: CriticalAction::None,
+            threshold: 3,
+            countdown_secs: 60,
+        }
+    }
+}
+
+impl CriticalActionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.threshold > 100 {
+            return Err("自动操作的电量阈值不能超过100%".to_string());
+        }
+        if self.countdown_secs < 10 {
+            return Err("自动操作的倒计时不能少于10秒".to_string());
+        }
+        if let CriticalAction::Command { program, .. } = &self.action {
+            if program.trim().is_empty() {
+                return Err("自动操作的命令不能为空".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 执行自动操作（休眠、关机等）
+///
+/// 系统实现见 `SystemActionExecutor`；测试使用 `DryRunExecutor` 只记录操作。
+pub trait ActionExecutor: Send + Sync {
+    fn execute(&self, action: &CriticalAction) -> Result<(), String>;
+}
+
+/// 调用系统命令执行操作
+pub struct SystemActionExecutor;
+
+impl ActionExecutor for SystemActionExecutor {
+    fn execute(&self, action: &CriticalAction) -> Result<(), String> {
+        let (program, args) = match action.system_command() {
+            Some(command) => command,
+            None => return Ok(()),
+        };
+
+        let mut child = std::process::Command::new(&program)
+            .args(&args)
+            .spawn()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+        // 休眠、关机命令可能在系统恢复后才返回，在独立线程中等待并记录结果
+        std::thread::spawn(move || match child.wait() {
+            Ok(status) if !status.success() => crate::log_error!("{} {:?} exited with {}", program, args, status),
+            Err(e) => crate::log_error!("Failed to wait for {}: {}", program, e),
+            _ => {}
+        });
+        Ok(())
+    }
+}
+
+/// 只记录操作、不真正执行
+#[derive(Default)]
+#[allow(dead_code)]
+pub struct DryRunExecutor {
+    executed: Mutex<Vec<CriticalAction>>,
+}
+
+#[allow(dead_code)]
+impl DryRunExecutor {
+    pub fn executed(&self) -> Vec<CriticalAction> {
+        self.executed.lock().unwrap().clone()
+    }
+}
+
+impl ActionExecutor for DryRunExecutor {
+    fn execute(&self, action: &CriticalAction) -> Result<(), String> {
+        crate::log_info!("Dry run: {:?}", action);
+        self.executed.lock().unwrap().push(action.clone());
+        Ok(())
+    }
+}
+
+/// 状态变化后需要界面处理的结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum CriticalDecision {
+    Idle,
+    CountdownStarted { action: CriticalAction, deadline: DateTime<Local> },  // 显示倒计时窗口
+    CountdownCancelled,                                                    // 接通电源或电量回升，关闭倒计时窗口
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CriticalState {
+    Idle,
+    Countdown(DateTime<Local>),  // 倒计时中，到期时执行
+    Done,                        // 已执行或被用户取消，接通电源或电量回升后才重新开始
+}
+
+/// 严重低电量自动操作的决策
+///
+/// 使用电池供电且电量降到 `threshold` 及以下时开始倒计时，倒计时结束时通过
+/// `ActionExecutor` 执行操作；倒计时期间接通电源、电量回升或用户取消都会停止。
+/// 每次电量下降到阈值只执行（或取消）一次。
+pub struct CriticalActionController {
+    config: CriticalActionConfig,
+    state: CriticalState,
+    executor: Arc<dyn ActionExecutor>,
+}
+
+impl CriticalActionController {
+    pub fn new(config: CriticalActionConfig, executor: Arc<dyn ActionExecutor>) -> Self {
+        Self {
+            config,
+            state: CriticalState::Idle,
+            executor,
+        }
+    }
+
+    /// 更新参数；关闭自动操作时停止正在进行的倒计时
+    pub fn set_config(&mut self, config: CriticalActionConfig) {
+        if config.action == CriticalAction::None {
+            self.state = CriticalState::Idle;
+        }
+        self.config = config;
+    }
+
+    /// 根据最新的电源状态开始或停止倒计时
+    pub fn on_status(&mut self, status: &BatteryStatus, now: DateTime<Local>) -> CriticalDecision {
+        let critical = self.config.action != CriticalAction::None
+            && status.is_battery_present
+            && !status.is_ac_connected
+            && status.battery_percentage <= self.config.threshold;
+
+        match (&self.state, critical) {
+            (CriticalState::Idle, true) => {
+                let deadline = now + Duration::seconds(self.config.countdown_secs as i64);
+                self.state = CriticalState::Countdown(deadline);
+                CriticalDecision::CountdownStarted {
+                    action: self.config.action.clone(),
+                    deadline,
+                }
+            }
+            (CriticalState::Countdown(_), false) => {
+                self.state = CriticalState::Idle;
+                CriticalDecision::CountdownCancelled
+            }
+            (CriticalState::Done, false) => {
+                self.state = CriticalState::Idle;
+                CriticalDecision::Idle
+            }
+            _ => CriticalDecision::Idle,
+        }
+    }
+
+    /// 用户在倒计时窗口中取消，返回是否取消了正在进行的倒计时
+    pub fn cancel(&mut self) -> bool {
+        if matches!(self.state, CriticalState::Countdown(_)) {
+            self.state = CriticalState::Done;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 倒计时到期时执行操作，返回执行的操作
+    pub fn tick(&mut self, now: DateTime<Local>) -> Option<Result<CriticalAction, String>> {
+        match self.state {
+            CriticalState::Countdown(deadline) if now >= deadline => {
+                self.state = CriticalState::Done;
+                let action = self.config.action.clone();
+                Some(self.executor.execute(&action).map(|_| action))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::{at, status};
+
+    /// 9 月 1 日 10:00 之后第 `n` 秒
+    fn second(n: i64) -> DateTime<Local> {
+        at(1, 10, 0) + chrono::Duration::seconds(n)
+    }
+
+    fn controller(action: CriticalAction) -> (CriticalActionController, Arc<DryRunExecutor>) {
+        let executor = Arc::new(DryRunExecutor::default());
+        let config = CriticalActionConfig {
+            action,
+            threshold: 3,
+            countdown_secs: 30,
+        };
+        (CriticalActionController::new(config, executor.clone()), executor)
+    }
+
+    #[test]
+    fn test_countdown_then_execute_once() {
+        let (mut controller, executor) = controller(CriticalAction::Hibernate);
+
+        assert_eq!(controller.on_status(&status(false, 4), second(0)), CriticalDecision::Idle);
+        assert_eq!(
+            controller.on_status(&status(false, 3), second(0)),
+            CriticalDecision::CountdownStarted { action: CriticalAction::Hibernate, deadline: second(30) }
+        );
+        assert_eq!(controller.on_status(&status(false, 2), second(10)), CriticalDecision::Idle);
+
+        assert!(controller.tick(second(29)).is_none());
+        assert_eq!(controller.tick(second(30)), Some(Ok(CriticalAction::Hibernate)));
+        assert!(controller.tick(second(40)).is_none());
+        assert_eq!(controller.on_status(&status(false, 2), second(45)), CriticalDecision::Idle);
+        assert_eq!(executor.executed(), vec![CriticalAction::Hibernate]);
+    }
+
+    #[test]
+    fn test_plug_in_and_cancel_stop_countdown() {
+        let (mut controller, executor) = controller(CriticalAction::Shutdown);
+
+        controller.on_status(&status(false, 3), second(0));
+        assert_eq!(controller.on_status(&status(true, 3), second(5)), CriticalDecision::CountdownCancelled);
+        assert!(controller.tick(second(30)).is_none());
+
+        // 拔掉电源后重新开始倒计时；用户取消后不再执行，直到接通电源
+        assert!(matches!(controller.on_status(&status(false, 3), second(31)), CriticalDecision::CountdownStarted { .. }));
+        assert!(controller.cancel());
+        assert!(controller.tick(second(59)).is_none());
+        assert_eq!(controller.on_status(&status(false, 2), second(59)), CriticalDecision::Idle);
+        assert!(executor.executed().is_empty());
+    }
+
+    #[test]
+    fn test_no_action_configured() {
+        let (mut controller, executor) = controller(CriticalAction::None);
+        assert_eq!(controller.on_status(&status(false, 1), second(0)), CriticalDecision::Idle);
+        assert!(controller.tick(second(59)).is_none());
+        assert!(executor.executed().is_empty());
+    }
+
+    #[test]
+    fn test_system_commands() {
+        assert_eq!(CriticalAction::None.system_command(), None);
+        let command = CriticalAction::Command { program: "notify-send".to_string(), args: vec!["low".to_string()] };
+        assert_eq!(command.system_command(), Some(("notify-send".to_string(), vec!["low".to_string()])));
+
+        let (program, args) = CriticalAction::Hibernate.system_command().unwrap();
+        if cfg!(target_os = "windows") {
+            assert_eq!((program.as_str(), args), ("shutdown", vec!["/h".to_string()]));
+        } else {
+            assert_eq!((program.as_str(), args), ("systemctl", vec!["hibernate".to_string()]));
+        }
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(CriticalActionConfig::default().validate().is_ok());
+        assert!(CriticalActionConfig { countdown_secs: 0, ..Default::default() }.validate().is_err());
+        let empty = CriticalAction::Command { program: " ".to_string(), args: Vec::new() };
+        assert!(CriticalActionConfig { action: empty, ..Default::default() }.validate().is_err());
+
+        let config: CriticalActionConfig = toml::from_str("action = \"hibernate\"\nthreshold = 4").unwrap();
+        assert_eq!((config.action, config.threshold, config.countdown_secs), (CriticalAction::Hibernate, 4, 60));
+        let config: CriticalActionConfig =
+            toml::from_str("action = { command = { program = \"/usr/local/bin/save-work\" } }").unwrap();
+        assert!(matches!(config.action, CriticalAction::Command { ref args, .. } if args.is_empty()));
+    }
+}
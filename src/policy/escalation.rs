@@ -0,0 +1,240 @@
+use crate::power::PowerEvent;
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 提醒升级参数
+///
+/// 低电量提醒弹出后，用户没有确认（提醒窗口中的"我知道了"）也没有接通电源时，
+/// 每隔一段时间重新响铃并把提醒窗口提到最前；每次重复后间隔按 `interval_factor` 缩短、
+/// 音量按 `volume_step` 增大。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EscalationConfig {
+    pub enabled: bool,
+    pub repeat_minutes: u32,        // 第一次重复提醒的间隔（分钟）
+    pub min_repeat_minutes: u32,    // 间隔缩短的下限（分钟）
+    pub interval_factor: f32,       // 每次重复后间隔乘以该系数，1.0 表示不加快
+    pub start_volume: f32,          // 第一次重复提醒的音量（0.0 - 1.0）
+    pub volume_step: f32,           // 每次重复增加的音量，0 表示不变
+    pub critical_fullscreen: bool,  // 电量严重不足时显示全屏提醒
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            repeat_minutes: 5,
+            min_repeat_minutes: 1,
+            interval_factor: 0.5,
+            start_volume: 0.5,
+            volume_step: 0.25,
+            critical_fullscreen: true,
+        }
+    }
+}
+
+impl EscalationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.repeat_minutes == 0 || self.min_repeat_minutes == 0 {
+            return Err("重复提醒间隔不能为0".to_string());
+        }
+        if self.min_repeat_minutes > self.repeat_minutes {
+            return Err("最短重复提醒间隔不能大于重复提醒间隔".to_string());
+        }
+        if !(self.interval_factor > 0.0 && self.interval_factor <= 1.0) {
+            return Err("重复提醒间隔系数必须在0到1之间".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.start_volume) || !(0.0..=1.0).contains(&self.volume_step) {
+            return Err("提醒音量必须在0.0到1.0之间".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 一次到期的重复提醒
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscalationStep {
+    pub alert_id: String,
+    pub event: PowerEvent,  // 触发提醒的事件（用于确定提示音和是否受静音时段限制）
+    pub step: u32,          // 第几次重复，从 1 开始
+    pub volume: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Escalation {
+    event: PowerEvent,
+    step: u32,
+    interval: Duration,
+    next_at: DateTime<Local>,
+}
+
+/// 提醒升级与确认状态
+#[derive(Debug, Clone, Default)]
+pub struct EscalationTracker {
+    config: EscalationConfig,
+    active: HashMap<String, Escalation>,
+    acknowledged: HashSet<String>,
+}
+
+impl EscalationTracker {
+    pub fn new(config: EscalationConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    pub fn config(&self) -> &EscalationConfig {
+        &self.config
+    }
+
+    /// 更新参数，正在进行的升级从下一次重复开始使用新间隔
+    pub fn set_config(&mut self, config: EscalationConfig) {
+        if !config.enabled {
+            self.active.clear();
+        }
+        self.config = config;
+    }
+
+    /// 提醒窗口弹出后开始升级；同一提醒已在升级时只更新触发事件，不重置进度
+    pub fn start(&mut self, alert_id: &str, event: &PowerEvent, now: DateTime<Local>) {
+        self.acknowledged.remove(alert_id);
+        if !self.config.enabled {
+            return;
+        }
+
+        let interval = Duration::minutes(self.config.repeat_minutes as i64);
+        self.active
+            .entry(alert_id.to_string())
+            .and_modify(|escalation| escalation.event = event.clone())
+            .or_insert_with(|| Escalation {
+                event: event.clone(),
+                step: 0,
+                interval,
+                next_at: now + interval,
+            });
+    }
+
+    /// 用户确认提醒，停止升级；返回该提醒之前是否在升级
+    pub fn acknowledge(&mut self, alert_id: &str) -> bool {
+        self.acknowledged.insert(alert_id.to_string());
+        self.active.remove(alert_id).is_some()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_acknowledged(&self, alert_id: &str) -> bool {
+        self.acknowledged.contains(alert_id)
+    }
+
+    #[allow(dead_code)]
+    pub fn is_escalating(&self, alert_id: &str) -> bool {
+        self.active.contains_key(alert_id)
+    }
+
+    /// 提醒不再需要（接通电源、电量恢复、窗口被关闭）时停止升级
+    pub fn stop(&mut self, alert_id: &str) {
+        self.active.remove(alert_id);
+    }
+
+    pub fn stop_all(&mut self) {
+        self.active.clear();
+    }
+
+    /// 取出 `now` 时到期的重复提醒，并安排下一次
+    pub fn due(&mut self, now: DateTime<Local>) -> Vec<EscalationStep> {
+        let config = &self.config;
+        let min_interval = Duration::minutes(config.min_repeat_minutes as i64);
+
+        let mut steps: Vec<EscalationStep> = self
+            .active
+            .iter_mut()
+            .filter(|(_, escalation)| now >= escalation.next_at)
+            .map(|(alert_id, escalation)| {
+                escalation.step += 1;
+                let scaled = escalation.interval.num_milliseconds() as f64 * config.interval_factor as f64;
+                escalation.interval = Duration::milliseconds(scaled as i64).max(min_interval);
+                escalation.next_at = now + escalation.interval;
+
+                let volume = config.start_volume + config.volume_step * (escalation.step - 1) as f32;
+                EscalationStep {
+                    alert_id: alert_id.clone(),
+                    event: escalation.event.clone(),
+                    step: escalation.step,
+                    volume: volume.min(1.0),
+                }
+            })
+            .collect();
+        steps.sort_by(|a, b| a.alert_id.cmp(&b.alert_id));
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::at;
+
+    #[test]
+    fn test_repeats_faster_and_louder() {
+        let mut tracker = EscalationTracker::new(EscalationConfig::default());
+        tracker.start("low_battery", &PowerEvent::CriticalBattery(4), at(1, 10, 0));
+
+        assert!(tracker.due(at(1, 10, 4)).is_empty());
+        // 5 分钟后第一次重复，之后间隔依次为 2.5 分钟、1.25 分钟、1 分钟（下限）
+        let steps = tracker.due(at(1, 10, 5));
+        assert_eq!(steps.len(), 1);
+        assert_eq!((steps[0].step, steps[0].volume), (1, 0.5));
+
+        assert!(tracker.due(at(1, 10, 7)).is_empty());
+        let steps = tracker.due(at(1, 10, 8));
+        assert_eq!((steps[0].step, steps[0].volume), (2, 0.75));
+
+        assert!(tracker.due(at(1, 10, 9)).is_empty());
+        assert_eq!(tracker.due(at(1, 10, 10))[0].volume, 1.0);
+        assert_eq!(tracker.due(at(1, 10, 11))[0].step, 4);
+    }
+
+    #[test]
+    fn test_acknowledge_stops_escalation() {
+        let mut tracker = EscalationTracker::new(EscalationConfig::default());
+        tracker.start("low_battery", &PowerEvent::CriticalBattery(4), at(1, 10, 0));
+
+        assert!(tracker.acknowledge("low_battery"));
+        assert!(tracker.is_acknowledged("low_battery"));
+        assert!(tracker.due(at(1, 10, 30)).is_empty());
+
+        // 再次弹出提醒时重新开始
+        tracker.start("low_battery", &PowerEvent::CriticalBattery(3), at(1, 10, 30));
+        assert!(!tracker.is_acknowledged("low_battery"));
+        assert_eq!(tracker.due(at(1, 10, 35))[0].step, 1);
+
+        tracker.stop_all();
+        assert!(!tracker.is_escalating("low_battery"));
+    }
+
+    #[test]
+    fn test_restart_keeps_progress() {
+        let mut tracker = EscalationTracker::new(EscalationConfig::default());
+        tracker.start("low_battery", &PowerEvent::CriticalBattery(5), at(1, 10, 0));
+        tracker.due(at(1, 10, 5));
+
+        // 更低的级别替换窗口时不重置进度
+        tracker.start("low_battery", &PowerEvent::CriticalBattery(3), at(1, 10, 6));
+        let steps = tracker.due(at(1, 10, 8));
+        assert_eq!(steps[0].step, 2);
+        assert_eq!(steps[0].event, PowerEvent::CriticalBattery(3));
+    }
+
+    #[test]
+    fn test_disabled_and_validation() {
+        let config = EscalationConfig { enabled: false, ..Default::default() };
+        let mut tracker = EscalationTracker::new(config);
+        tracker.start("low_battery", &PowerEvent::CriticalBattery(4), at(1, 10, 0));
+        assert!(tracker.due(at(1, 10, 30)).is_empty());
+
+        assert!(EscalationConfig::default().validate().is_ok());
+        assert!(EscalationConfig { interval_factor: 0.0, ..Default::default() }.validate().is_err());
+        assert!(EscalationConfig { min_repeat_minutes: 10, ..Default::default() }.validate().is_err());
+    }
+}
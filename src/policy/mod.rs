@@ -0,0 +1,13 @@
+pub mod alerts;
+pub mod critical;
+pub mod escalation;
+pub mod rules;
+pub mod schedule;
+pub mod snooze;
+
+pub use alerts::*;
+pub use critical::*;
+pub use escalation::*;
+pub use rules::*;
+pub use schedule::*;
+pub use snooze::*;
@@ -0,0 +1,390 @@
+use crate::power::{BatteryStatus, LevelAction, MonitorEvent, PowerEvent};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 外部命令和 Webhook 的最长执行时间
+const EXTERNAL_ACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 规则匹配的事件类型（`StatusUpdate` 不能作为触发条件）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    AcConnected,
+    AcDisconnected,
+    BatteryLow,
+    BatteryNormal,
+    DeviceBatteryLow,
+    DeviceBatteryNormal,
+    CriticalBattery,
+    RapidDrain,
+    ChargingStalled,
+    FullyCharged,
+    BatteryRemoved,
+    BatteryInserted,
+    BatteryDegraded,
+}
+
+impl EventKind {
+    pub fn of(event: &PowerEvent) -> Option<Self> {
+        Some(match event {
+            PowerEvent::AcConnected => EventKind::AcConnected,
+            PowerEvent::AcDisconnected => EventKind::AcDisconnected,
+            PowerEvent::BatteryLow { .. } => EventKind::BatteryLow,
+            PowerEvent::BatteryNormal(_) => EventKind::BatteryNormal,
+            PowerEvent::DeviceBatteryLow { .. } => EventKind::DeviceBatteryLow,
+            PowerEvent::DeviceBatteryNormal { .. } => EventKind::DeviceBatteryNormal,
+            PowerEvent::CriticalBattery(_) => EventKind::CriticalBattery,
+            PowerEvent::RapidDrain { .. } => EventKind::RapidDrain,
+            PowerEvent::ChargingStalled(_) => EventKind::ChargingStalled,
+            PowerEvent::FullyCharged => EventKind::FullyCharged,
+            PowerEvent::BatteryRemoved => EventKind::BatteryRemoved,
+            PowerEvent::BatteryInserted => EventKind::BatteryInserted,
+            PowerEvent::BatteryDegraded { .. } => EventKind::BatteryDegraded,
+            PowerEvent::StatusUpdate => return None,
+        })
+    }
+
+    /// 配置和日志中使用的名称
+    pub fn name(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+}
+
+/// 事件发生时的状态条件，未设置的条件不检查
+///
+/// 电量和电源条件检查的是系统电池的汇总状态（外设事件也一样）。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleConditions {
+    pub battery_below: Option<u8>,             // 电量低于（%）
+    pub battery_above: Option<u8>,             // 电量高于（%）
+    pub ac_connected: Option<bool>,
+    pub charging: Option<bool>,
+    pub level: Option<String>,                 // battery_low 事件的电量级别名称
+    pub level_action: Option<LevelAction>,     // battery_low 事件的电量级别提醒方式
+}
+
+impl RuleConditions {
+    pub fn matches(&self, event: &PowerEvent, status: &BatteryStatus) -> bool {
+        let percentage = status.battery_percentage;
+        if self.battery_below.is_some_and(|below| percentage >= below)
+            || self.battery_above.is_some_and(|above| percentage <= above)
+            || self.ac_connected.is_some_and(|ac| status.is_ac_connected != ac)
+            || self.charging.is_some_and(|charging| status.is_charging != charging)
+        {
+            return false;
+        }
+
+        if self.level.is_none() && self.level_action.is_none() {
+            return true;
+        }
+        match event {
+            PowerEvent::BatteryLow { level, .. } => {
+                self.level.as_ref().is_none_or(|name| *name == level.name)
+                    && self.level_action.is_none_or(|action| action == level.action)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 规则触发时执行的动作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    Window,        // 弹出置顶提醒窗口
+    Sound,         // 播放提示音（battery_low 使用该级别的声音设置）
+    Notification,  // 显示托盘通知
+    Command {      // 运行外部命令，事件信息通过 ISBATTERY_* 环境变量传入
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    Webhook {      // 以 JSON POST 事件信息（使用系统的 curl）
+        url: String,
+    },
+}
+
+impl RuleAction {
+    /// 是否为外部动作（命令或 Webhook），外部动作不受静音时段和暂停提醒影响
+    pub fn is_external(&self) -> bool {
+        matches!(self, RuleAction::Command { .. } | RuleAction::Webhook { .. })
+    }
+}
+
+/// 一条提醒规则：当 `when` 事件发生且满足 `conditions` 时执行 `then` 中的动作
+///
+/// ```toml
+/// [[monitoring.rules]]
+/// name = "unplugged-below-half"
+/// when = "ac_disconnected"
+/// conditions = { battery_below = 50 }
+/// then = ["window", "sound"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertRule {
+    #[serde(default)]
+    pub name: String,
+    pub when: EventKind,
+    #[serde(default)]
+    pub conditions: RuleConditions,
+    pub then: Vec<RuleAction>,
+}
+
+impl AlertRule {
+    fn new(name: &str, when: EventKind, then: Vec<RuleAction>) -> Self {
+        Self {
+            name: name.to_string(),
+            when,
+            conditions: RuleConditions::default(),
+            then,
+        }
+    }
+
+    pub fn matches(&self, event: &PowerEvent, status: &BatteryStatus) -> bool {
+        EventKind::of(event) == Some(self.when) && self.conditions.matches(event, status)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let name = if self.name.is_empty() { self.when.name() } else { self.name.clone() };
+        if self.then.is_empty() {
+            return Err(format!("提醒规则 {} 没有设置动作", name));
+        }
+        if self.conditions.battery_below.is_some_and(|below| below > 100)
+            || self.conditions.battery_above.is_some_and(|above| above > 100)
+        {
+            return Err(format!("提醒规则 {} 的电量条件不能超过100%", name));
+        }
+        for action in &self.then {
+            match action {
+                RuleAction::Command { program, .. } if program.trim().is_empty() => {
+                    return Err(format!("提醒规则 {} 的命令不能为空", name));
+                }
+                RuleAction::Webhook { url } if !url.starts_with("http://") && !url.starts_with("https://") => {
+                    return Err(format!("提醒规则 {} 的 Webhook 地址无效: {}", name, url));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 提醒规则引擎
+///
+/// 按顺序检查所有规则，返回匹配规则的动作（去重后保持首次出现的顺序）。
+/// 没有配置规则时使用 `default_rules`，与之前内置的提醒行为一致。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleEngine {
+    rules: Vec<AlertRule>,
+}
+
+impl RuleEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        if rules.is_empty() {
+            Self::default()
+        } else {
+            Self { rules }
+        }
+    }
+
+    /// 内置的提醒规则
+    pub fn default_rules() -> Vec<AlertRule> {
+        use RuleAction::{Notification, Sound, Window};
+
+        let mut alert_level = AlertRule::new("battery-low-window", EventKind::BatteryLow, vec![Window]);
+        alert_level.conditions.level_action = Some(LevelAction::Alert);
+
+        vec![
+            AlertRule::new("ac-disconnected", EventKind::AcDisconnected, vec![Window, Sound, Notification]),
+            AlertRule::new("ac-connected", EventKind::AcConnected, vec![Notification]),
+            alert_level,
+            AlertRule::new("battery-low", EventKind::BatteryLow, vec![Sound, Notification]),
+            AlertRule::new("battery-normal", EventKind::BatteryNormal, vec![Notification]),
+            AlertRule::new("critical-battery", EventKind::CriticalBattery, vec![Window, Sound, Notification]),
+            AlertRule::new("rapid-drain", EventKind::RapidDrain, vec![Notification]),
+            AlertRule::new("charging-stalled", EventKind::ChargingStalled, vec![Notification]),
+            AlertRule::new("fully-charged", EventKind::FullyCharged, vec![Notification]),
+            AlertRule::new("battery-removed", EventKind::BatteryRemoved, vec![Notification]),
+            AlertRule::new("battery-inserted", EventKind::BatteryInserted, vec![Notification]),
+            AlertRule::new("battery-degraded", EventKind::BatteryDegraded, vec![Notification]),
+            // 外设电量不足只显示托盘通知，不弹出置顶提醒窗口
+            AlertRule::new("device-battery-low", EventKind::DeviceBatteryLow, vec![Notification]),
+        ]
+    }
+
+    /// 匹配该事件的所有规则的动作
+    pub fn actions(&self, event: &MonitorEvent) -> Vec<RuleAction> {
+        let mut actions: Vec<RuleAction> = Vec::new();
+        for rule in &self.rules {
+            if !rule.matches(&event.power_event, &event.current_status) {
+                continue;
+            }
+            for action in &rule.then {
+                if !actions.contains(action) {
+                    actions.push(action.clone());
+                }
+            }
+        }
+        actions
+    }
+}
+
+impl Default for RuleEngine {
+    fn default() -> Self {
+        Self {
+            rules: Self::default_rules(),
+        }
+    }
+}
+
+/// 外部动作收到的事件信息
+pub fn event_payload(event: &MonitorEvent) -> serde_json::Value {
+    let status = &event.current_status;
+    serde_json::json!({
+        "event": EventKind::of(&event.power_event).map(|kind| kind.name()),
+        "description": event.power_event.to_string(),
+        "battery_percentage": status.battery_percentage,
+        "is_ac_connected": status.is_ac_connected,
+        "is_charging": status.is_charging,
+        "power_draw_watts": status.power_draw_watts,
+        "remaining_time_minutes": status.remaining_time_minutes,
+        "timestamp": Local::now().to_rfc3339(),
+    })
+}
+
+/// 执行命令或 Webhook 动作，其他动作由界面层处理
+pub async fn run_external_action(action: &RuleAction, event: &MonitorEvent) -> Result<(), String> {
+    let payload = event_payload(event);
+    let mut command = match action {
+        RuleAction::Command { program, args } => {
+            let mut command = tokio::process::Command::new(program);
+            command
+                .args(args)
+                .env("ISBATTERY_EVENT", payload["event"].as_str().unwrap_or_default())
+                .env("ISBATTERY_PERCENTAGE", event.current_status.battery_percentage.to_string())
+                .env("ISBATTERY_AC_CONNECTED", event.current_status.is_ac_connected.to_string())
+                .env("ISBATTERY_PAYLOAD", payload.to_string());
+            command
+        }
+        RuleAction::Webhook { url } => {
+            let mut command = tokio::process::Command::new("curl");
+            command.args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--max-time",
+                "10",
+                "--header",
+                "Content-Type: application/json",
+                "--data",
+                &payload.to_string(),
+                url,
+            ]);
+            command
+        }
+        _ => return Ok(()),
+    };
+    command.stdin(std::process::Stdio::null()).kill_on_drop(true);
+
+    let output = tokio::time::timeout(EXTERNAL_ACTION_TIMEOUT, command.output())
+        .await
+        .map_err(|_| format!("{:?} timed out", action))?
+        .map_err(|e| format!("Failed to run {:?}: {}", action, e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{:?} exited with {}: {}",
+            action,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::event;
+    use crate::power::BatteryLevel;
+
+    #[test]
+    fn test_default_rules_match_builtin_behavior() {
+        let engine = RuleEngine::default();
+        use RuleAction::{Notification, Sound, Window};
+
+        assert_eq!(engine.actions(&event(PowerEvent::AcDisconnected, false, 80)), vec![Window, Sound, Notification]);
+        assert_eq!(engine.actions(&event(PowerEvent::AcConnected, true, 80)), vec![Notification]);
+
+        let mut warning = BatteryLevel::low(30);
+        warning.action = LevelAction::Notify;
+        let low = PowerEvent::BatteryLow { level: warning, percentage: 30 };
+        assert_eq!(engine.actions(&event(low, false, 30)), vec![Sound, Notification]);
+        let low = PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 20 };
+        assert_eq!(engine.actions(&event(low, false, 20)), vec![Window, Sound, Notification]);
+
+        assert!(engine.actions(&event(PowerEvent::StatusUpdate, false, 50)).is_empty());
+        let device = PowerEvent::DeviceBatteryNormal { device_id: "mouse".to_string(), name: "Mouse".to_string(), percentage: 60 };
+        assert!(engine.actions(&event(device, false, 50)).is_empty());
+    }
+
+    #[test]
+    fn test_conditions() {
+        let rules: Vec<AlertRule> = toml::from_str::<toml::Table>(r#"
+            [[rules]]
+            name = "unplugged-below-half"
+            when = "ac_disconnected"
+            conditions = { battery_below = 50 }
+            then = ["window", "sound", { webhook = { url = "https://example.com/hook" } }]
+
+            [[rules]]
+            when = "battery_low"
+            conditions = { level = "critical", ac_connected = false }
+            then = [{ command = { program = "notify-send", args = ["battery"] } }]
+        "#).unwrap()["rules"].clone().try_into().unwrap();
+        let engine = RuleEngine::new(rules);
+
+        assert!(engine.actions(&event(PowerEvent::AcDisconnected, false, 80)).is_empty());
+        let actions = engine.actions(&event(PowerEvent::AcDisconnected, false, 49));
+        assert_eq!(actions.len(), 3);
+        assert_eq!(actions[2], RuleAction::Webhook { url: "https://example.com/hook".to_string() });
+
+        let mut critical = BatteryLevel::low(5);
+        critical.name = "critical".to_string();
+        let low = PowerEvent::BatteryLow { level: critical, percentage: 5 };
+        assert_eq!(engine.actions(&event(low.clone(), false, 5)).len(), 1);
+        assert!(engine.actions(&event(low, true, 5)).is_empty());
+        let low = PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 20 };
+        assert!(engine.actions(&event(low, false, 20)).is_empty());
+    }
+
+    #[test]
+    fn test_rule_validation() {
+        let mut rule = AlertRule::new("hook", EventKind::AcDisconnected, vec![RuleAction::Webhook { url: "example.com".to_string() }]);
+        assert!(rule.validate().is_err());
+
+        rule.then = vec![RuleAction::Window];
+        assert!(rule.validate().is_ok());
+
+        rule.conditions.battery_below = Some(120);
+        assert!(rule.validate().is_err());
+
+        rule.conditions.battery_below = None;
+        rule.then.clear();
+        assert!(rule.validate().is_err());
+    }
+
+    #[test]
+    fn test_event_payload() {
+        let payload = event_payload(&event(PowerEvent::CriticalBattery(4), false, 4));
+        assert_eq!(payload["event"], "critical_battery");
+        assert_eq!(payload["battery_percentage"], 4);
+        assert_eq!(payload["is_ac_connected"], false);
+    }
+}
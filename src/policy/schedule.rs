@@ -0,0 +1,165 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// 提醒方式，按限制程度从低到高排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertMode {
+    #[default]
+    Normal,  // 提醒窗口、提示音和托盘通知
+    Silent,  // 只显示托盘通知
+    Off,     // 不提醒（托盘状态仍然更新）
+}
+
+/// 一个静音时段
+///
+/// `start` 晚于 `end` 时跨过午夜，例如 22:00–07:00；`days` 指时段开始的那一天，
+/// 为空时每天生效。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    #[serde(default)]
+    pub days: Vec<Weekday>,  // 生效的星期（"Mon"、"Tue" ...），为空表示每天
+    #[serde(with = "hh_mm")]
+    pub start: NaiveTime,    // 开始时间（"HH:MM"）
+    #[serde(with = "hh_mm")]
+    pub end: NaiveTime,      // 结束时间（"HH:MM"），不包含
+    #[serde(default = "default_quiet_mode")]
+    pub mode: AlertMode,
+}
+
+fn default_quiet_mode() -> AlertMode {
+    AlertMode::Silent
+}
+
+impl QuietHours {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start == self.end {
+            return Err(format!("静音时段 {} 的开始和结束时间不能相同", self.start.format("%H:%M")));
+        }
+        if self.mode == AlertMode::Normal {
+            return Err("静音时段的提醒方式应为 silent 或 off".to_string());
+        }
+        Ok(())
+    }
+
+    /// 该时段是否覆盖 `now`
+    pub fn contains(&self, now: DateTime<Local>) -> bool {
+        let time = now.time();
+        if self.start < self.end {
+            self.applies_on(now.weekday()) && time >= self.start && time < self.end
+        } else {
+            // 跨午夜：午夜之前属于当天开始的时段，午夜之后属于前一天开始的时段
+            (time >= self.start && self.applies_on(now.weekday()))
+                || (time < self.end && self.applies_on((now - Duration::days(1)).weekday()))
+        }
+    }
+
+    fn applies_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+/// `now` 所在的静音时段中限制程度最高的提醒方式，不在任何时段内时为 `Normal`
+pub fn scheduled_mode(quiet_hours: &[QuietHours], now: DateTime<Local>) -> AlertMode {
+    quiet_hours
+        .iter()
+        .filter(|period| period.contains(now))
+        .map(|period| period.mode)
+        .max()
+        .unwrap_or_default()
+}
+
+/// 以 "HH:MM" 格式读写时间
+mod hh_mm {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%H:%M";
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&time.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&text, FORMAT)
+            .map_err(|_| serde::de::Error::custom(format!("无效的时间 \"{}\"，应为 HH:MM 格式", text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::at;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    // 2025-09-01 是星期一
+    #[test]
+    fn test_daytime_period() {
+        let meetings = QuietHours {
+            days: vec![Weekday::Mon, Weekday::Wed],
+            start: time(14, 0),
+            end: time(16, 30),
+            mode: AlertMode::Silent,
+        };
+        assert!(meetings.contains(at(1, 14, 0)));
+        assert!(meetings.contains(at(3, 16, 29)));
+        assert!(!meetings.contains(at(1, 16, 30)));
+        assert!(!meetings.contains(at(2, 15, 0)));
+    }
+
+    #[test]
+    fn test_overnight_period() {
+        // 周五晚上开始，周六早上结束
+        let overnight = QuietHours {
+            days: vec![Weekday::Fri],
+            start: time(22, 0),
+            end: time(7, 0),
+            mode: AlertMode::Off,
+        };
+        assert!(overnight.contains(at(5, 23, 0)));
+        assert!(overnight.contains(at(6, 6, 59)));
+        assert!(!overnight.contains(at(6, 23, 0)));
+        assert!(!overnight.contains(at(5, 6, 0)));
+    }
+
+    #[test]
+    fn test_most_restrictive_mode_wins() {
+        let quiet_hours = vec![
+            QuietHours { days: Vec::new(), start: time(22, 0), end: time(8, 0), mode: AlertMode::Silent },
+            QuietHours { days: Vec::new(), start: time(0, 0), end: time(6, 0), mode: AlertMode::Off },
+        ];
+        assert_eq!(scheduled_mode(&quiet_hours, at(1, 12, 0)), AlertMode::Normal);
+        assert_eq!(scheduled_mode(&quiet_hours, at(1, 23, 0)), AlertMode::Silent);
+        assert_eq!(scheduled_mode(&quiet_hours, at(2, 3, 0)), AlertMode::Off);
+    }
+
+    #[test]
+    fn test_quiet_hours_toml() {
+        #[derive(Deserialize)]
+        struct Config {
+            quiet_hours: Vec<QuietHours>,
+        }
+
+        let config: Config = toml::from_str(r#"
+            [[quiet_hours]]
+            days = ["Mon", "Tue", "Wed", "Thu", "Fri"]
+            start = "09:30"
+            end = "11:00"
+
+            [[quiet_hours]]
+            start = "23:00"
+            end = "07:00"
+            mode = "off"
+        "#).unwrap();
+
+        assert_eq!(config.quiet_hours[0].days.len(), 5);
+        assert_eq!(config.quiet_hours[0].start, time(9, 30));
+        assert_eq!(config.quiet_hours[0].mode, AlertMode::Silent);
+        assert_eq!(config.quiet_hours[1].mode, AlertMode::Off);
+        assert!(toml::from_str::<Config>("[[quiet_hours]]\nstart = \"9h\"\nend = \"10:00\"").is_err());
+    }
+}
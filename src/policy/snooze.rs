@@ -0,0 +1,259 @@
+use crate::power::PowerEvent;
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 暂停提醒的时长选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnoozeDuration {
+    Minutes(u32),   // 暂停指定分钟数
+    UntilPlugIn,    // 直到下次接通电源
+    UntilTomorrow,  // 直到明天 0 点
+}
+
+/// 暂停提醒的结束条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnoozeUntil {
+    Time(DateTime<Local>),
+    PlugIn,
+}
+
+/// 一次暂停提醒
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snooze {
+    pub since: DateTime<Local>,
+    pub until: SnoozeUntil,
+}
+
+impl Snooze {
+    pub fn new(duration: SnoozeDuration, now: DateTime<Local>) -> Self {
+        let until = match duration {
+            SnoozeDuration::Minutes(minutes) => SnoozeUntil::Time(now + Duration::minutes(minutes as i64)),
+            SnoozeDuration::UntilPlugIn => SnoozeUntil::PlugIn,
+            SnoozeDuration::UntilTomorrow => SnoozeUntil::Time(next_midnight(now)),
+        };
+        Self { since: now, until }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Local>) -> bool {
+        match self.until {
+            SnoozeUntil::Time(until) => now >= until,
+            SnoozeUntil::PlugIn => false,
+        }
+    }
+
+    /// 剩余时间，直到接通电源时返回 `None`
+    pub fn remaining(&self, now: DateTime<Local>) -> Option<Duration> {
+        match self.until {
+            SnoozeUntil::Time(until) => Some((until - now).max(Duration::zero())),
+            SnoozeUntil::PlugIn => None,
+        }
+    }
+}
+
+/// 下一个本地时间 0 点（夏令时切换导致 0 点不存在时取之后最早的有效时间）
+fn next_midnight(now: DateTime<Local>) -> DateTime<Local> {
+    let tomorrow = now.date_naive() + Duration::days(1);
+    let midnight = tomorrow.and_time(NaiveTime::MIN);
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| now + Duration::days(1))
+}
+
+/// 暂停提醒状态管理
+///
+/// 暂停期间仍然检测电源状态（托盘信息保持更新），只是不弹出提醒窗口、不播放提示音、
+/// 不显示托盘通知（由 `AlertPolicy` 决定，严重低电量不受暂停影响）。状态保存在配置目录的 `snooze.json` 中，
+/// 程序重启后继续生效。
+pub struct SnoozeManager {
+    path: Option<PathBuf>,
+    current: Mutex<Option<Snooze>>,
+}
+
+impl SnoozeManager {
+    /// 从文件恢复暂停状态，文件不存在、无法解析或已经过期时视为未暂停
+    pub fn load(path: PathBuf) -> Self {
+        let current = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Snooze>(&content).ok())
+            .filter(|snooze| !snooze.is_expired(Local::now()));
+
+        let manager = Self {
+            path: Some(path),
+            current: Mutex::new(current),
+        };
+        if current.is_none() {
+            manager.save(None);
+        }
+        manager
+    }
+
+    /// 不保存到文件的暂停状态
+    #[allow(dead_code)]
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// 暂停状态文件的默认位置（与配置文件同目录）
+    pub fn default_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(crate::config::AppConfig::get_config_path()?.with_file_name("snooze.json"))
+    }
+
+    /// 开始暂停提醒（替换正在进行的暂停）
+    pub fn snooze(&self, duration: SnoozeDuration, now: DateTime<Local>) -> Snooze {
+        let snooze = Snooze::new(duration, now);
+        *self.current.lock().unwrap() = Some(snooze);
+        self.save(Some(&snooze));
+        snooze
+    }
+
+    /// 取消暂停提醒，返回是否原本处于暂停状态
+    pub fn cancel(&self) -> bool {
+        let previous = self.current.lock().unwrap().take();
+        self.save(None);
+        previous.is_some()
+    }
+
+    /// 当前的暂停提醒，已经到期时清除并返回 `None`
+    pub fn current(&self, now: DateTime<Local>) -> Option<Snooze> {
+        let mut current = self.current.lock().unwrap();
+        match *current {
+            Some(snooze) if snooze.is_expired(now) => {
+                *current = None;
+                drop(current);
+                self.save(None);
+                None
+            }
+            snooze => snooze,
+        }
+    }
+
+    pub fn is_active(&self, now: DateTime<Local>) -> bool {
+        self.current(now).is_some()
+    }
+
+    /// 接通电源时结束“直到接通电源”的暂停，返回是否结束了暂停
+    pub fn on_power_event(&self, event: &PowerEvent) -> bool {
+        if *event != PowerEvent::AcConnected {
+            return false;
+        }
+
+        let mut current = self.current.lock().unwrap();
+        if !matches!(*current, Some(Snooze { until: SnoozeUntil::PlugIn, .. })) {
+            return false;
+        }
+        *current = None;
+        drop(current);
+        self.save(None);
+        true
+    }
+
+    fn save(&self, snooze: Option<&Snooze>) {
+        if let Some(path) = self.path.as_deref() {
+            if let Err(e) = Self::write(path, snooze) {
+                crate::log_error!("Failed to save snooze state: {}", e);
+            }
+        }
+    }
+
+    fn write(path: &Path, snooze: Option<&Snooze>) -> Result<(), Box<dyn std::error::Error>> {
+        match snooze {
+            Some(snooze) => std::fs::write(path, serde_json::to_string_pretty(snooze)?)?,
+            None if path.exists() => std::fs::remove_file(path)?,
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::at;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("isbattery-snooze-{}-{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_snooze_durations() {
+        let now = at(1, 21, 30);
+
+        let snooze = Snooze::new(SnoozeDuration::Minutes(15), now);
+        assert_eq!(snooze.remaining(now), Some(Duration::minutes(15)));
+        assert!(!snooze.is_expired(at(1, 21, 44)));
+        assert!(snooze.is_expired(at(1, 21, 45)));
+
+        let snooze = Snooze::new(SnoozeDuration::UntilTomorrow, now);
+        assert_eq!(snooze.until, SnoozeUntil::Time(at(2, 0, 0)));
+
+        let snooze = Snooze::new(SnoozeDuration::UntilPlugIn, now);
+        assert_eq!(snooze.remaining(now), None);
+        assert!(!snooze.is_expired(now + Duration::days(30)));
+    }
+
+    #[test]
+    fn test_snooze_active_until_expired() {
+        let manager = SnoozeManager::in_memory();
+        let now = at(1, 10, 0);
+        assert!(!manager.is_active(now));
+
+        manager.snooze(SnoozeDuration::Minutes(60), now);
+        assert!(manager.is_active(at(1, 10, 30)));
+
+        assert!(!manager.is_active(at(1, 11, 0)));
+        assert_eq!(manager.current(at(1, 10, 30)), None);
+    }
+
+    #[test]
+    fn test_plug_in_ends_snooze() {
+        let manager = SnoozeManager::in_memory();
+        manager.snooze(SnoozeDuration::UntilPlugIn, at(1, 10, 0));
+
+        assert!(!manager.on_power_event(&PowerEvent::AcDisconnected));
+        assert!(manager.is_active(at(1, 23, 0)));
+        assert!(manager.on_power_event(&PowerEvent::AcConnected));
+        assert!(!manager.is_active(at(1, 23, 0)));
+
+        // 按时间暂停时接通电源不影响
+        manager.snooze(SnoozeDuration::Minutes(15), at(1, 10, 0));
+        assert!(!manager.on_power_event(&PowerEvent::AcConnected));
+        assert!(manager.is_active(at(1, 10, 5)));
+    }
+
+    #[test]
+    fn test_snooze_survives_restart() {
+        let path = temp_path("restart");
+
+        let manager = SnoozeManager::load(path.clone());
+        let snooze = manager.snooze(SnoozeDuration::UntilPlugIn, Local::now());
+        drop(manager);
+
+        let manager = SnoozeManager::load(path.clone());
+        assert_eq!(manager.current(Local::now()), Some(snooze));
+
+        assert!(manager.cancel());
+        assert!(!path.exists());
+        assert_eq!(SnoozeManager::load(path.clone()).current(Local::now()), None);
+    }
+
+    #[test]
+    fn test_expired_snooze_discarded_on_load() {
+        let path = temp_path("expired");
+        let expired = Snooze::new(SnoozeDuration::Minutes(15), Local::now() - Duration::hours(1));
+        std::fs::write(&path, serde_json::to_string(&expired).unwrap()).unwrap();
+
+        let manager = SnoozeManager::load(path.clone());
+        assert_eq!(manager.current(Local::now()), None);
+        assert!(!path.exists());
+    }
+}
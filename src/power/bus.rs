@@ -0,0 +1,155 @@
+use crate::power::{BatteryStatus, MonitorEvent, PowerEvent};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+
+/// 事件总线默认缓冲的事件数
+pub const DEFAULT_BUS_CAPACITY: usize = 256;
+
+/// 监控事件总线
+///
+/// 监控循环把事件发布到总线，托盘、提醒窗口、声音、历史记录等各自订阅，互不影响。
+/// 总线不会因为某个订阅者处理缓慢而阻塞：缓冲区写满后最旧的事件被覆盖，
+/// 落后的订阅者在下一次接收时得到处理（见 `EventSubscriber::recv`）。
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<MonitorEvent>,
+    latest: Arc<Mutex<Option<BatteryStatus>>>,  // 最近一次发布的电源状态，用于落后订阅者重新同步
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            latest: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// 发布事件，返回收到事件的订阅者数量（没有订阅者时事件被丢弃）
+    pub fn publish(&self, event: MonitorEvent) -> usize {
+        *self.latest.lock().unwrap() = Some(event.current_status.clone());
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    /// 订阅之后发布的事件，`name` 用于日志
+    pub fn subscribe(&self, name: &str) -> EventSubscriber {
+        EventSubscriber {
+            name: name.to_string(),
+            receiver: self.sender.subscribe(),
+            latest: Arc::clone(&self.latest),
+            lagged: 0,
+        }
+    }
+
+    /// 当前的订阅者数量
+    #[allow(dead_code)]
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUS_CAPACITY)
+    }
+}
+
+/// 事件总线的一个订阅者
+#[derive(Debug)]
+pub struct EventSubscriber {
+    name: String,
+    receiver: broadcast::Receiver<MonitorEvent>,
+    latest: Arc<Mutex<Option<BatteryStatus>>>,
+    lagged: u64,
+}
+
+impl EventSubscriber {
+    /// 接收下一个事件，总线关闭后返回 `None`
+    ///
+    /// 订阅者落后、缓冲区中的事件已被覆盖时，丢失的事件无法恢复：记录日志和丢失数量，
+    /// 先返回一次带最新状态的 `StatusUpdate` 让订阅者刷新显示，再继续接收之后的事件。
+    pub async fn recv(&mut self) -> Option<MonitorEvent> {
+        match self.receiver.recv().await {
+            Ok(event) => Some(event),
+            Err(RecvError::Lagged(skipped)) => self.resync(skipped),
+            Err(RecvError::Closed) => None,
+        }
+    }
+
+    /// 不等待地接收一个事件，暂时没有事件或总线已关闭时返回 `None`
+    #[allow(dead_code)]
+    pub fn try_recv(&mut self) -> Option<MonitorEvent> {
+        match self.receiver.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Lagged(skipped)) => self.resync(skipped),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => None,
+        }
+    }
+
+    /// 累计丢失的事件数
+    #[allow(dead_code)]
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+
+    fn resync(&mut self, skipped: u64) -> Option<MonitorEvent> {
+        self.lagged += skipped;
+        crate::log_error!("Event subscriber '{}' lagged behind, {} events skipped", self.name, skipped);
+
+        let latest = self.latest.lock().unwrap().clone()?;
+        Some(MonitorEvent {
+            power_event: PowerEvent::StatusUpdate,
+            current_status: latest,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::event;
+
+    #[tokio::test]
+    async fn test_every_subscriber_receives_events() {
+        let bus = EventBus::default();
+        let mut tray = bus.subscribe("tray");
+        let mut alerts = bus.subscribe("alerts");
+        assert_eq!(bus.subscriber_count(), 2);
+
+        assert_eq!(bus.publish(event(PowerEvent::AcDisconnected, false, 80)), 2);
+        assert_eq!(tray.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+        assert_eq!(alerts.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+
+        // 订阅之前发布的事件不会收到
+        let mut late = bus.subscribe("late");
+        assert!(late.try_recv().is_none());
+
+        drop(bus);
+        assert!(tray.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_does_not_block_others() {
+        let bus = EventBus::new(4);
+        let mut slow = bus.subscribe("slow");
+        let mut fast = bus.subscribe("fast");
+
+        for percentage in (60..70).rev() {
+            bus.publish(event(PowerEvent::StatusUpdate, false, percentage));
+            assert_eq!(fast.recv().await.unwrap().current_status.battery_percentage, percentage);
+        }
+
+        // 落后的订阅者先收到带最新状态的同步事件，再从仍在缓冲区中的事件继续
+        let resync = slow.recv().await.unwrap();
+        assert_eq!(resync.power_event, PowerEvent::StatusUpdate);
+        assert_eq!(resync.current_status.battery_percentage, 60);
+        assert_eq!(slow.lagged(), 6);
+        assert_eq!(slow.recv().await.unwrap().current_status.battery_percentage, 63);
+    }
+
+    #[test]
+    fn test_publish_without_subscribers() {
+        let bus = EventBus::default();
+        assert_eq!(bus.publish(event(PowerEvent::AcConnected, false, 50)), 0);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::power::{BatteryLevel, BatteryStatus, PowerEvent, PowerEventConfig};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 已经触发、等待电量回升后重新生效的阈值
+#[derive(Debug, Clone, PartialEq)]
+enum Threshold {
+    Level(String),  // 电量提醒级别（按名称区分）
+    Critical,       // PowerEventConfig::critical_threshold
+}
+
+/// 电源事件去抖
+///
+/// 充电器接触不良时 AC 状态会反复变化，电量停在阈值附近时会反复越过阈值，
+/// 直接使用 `detect_power_events` 的结果会连续弹出提醒窗口。这里对事件做两种处理：
+/// - AC 状态变化需要保持 `ac_min_dwell_secs` 秒才会报告；
+/// - 电量阈值触发后，电量回升到阈值 + `recovery_margin_percent` 以上才会恢复
+///   （重新生效、产生 `BatteryNormal`）。
+#[derive(Debug, Clone)]
+pub struct EventDebouncer {
+    config: PowerEventConfig,
+    confirmed_ac: Option<bool>,
+    pending_ac: Option<(bool, Instant)>,
+    disarmed: Vec<(Threshold, u8)>,
+    is_low: bool,
+}
+
+impl EventDebouncer {
+    pub fn new(config: PowerEventConfig) -> Self {
+        Self {
+            config,
+            confirmed_ac: None,
+            pending_ac: None,
+            disarmed: Vec::new(),
+            is_low: false,
+        }
+    }
+
+    /// 过滤本次检测产生的事件
+    ///
+    /// 首次调用时以当前状态为基准，AC 事件原样保留（首次检测的提醒不需要去抖）。
+    pub fn filter(
+        &mut self,
+        at: Instant,
+        status: &BatteryStatus,
+        levels: &[BatteryLevel],
+        events: Vec<PowerEvent>,
+    ) -> Vec<PowerEvent> {
+        let initial = self.confirmed_ac.is_none();
+        let mut filtered = Vec::new();
+
+        if initial {
+            self.confirmed_ac = Some(status.is_ac_connected);
+            self.is_low = status.is_battery_present && BatteryLevel::current(levels, status.battery_percentage).is_some();
+        } else if let Some(event) = self.debounce_ac(at, status.is_ac_connected) {
+            filtered.push(event);
+        }
+
+        for event in events {
+            match event {
+                PowerEvent::AcConnected | PowerEvent::AcDisconnected => {
+                    if initial {
+                        filtered.push(event);
+                    }
+                }
+                PowerEvent::BatteryLow { ref level, .. } => {
+                    if self.disarm(Threshold::Level(level.name.clone()), level.threshold) {
+                        self.is_low = true;
+                        filtered.push(event);
+                    }
+                }
+                PowerEvent::CriticalBattery(_) => {
+                    if self.disarm(Threshold::Critical, self.config.critical_threshold) {
+                        filtered.push(event);
+                    }
+                }
+                // 恢复正常由下面按回差判断
+                PowerEvent::BatteryNormal(_) => {}
+                other => filtered.push(other),
+            }
+        }
+
+        if !status.is_battery_present {
+            return filtered;
+        }
+
+        let percentage = status.battery_percentage;
+        let margin = self.config.recovery_margin_percent;
+        self.disarmed.retain(|(_, threshold)| percentage <= threshold.saturating_add(margin));
+
+        if self.is_low {
+            let recovered = match levels.first() {
+                Some(highest) => percentage > highest.threshold.saturating_add(margin),
+                None => true,
+            };
+            if recovered {
+                self.is_low = false;
+                filtered.push(PowerEvent::BatteryNormal(percentage));
+            }
+        }
+
+        filtered
+    }
+
+    /// 阈值仍然有效时标记为已触发并返回 true
+    fn disarm(&mut self, key: Threshold, threshold: u8) -> bool {
+        if self.disarmed.iter().any(|(k, _)| *k == key) {
+            return false;
+        }
+        self.disarmed.push((key, threshold));
+        true
+    }
+
+    /// 新的 AC 状态保持足够长时间后返回对应事件
+    fn debounce_ac(&mut self, at: Instant, is_ac_connected: bool) -> Option<PowerEvent> {
+        if self.confirmed_ac == Some(is_ac_connected) {
+            self.pending_ac = None;
+            return None;
+        }
+
+        let since = match self.pending_ac {
+            Some((pending, since)) if pending == is_ac_connected => since,
+            _ => {
+                self.pending_ac = Some((is_ac_connected, at));
+                at
+            }
+        };
+
+        let dwell = Duration::from_secs(self.config.ac_min_dwell_secs);
+        if at.saturating_duration_since(since) < dwell {
+            return None;
+        }
+
+        self.confirmed_ac = Some(is_ac_connected);
+        self.pending_ac = None;
+        Some(if is_ac_connected {
+            PowerEvent::AcConnected
+        } else {
+            PowerEvent::AcDisconnected
+        })
+    }
+}
+
+impl Default for EventDebouncer {
+    fn default() -> Self {
+        Self::new(PowerEventConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::PowerDetector;
+    use crate::power::test_support::status;
+
+    /// 依次喂入状态序列（每 10 秒一次），返回去抖后的事件
+    fn feed(debouncer: &mut EventDebouncer, levels: &[BatteryLevel], statuses: &[BatteryStatus]) -> Vec<PowerEvent> {
+        let detector = PowerDetector::new();
+        let start = Instant::now();
+        let mut events = Vec::new();
+        let mut previous: Option<&BatteryStatus> = None;
+
+        for (i, current) in statuses.iter().enumerate() {
+            let raw = match previous {
+                Some(previous) => detector.detect_power_events(previous, current, levels),
+                None => Vec::new(),
+            };
+            let at = start + Duration::from_secs(10 * i as u64);
+            events.extend(debouncer.filter(at, current, levels, raw));
+            previous = Some(current);
+        }
+        events
+    }
+
+    #[test]
+    fn test_battery_hovering_at_threshold() {
+        let mut debouncer = EventDebouncer::new(PowerEventConfig {
+            recovery_margin_percent: 5,
+            ..Default::default()
+        });
+        let levels = [BatteryLevel::low(20)];
+        let noisy: Vec<BatteryStatus> = [21, 20, 21, 20, 22, 19, 21, 20].iter().map(|p| status(false, *p)).collect();
+
+        let events = feed(&mut debouncer, &levels, &noisy);
+        assert_eq!(events.len(), 1, "{:?}", events);
+        assert!(matches!(events[0], PowerEvent::BatteryLow { percentage: 20, .. }));
+
+        // 回升到 20 + 5 以上才恢复，之后再次降到阈值时重新提醒
+        let recovering: Vec<BatteryStatus> = [20, 25, 26, 20].iter().map(|p| status(true, *p)).collect();
+        let events = feed(&mut debouncer, &levels, &recovering);
+        let battery_events: Vec<&PowerEvent> = events
+            .iter()
+            .filter(|e| !matches!(e, PowerEvent::AcConnected | PowerEvent::AcDisconnected))
+            .collect();
+        assert_eq!(battery_events.len(), 2, "{:?}", events);
+        assert_eq!(*battery_events[0], PowerEvent::BatteryNormal(26));
+        assert!(matches!(battery_events[1], PowerEvent::BatteryLow { percentage: 20, .. }));
+    }
+
+    #[test]
+    fn test_zero_margin_matches_raw_events() {
+        let mut debouncer = EventDebouncer::new(PowerEventConfig {
+            recovery_margin_percent: 0,
+            ..Default::default()
+        });
+        let levels = [BatteryLevel::low(20)];
+        let statuses: Vec<BatteryStatus> = [21, 20, 21, 20].iter().map(|p| status(false, *p)).collect();
+
+        let events = feed(&mut debouncer, &levels, &statuses);
+        assert_eq!(events.len(), 3, "{:?}", events);
+        assert_eq!(events[1], PowerEvent::BatteryNormal(21));
+    }
+
+    #[test]
+    fn test_flapping_charger() {
+        let mut debouncer = EventDebouncer::new(PowerEventConfig {
+            ac_min_dwell_secs: 15,
+            ..Default::default()
+        });
+        let levels = [BatteryLevel::low(20)];
+        // 每 10 秒采样一次：接触不良时 AC 状态来回跳变，最后稳定为断开
+        let flapping: Vec<BatteryStatus> = [true, false, true, false, true, false, false, false]
+            .iter()
+            .map(|ac| status(*ac, 80))
+            .collect();
+
+        let events = feed(&mut debouncer, &levels, &flapping);
+        assert_eq!(events, vec![PowerEvent::AcDisconnected]);
+    }
+
+    #[test]
+    fn test_zero_dwell_reports_immediately() {
+        let mut debouncer = EventDebouncer::new(PowerEventConfig {
+            ac_min_dwell_secs: 0,
+            ..Default::default()
+        });
+        let statuses = [status(true, 80), status(false, 80), status(true, 80)];
+
+        let events = feed(&mut debouncer, &[BatteryLevel::low(20)], &statuses);
+        assert_eq!(events, vec![PowerEvent::AcDisconnected, PowerEvent::AcConnected]);
+    }
+
+    #[test]
+    fn test_critical_battery_hysteresis() {
+        let mut debouncer = EventDebouncer::new(PowerEventConfig {
+            critical_threshold: 5,
+            recovery_margin_percent: 3,
+            ..Default::default()
+        });
+        let now = Instant::now();
+        let levels = [BatteryLevel::low(20)];
+
+        assert!(debouncer.filter(now, &status(false, 6), &levels, Vec::new()).is_empty());
+        let critical = vec![PowerEvent::CriticalBattery(5)];
+        assert_eq!(debouncer.filter(now, &status(false, 5), &levels, critical.clone()), critical);
+        assert!(debouncer.filter(now, &status(false, 7), &levels, Vec::new()).is_empty());
+        assert!(debouncer.filter(now, &status(false, 5), &levels, critical.clone()).is_empty());
+        assert!(debouncer.filter(now, &status(false, 9), &levels, Vec::new()).is_empty());
+        assert_eq!(debouncer.filter(now, &status(false, 5), &levels, critical.clone()), critical);
+    }
+}
@@ -1,7 +1,18 @@
+use crate::power::{
+    default_power_source, BatteryHealth, BatteryLevel, ChangeReceiver, DrainRate, PowerDevice, PowerSource,
+    PredictionDirection, Provenance, RemainingTimePrediction, RemainingTimePredictor, SourceCapabilities,
+    StatusProvenance,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+use std::sync::{Arc, Mutex};
+use tokio::time::Instant;
+
+/// 系统电源状态
+///
+/// 顶层字段是系统电池的汇总视图（多块电池时合并计算），
+/// `devices` 中列出后端识别到的每个电源设备，包括无线外设。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct BatteryStatus {
     pub is_charging: bool,
     pub is_ac_connected: bool,
@@ -12,14 +23,61 @@ pub struct BatteryStatus {
     pub battery_capacity_mwh: Option<u32>,    // 电池容量（毫瓦时）
     pub remaining_time_minutes: Option<u32>,  // 剩余时间（分钟）
     pub charge_rate_watts: Option<f32>,       // 充电/放电速率（瓦特）
+    #[serde(default)]
+    pub devices: Vec<PowerDevice>,            // 各个电源设备（电池、适配器、外设）
+    #[serde(default)]
+    pub provenance: StatusProvenance,         // 各数值字段的来源（实测/计算/估算）
+    #[serde(default)]
+    pub prediction: Option<RemainingTimePrediction>,  // 根据电量历史预测的耗尽/充满时间
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl BatteryStatus {
+    /// 外设电池（鼠标、键盘、耳机等）
+    pub fn peripherals(&self) -> impl Iterator<Item = &PowerDevice> {
+        self.devices.iter().filter(|d| d.is_peripheral())
+    }
+
+    /// 去掉估算值后的状态，供只接受真实数据的使用方使用
+    pub fn without_estimates(&self) -> Self {
+        let mut status = self.clone();
+        let provenance = &mut status.provenance;
+
+        if provenance.power_draw_watts.is_estimated() {
+            status.power_draw_watts = None;
+            provenance.power_draw_watts = Provenance::Unknown;
+        }
+        if provenance.battery_capacity_mwh.is_estimated() {
+            status.battery_capacity_mwh = None;
+            provenance.battery_capacity_mwh = Provenance::Unknown;
+        }
+        if provenance.remaining_time_minutes.is_estimated() {
+            status.remaining_time_minutes = None;
+            provenance.remaining_time_minutes = Provenance::Unknown;
+        }
+        if provenance.charge_rate_watts.is_estimated() {
+            status.charge_rate_watts = None;
+            provenance.charge_rate_watts = Provenance::Unknown;
+        }
+
+        status
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PowerEvent {
     AcConnected,
     AcDisconnected,
-    BatteryLow(u8),
+    BatteryLow { level: BatteryLevel, percentage: u8 },  // 电量向下越过某个提醒级别
     BatteryNormal(u8),
+    DeviceBatteryLow { device_id: String, name: String, percentage: u8 },    // 外设电量不足
+    DeviceBatteryNormal { device_id: String, name: String, percentage: u8 }, // 外设电量恢复
+    CriticalBattery(u8),                           // 电量低于严重不足阈值
+    RapidDrain { watts_or_pct_per_min: DrainRate }, // 耗电过快
+    ChargingStalled(u8),                           // 接通电源但长时间未充电
+    FullyCharged,
+    BatteryRemoved,
+    BatteryInserted,
+    BatteryDegraded { wear_percent: f32 },        // 电池损耗达到提醒阈值
     StatusUpdate, // 用于状态更新（不是事件变化）
 }
 
@@ -28,83 +86,109 @@ impl fmt::Display for PowerEvent {
         match self {
             PowerEvent::AcConnected => write!(f, "AC电源已连接"),
             PowerEvent::AcDisconnected => write!(f, "AC电源已断开"),
-            PowerEvent::BatteryLow(percentage) => write!(f, "电池电量不足: {}%", percentage),
+            PowerEvent::BatteryLow { level, percentage } => write!(f, "电池电量低于 {} 级别: {}%", level.name, percentage),
             PowerEvent::BatteryNormal(percentage) => write!(f, "电池电量正常: {}%", percentage),
+            PowerEvent::DeviceBatteryLow { name, percentage, .. } => write!(f, "{} 电量不足: {}%", name, percentage),
+            PowerEvent::DeviceBatteryNormal { name, percentage, .. } => write!(f, "{} 电量正常: {}%", name, percentage),
+            PowerEvent::CriticalBattery(percentage) => write!(f, "电池电量严重不足: {}%", percentage),
+            PowerEvent::RapidDrain { watts_or_pct_per_min } => write!(f, "耗电过快: {}", watts_or_pct_per_min),
+            PowerEvent::ChargingStalled(percentage) => write!(f, "已接通电源但未充电: {}%", percentage),
+            PowerEvent::FullyCharged => write!(f, "电池已充满"),
+            PowerEvent::BatteryRemoved => write!(f, "电池已移除"),
+            PowerEvent::BatteryInserted => write!(f, "电池已插入"),
+            PowerEvent::BatteryDegraded { wear_percent } => write!(f, "电池损耗已达 {:.1}%", wear_percent),
             PowerEvent::StatusUpdate => write!(f, "状态更新"),
         }
     }
 }
 
-pub struct PowerDetector;
+/// 电源状态检测器：从后端读取状态，并根据前后两次状态判断电源事件
+///
+/// 克隆出的检测器共享同一个后端和剩余时间预测器，
+/// 因此监控循环和界面命令的查询都会计入电量历史。
+#[derive(Clone)]
+pub struct PowerDetector {
+    source: Arc<dyn PowerSource>,
+    predictor: Arc<Mutex<RemainingTimePredictor>>,
+}
 
 impl PowerDetector {
+    /// 使用当前平台的默认后端创建检测器
     pub fn new() -> Self {
-        Self
+        Self::with_source(default_power_source())
+    }
+
+    /// 使用指定的电源后端创建检测器
+    pub fn with_source(source: Arc<dyn PowerSource>) -> Self {
+        Self {
+            source,
+            predictor: Arc::new(Mutex::new(RemainingTimePredictor::default())),
+        }
+    }
+
+    /// 获取当前使用的电源后端
+    #[allow(dead_code)]
+    pub fn source(&self) -> &Arc<dyn PowerSource> {
+        &self.source
     }
 
     /// 获取当前电源状态
+    ///
+    /// 每次查询都会记录到剩余时间预测器；后端没有提供剩余时间或只有估算值时，
+    /// 使用根据电量历史预测的结果代替。
     pub fn get_power_status(&self) -> Result<BatteryStatus, String> {
-        #[cfg(target_os = "windows")]
-        {
-            use windows::Win32::System::Power::{
-                GetSystemPowerStatus, SYSTEM_POWER_STATUS
-            };
-
-            unsafe {
-                let mut status = SYSTEM_POWER_STATUS::default();
-                if GetSystemPowerStatus(&mut status).is_err() {
-                    return Err("Failed to get system power status".to_string());
-                }
-                
-                let is_ac_connected = status.ACLineStatus == 1;
-                let is_charging = status.BatteryFlag & 8 != 0; // 充电状态
-                let is_battery_present = status.BatteryFlag != 128; // 128表示没有电池
-                
-                // 电池百分比，255表示未知
-                let battery_percentage = if status.BatteryLifePercent == 255 {
-                    100 // 如果无法获取，默认为100%
-                } else {
-                    status.BatteryLifePercent as u8
-                };
-
-                // 获取详细的电池信息
-                let (power_draw_watts, battery_capacity_mwh, remaining_time_minutes, charge_rate_watts) = 
-                    self.get_advanced_battery_info();
-
-                Ok(BatteryStatus {
-                    is_charging,
-                    is_ac_connected,
-                    battery_percentage,
-                    is_battery_present,
-                    power_draw_watts,
-                    battery_capacity_mwh,
-                    remaining_time_minutes,
-                    charge_rate_watts,
-                })
+        let mut status = self.source.get_power_status()?;
+
+        let prediction = self.predictor.lock().unwrap().observe(Instant::now(), &status);
+        status.prediction = prediction;
+
+        if let Some(prediction) = prediction {
+            let replace = status.remaining_time_minutes.is_none()
+                || status.provenance.remaining_time_minutes.is_estimated();
+            if prediction.direction == PredictionDirection::ToEmpty && replace {
+                status.remaining_time_minutes = Some(prediction.minutes);
+                status.provenance.remaining_time_minutes = Provenance::Derived;
             }
         }
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            // 对于非Windows平台，返回默认状态
-            Ok(BatteryStatus {
-                is_charging: false,
-                is_ac_connected: true,
-                battery_percentage: 100,
-                is_battery_present: false,
-                power_draw_watts: None,
-                battery_capacity_mwh: None,
-                remaining_time_minutes: None,
-                charge_rate_watts: None,
-            })
-        }
+        Ok(status)
+    }
+
+    /// 清空剩余时间预测的电量历史（例如恢复监控后）
+    #[allow(dead_code)]
+    pub fn reset_prediction(&self) {
+        self.predictor.lock().unwrap().reset();
+    }
+
+    /// 列出当前后端识别到的电源设备
+    pub fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        self.source.list_devices()
+    }
+
+    /// 获取电池健康信息（设计容量、满充容量、循环次数等）
+    pub fn battery_health(&self) -> Result<Option<BatteryHealth>, String> {
+        self.source.battery_health()
+    }
+
+    /// 订阅后端推送的电源变化通知
+    pub fn subscribe_changes(&self) -> Option<ChangeReceiver> {
+        self.source.subscribe_changes()
+    }
+
+    /// 当前后端的能力描述
+    #[allow(dead_code)]
+    pub fn capabilities(&self) -> SourceCapabilities {
+        self.source.capabilities()
     }
 
     /// 检测电源状态变化
+    ///
+    /// `levels` 按阈值从高到低排列：电量每向下越过一个级别产生一个 `BatteryLow`，
+    /// 回升到最高级别以上时产生 `BatteryNormal`。外设使用最高级别的阈值。
     pub fn detect_power_events(&self, 
         previous_status: &BatteryStatus, 
         current_status: &BatteryStatus,
-        low_battery_threshold: u8
+        levels: &[BatteryLevel]
     ) -> Vec<PowerEvent> {
         let mut events = Vec::new();
 
@@ -119,13 +203,51 @@ impl PowerDetector {
 
         // 检测电池电量变化
         if current_status.is_battery_present {
-            let was_low = previous_status.battery_percentage <= low_battery_threshold;
-            let is_low = current_status.battery_percentage <= low_battery_threshold;
+            let previous = previous_status.battery_percentage;
+            let current = current_status.battery_percentage;
+
+            for level in BatteryLevel::crossed_down(levels, previous, current) {
+                events.push(PowerEvent::BatteryLow {
+                    level: level.clone(),
+                    percentage: current,
+                });
+            }
+
+            let was_low = BatteryLevel::current(levels, previous).is_some();
+            let is_low = BatteryLevel::current(levels, current).is_some();
+            if was_low && !is_low {
+                events.push(PowerEvent::BatteryNormal(current));
+            }
+        }
+
+        let low_battery_threshold = match levels.first() {
+            Some(level) => level.threshold,
+            None => return events,
+        };
+
+        // 检测外设电量变化（按设备ID匹配，新出现的设备以“正常”作为之前的状态）
+        for device in current_status.peripherals() {
+            let Some(percentage) = device.percentage else { continue };
+            let was_low = previous_status
+                .peripherals()
+                .find(|d| d.id == device.id)
+                .and_then(|d| d.percentage)
+                .map(|p| p <= low_battery_threshold)
+                .unwrap_or(false);
+            let is_low = percentage <= low_battery_threshold;
 
             if !was_low && is_low {
-                events.push(PowerEvent::BatteryLow(current_status.battery_percentage));
+                events.push(PowerEvent::DeviceBatteryLow {
+                    device_id: device.id.clone(),
+                    name: device.display_name(),
+                    percentage,
+                });
             } else if was_low && !is_low {
-                events.push(PowerEvent::BatteryNormal(current_status.battery_percentage));
+                events.push(PowerEvent::DeviceBatteryNormal {
+                    device_id: device.id.clone(),
+                    name: device.display_name(),
+                    percentage,
+                });
             }
         }
 
@@ -135,15 +257,13 @@ impl PowerDetector {
     /// 检查是否需要显示提醒
     pub fn should_show_alert(&self, 
         status: &BatteryStatus, 
-        low_battery_threshold: u8
+        levels: &[BatteryLevel]
     ) -> (bool, String, String) {
-        // 优先检查低电量提醒（无论是否连接电源）
-        if status.is_battery_present && status.battery_percentage <= low_battery_threshold {
-            return (
-                true, 
-                "电池电量不足！请及时充电".to_string(),
-                "#FF0000".to_string() // 红色背景
-            );
+        // 优先检查低电量提醒（无论是否连接电源），使用当前所处级别的文字和颜色
+        if status.is_battery_present {
+            if let Some(level) = BatteryLevel::current(levels, status.battery_percentage) {
+                return (true, level.message.clone(), level.color.clone());
+            }
         }
 
         // 检查电源断开提醒
@@ -157,338 +277,6 @@ impl PowerDetector {
 
         (false, String::new(), String::new())
     }
-
-    /// 获取高级电池信息（功率、容量等）
-    #[cfg(target_os = "windows")]
-    fn get_advanced_battery_info(&self) -> (Option<f32>, Option<u32>, Option<u32>, Option<f32>) {
-        crate::log_info!("获取高级电池信息...");
-        
-        // 使用WMI获取详细的电池信息
-        match self.query_wmi_battery_info() {
-            Ok((power_draw, capacity, remaining_time, charge_rate)) => {
-                crate::log_info!("WMI查询成功 - 功耗: {:.1}W, 容量: {}mWh, 剩余: {}分钟", 
-                               power_draw, capacity, remaining_time);
-                (Some(power_draw), Some(capacity), Some(remaining_time), Some(charge_rate))
-            }
-            Err(e) => {
-                crate::log_error!("WMI查询失败: {}, 使用估算方法", e);
-                // 如果WMI查询失败，尝试使用简单的计算方法
-                let result = self.estimate_power_info();
-                if let (Some(power), Some(cap), Some(time), Some(rate)) = result {
-                    crate::log_info!("估算结果 - 功耗: {:.1}W, 容量: {}mWh, 剩余: {}分钟", 
-                                   power, cap, time);
-                }
-                result
-            }
-        }
-    }
-
-    /// 通过WMI查询电池信息
-    #[cfg(target_os = "windows")]
-    fn query_wmi_battery_info(&self) -> Result<(f32, u32, u32, f32), String> {
-        use std::process::{Command, Stdio};
-        use std::os::windows::process::CommandExt;
-        
-        crate::log_info!("开始WMI电池信息查询...");
-        
-        // 尝试多个WMI查询获取更准确的数据
-        let queries = [
-            // 查询1: 基础电池信息
-            "Get-WmiObject -Class Win32_Battery | Select-Object EstimatedChargeRemaining,DesignCapacity,EstimatedRunTime,DischargeRate | ConvertTo-Json",
-            // 查询2: 更详细的电池状态
-            "Get-WmiObject -Class Win32_PortableBattery | Select-Object DesignCapacity,MaxRechargeTime,EstimatedRunTime,Chemistry | ConvertTo-Json",
-            // 查询3: 系统电源设置
-            "powercfg /batteryreport /output temp_battery_report.xml 2>$null; if($?){Get-Content temp_battery_report.xml -Raw; Remove-Item temp_battery_report.xml -Force 2>$null}"
-        ];
-        
-        // 先尝试基础查询
-        let output = Command::new("powershell")
-            .args(&[
-                "-WindowStyle", "Hidden",  // 隐藏窗口
-                "-NoProfile",              // 不加载配置文件
-                "-NonInteractive",         // 非交互模式
-                "-ExecutionPolicy", "Bypass", // 绕过执行策略
-                "-Command",
-                queries[0]
-            ])
-            .stdin(Stdio::null())       // 不需要输入
-            .stdout(Stdio::piped())     // 捕获输出
-            .stderr(Stdio::piped())     // 捕获错误输出以便调试
-            .creation_flags(0x08000000) // CREATE_NO_WINDOW flag for Windows
-            .output()
-            .map_err(|e| {
-                crate::log_error!("PowerShell执行失败: {}", e);
-                format!("Failed to execute PowerShell: {}", e)
-            })?;
-
-        if !output.status.success() {
-            crate::log_error!("PowerShell命令执行失败，状态码: {:?}", output.status.code());
-            return Err("PowerShell command failed".to_string());
-        }
-
-        let json_str = String::from_utf8(output.stdout)
-            .map_err(|e| {
-                crate::log_error!("输出解析失败: {}", e);
-                format!("Failed to parse output: {}", e)
-            })?;
-        
-        crate::log_info!("WMI输出: {}", json_str.trim());
-
-        // 解析JSON输出
-        let result = self.parse_battery_json(&json_str);
-        
-        // 如果基础查询返回的功耗为默认值，尝试获取真实功耗
-        if let Ok((power, capacity, time, rate)) = &result {
-            if *power == 15.0 { // 如果是默认值，尝试获取真实数据
-                if let Ok(real_power) = self.get_real_power_consumption() {
-                    crate::log_info!("获取到真实功耗: {:.1}W", real_power);
-                    return Ok((real_power, *capacity, *time, *rate));
-                }
-            }
-        }
-        
-        result
-    }
-
-    /// 解析电池JSON数据
-    #[cfg(target_os = "windows")]
-    fn parse_battery_json(&self, json_str: &str) -> Result<(f32, u32, u32, f32), String> {
-        crate::log_info!("开始解析JSON数据...");
-        
-        // 简单的JSON解析（不依赖外部库）
-        let capacity = self.extract_json_value(json_str, "DesignCapacity")
-            .unwrap_or(50000.0) as u32; // 默认值50Wh
-        
-        let discharge_rate = self.extract_json_value(json_str, "DischargeRate")
-            .unwrap_or(15000.0); // 默认放电速率15000mW (15W)
-        
-        let estimated_runtime = self.extract_json_value(json_str, "EstimatedRunTime")
-            .map(|v| {
-                // 检查值是否合理，如果超过1440分钟（24小时），则使用默认值
-                if v > 1440.0 || v < 0.0 {
-                    240.0 // 默认剩余4小时
-                } else {
-                    v
-                }
-            })
-            .unwrap_or(240.0) as u32; // 默认剩余4小时
-
-        crate::log_info!("解析结果 - 容量: {}mWh, 放电率: {:.1}mW, 剩余时间: {}分钟", 
-                        capacity, discharge_rate, estimated_runtime);
-
-        // 计算当前功耗
-        let power_draw = if discharge_rate > 0.0 {
-            let watts = (discharge_rate / 1000.0) as f32; // 转换为瓦特
-            if watts < 0.1 { 15.0 } else { watts } // 如果太小，使用默认值
-        } else {
-            // 如果没有放电率数据，估算一个值
-            15.0 // 默认估算15W
-        };
-
-        crate::log_info!("计算功耗: {:.1}W", power_draw);
-        Ok((power_draw, capacity, estimated_runtime, (discharge_rate / 1000.0) as f32))
-    }
-
-    /// 从 JSON 字符串中提取数值
-    #[cfg(target_os = "windows")]
-    fn extract_json_value(&self, json_str: &str, key: &str) -> Option<f64> {
-        // 简单的JSON值提取，查找 "key":value 模式
-        if let Some(start) = json_str.find(&format!("\"{}\"", key)) {
-            if let Some(colon_pos) = json_str[start..].find(':') {
-                let after_colon = &json_str[start + colon_pos + 1..];
-                
-                // 先检查是否为null
-                let trimmed = after_colon.trim_start();
-                if trimmed.starts_with("null") {
-                    crate::log_info!("字段 {} 的值为 null", key);
-                    return None;
-                }
-                
-                // 查找数字
-                let mut num_str = String::new();
-                let mut found_digit = false;
-                
-                for ch in after_colon.chars() {
-                    if ch.is_whitespace() && !found_digit {
-                        continue; // 跳过前导空格
-                    }
-                    if ch.is_numeric() || ch == '.' || ch == '-' {
-                        num_str.push(ch);
-                        found_digit = true;
-                    } else if found_digit {
-                        break;
-                    }
-                }
-                
-                if found_digit {
-                    let result = num_str.parse().ok();
-                    crate::log_info!("字段 {} 的值: {}", key, num_str);
-                    result
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    }
-
-    /// 估算电源信息（备用方法）
-    #[cfg(target_os = "windows")]
-    fn estimate_power_info(&self) -> (Option<f32>, Option<u32>, Option<u32>, Option<f32>) {
-        // 获取当前电池状态
-        if let Ok(status) = self.get_basic_power_status() {
-            let estimated_power = if status.is_charging {
-                // 充电时估算功耗
-                match status.battery_percentage {
-                    0..=20 => 25.0,   // 低电量快充
-                    21..=80 => 20.0,  // 正常充电
-                    _ => 10.0,        // 慢充模式
-                }
-            } else {
-                // 放电时估算功耗（基于典型笔记本功耗）
-                15.0 // 典型笔记本在正常使用下的功耗
-            };
-
-            let estimated_capacity = 50000u32; // 50Wh 典型笔记本电池容量
-            
-            // 估算剩余时间
-            let remaining_time = if !status.is_charging && status.battery_percentage > 0 {
-                let remaining_capacity = (estimated_capacity as f32) * (status.battery_percentage as f32) / 100.0;
-                ((remaining_capacity / estimated_power) * 60.0) as u32 // 转换为分钟
-            } else {
-                0u32
-            };
-
-            (Some(estimated_power), Some(estimated_capacity), Some(remaining_time), Some(estimated_power))
-        } else {
-            (None, None, None, None)
-        }
-    }
-
-    /// 获取真实的系统功耗（通过性能计数器）
-    #[cfg(target_os = "windows")]
-    fn get_real_power_consumption(&self) -> Result<f32, String> {
-        use std::process::{Command, Stdio};
-        use std::os::windows::process::CommandExt;
-        
-        crate::log_info!("尝试获取真实系统功耗...");
-        
-        // 使用Windows性能计数器获取功耗信息
-        let commands = [
-            // 命令1: 获取电池放电率
-            "(Get-Counter '\\Battery(*)\\Battery Discharge Rate' -ErrorAction SilentlyContinue).CounterSamples.CookedValue",
-            // 命令2: 获取处理器功耗
-            "(Get-Counter '\\Processor(_Total)\\% Processor Time' -ErrorAction SilentlyContinue).CounterSamples.CookedValue",
-            // 命令3: 通过powercfg获取电池信息
-            "powercfg /energy /output temp_energy.html /duration 5 2>$null; if($?){Select-String -Path temp_energy.html -Pattern 'Battery.*[0-9]+.*W' | Select-Object -First 1; Remove-Item temp_energy.html -Force 2>$null}"
-        ];
-        
-        for (i, cmd) in commands.iter().enumerate() {
-            crate::log_info!("执行功耗检测命令 {}: {}", i+1, cmd);
-            
-            let output = Command::new("powershell")
-                .args(&[
-                    "-WindowStyle", "Hidden",
-                    "-NoProfile",
-                    "-NonInteractive",
-                    "-ExecutionPolicy", "Bypass",
-                    "-Command",
-                    cmd
-                ])
-                .stdin(Stdio::null())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::null())
-                .creation_flags(0x08000000)
-                .output()
-                .map_err(|e| format!("PowerShell执行失败: {}", e))?;
-            
-            if output.status.success() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                crate::log_info!("命令 {} 输出: {}", i+1, output_str.trim());
-                
-                // 尝试解析数值
-                if let Some(power) = self.extract_power_from_output(&output_str) {
-                    if power > 0.0 && power < 200.0 { // 合理范围内的功耗值
-                        crate::log_info!("从命令 {} 获取到功耗: {:.1}W", i+1, power);
-                        return Ok(power);
-                    }
-                }
-            }
-        }
-        
-        Err("无法获取真实功耗数据".to_string())
-    }
-    
-    /// 从命令输出中提取功耗数值
-    #[cfg(target_os = "windows")]
-    fn extract_power_from_output(&self, output: &str) -> Option<f32> {
-        // 查找数字模式
-        for line in output.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-            
-            // 尝试解析为数字
-            if let Ok(value) = line.parse::<f64>() {
-                if value > 0.0 && value < 200.0 {
-                    return Some(value as f32);
-                }
-            }
-            
-            // 查找包含"W"的行
-            if line.contains('W') || line.contains("watt") {
-                for word in line.split_whitespace() {
-                    let clean_word = word.trim_matches(|c: char| !c.is_numeric() && c != '.');
-                    if let Ok(value) = clean_word.parse::<f64>() {
-                        if value > 0.0 && value < 200.0 {
-                            return Some(value as f32);
-                        }
-                    }
-                }
-            }
-        }
-        None
-    }
-
-    /// 获取基础电源状态（不包含高级信息）
-    #[cfg(target_os = "windows")]
-    fn get_basic_power_status(&self) -> Result<BatteryStatus, String> {
-        use windows::Win32::System::Power::{
-            GetSystemPowerStatus, SYSTEM_POWER_STATUS
-        };
-
-        unsafe {
-            let mut status = SYSTEM_POWER_STATUS::default();
-            if GetSystemPowerStatus(&mut status).is_err() {
-                return Err("Failed to get system power status".to_string());
-            }
-            
-            let is_ac_connected = status.ACLineStatus == 1;
-            let is_charging = status.BatteryFlag & 8 != 0;
-            let is_battery_present = status.BatteryFlag != 128;
-            
-            let battery_percentage = if status.BatteryLifePercent == 255 {
-                100
-            } else {
-                status.BatteryLifePercent as u8
-            };
-
-            Ok(BatteryStatus {
-                is_charging,
-                is_ac_connected,
-                battery_percentage,
-                is_battery_present,
-                power_draw_watts: None,
-                battery_capacity_mwh: None,
-                remaining_time_minutes: None,
-                charge_rate_watts: None,
-            })
-        }
-    }
 }
 
 impl Default for PowerDetector {
@@ -521,6 +309,9 @@ mod tests {
             battery_capacity_mwh: None,
             remaining_time_minutes: None,
             charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
         };
 
         let current_status = BatteryStatus {
@@ -532,9 +323,12 @@ mod tests {
             battery_capacity_mwh: None,
             remaining_time_minutes: None,
             charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
         };
 
-        let events = detector.detect_power_events(&previous_status, &current_status, 20);
+        let events = detector.detect_power_events(&previous_status, &current_status, &[BatteryLevel::low(20)]);
         assert_eq!(events.len(), 1);
         assert!(matches!(events[0], PowerEvent::AcDisconnected));
     }
@@ -552,6 +346,9 @@ mod tests {
             battery_capacity_mwh: None,
             remaining_time_minutes: None,
             charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
         };
 
         let current_status = BatteryStatus {
@@ -563,11 +360,14 @@ mod tests {
             battery_capacity_mwh: None,
             remaining_time_minutes: None,
             charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
         };
 
-        let events = detector.detect_power_events(&previous_status, &current_status, 20);
+        let events = detector.detect_power_events(&previous_status, &current_status, &[BatteryLevel::low(20)]);
         assert_eq!(events.len(), 1);
-        assert!(matches!(events[0], PowerEvent::BatteryLow(15)));
+        assert!(matches!(events[0], PowerEvent::BatteryLow { percentage: 15, .. }));
     }
 
     #[test]
@@ -580,9 +380,16 @@ mod tests {
             is_ac_connected: true, // 即使连接电源也要提醒低电量
             battery_percentage: 15,
             is_battery_present: true,
+            power_draw_watts: None,
+            battery_capacity_mwh: None,
+            remaining_time_minutes: None,
+            charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
         };
 
-        let (should_alert, message, color) = detector.should_show_alert(&low_battery_status, 20);
+        let (should_alert, message, color) = detector.should_show_alert(&low_battery_status, &[BatteryLevel::low(20)]);
         assert!(should_alert);
         assert_eq!(message, "电池电量不足！请及时充电");
         assert_eq!(color, "#FF0000");
@@ -593,11 +400,148 @@ mod tests {
             is_ac_connected: false,
             battery_percentage: 50,
             is_battery_present: true,
+            power_draw_watts: None,
+            battery_capacity_mwh: None,
+            remaining_time_minutes: None,
+            charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
         };
 
-        let (should_alert, message, color) = detector.should_show_alert(&ac_disconnected_status, 20);
+        let (should_alert, message, color) = detector.should_show_alert(&ac_disconnected_status, &[BatteryLevel::low(20)]);
         assert!(should_alert);
         assert_eq!(message, "请连接电源适配器");
         assert_eq!(color, "#FF6B35");
     }
+
+    #[test]
+    fn test_multiple_battery_levels() {
+        let detector = PowerDetector::new();
+        let levels = vec![
+            BatteryLevel { name: "warning".to_string(), threshold: 30, color: "#FFA500".to_string(), ..Default::default() },
+            BatteryLevel { name: "low".to_string(), threshold: 15, ..Default::default() },
+            BatteryLevel { name: "critical".to_string(), threshold: 5, ..Default::default() },
+        ];
+        let at = |percentage| BatteryStatus {
+            is_battery_present: true,
+            battery_percentage: percentage,
+            ..Default::default()
+        };
+        let level_names = |events: Vec<PowerEvent>| -> Vec<String> {
+            events
+                .into_iter()
+                .map(|event| match event {
+                    PowerEvent::BatteryLow { level, .. } => level.name,
+                    other => other.to_string(),
+                })
+                .collect()
+        };
+
+        assert_eq!(level_names(detector.detect_power_events(&at(31), &at(29), &levels)), vec!["warning"]);
+        assert!(detector.detect_power_events(&at(29), &at(20), &levels).is_empty());
+        // 一次跳过多个级别时每个级别产生一个事件
+        assert_eq!(level_names(detector.detect_power_events(&at(20), &at(4), &levels)), vec!["low", "critical"]);
+        // 在级别之间回升不产生事件，回到最高级别以上才恢复正常
+        assert!(detector.detect_power_events(&at(4), &at(20), &levels).is_empty());
+        assert_eq!(detector.detect_power_events(&at(20), &at(35), &levels), vec![PowerEvent::BatteryNormal(35)]);
+
+        let (should_alert, _, color) = detector.should_show_alert(&at(25), &levels);
+        assert!(should_alert);
+        assert_eq!(color, "#FFA500");
+    }
+
+    #[test]
+    fn test_without_estimates() {
+        let status = BatteryStatus {
+            is_battery_present: true,
+            battery_percentage: 60,
+            power_draw_watts: Some(15.0),
+            battery_capacity_mwh: Some(57000),
+            remaining_time_minutes: Some(240),
+            provenance: StatusProvenance {
+                power_draw_watts: Provenance::Estimated,
+                battery_capacity_mwh: Provenance::Measured,
+                remaining_time_minutes: Provenance::Estimated,
+                charge_rate_watts: Provenance::Unknown,
+            },
+            ..Default::default()
+        };
+        assert!(status.provenance.has_estimates());
+
+        let filtered = status.without_estimates();
+        assert_eq!(filtered.power_draw_watts, None);
+        assert_eq!(filtered.battery_capacity_mwh, Some(57000));
+        assert_eq!(filtered.remaining_time_minutes, None);
+        assert!(!filtered.provenance.has_estimates());
+    }
+
+    #[test]
+    fn test_provenance_defaults_when_missing_from_json() {
+        let json = r#"{"is_charging":false,"is_ac_connected":true,"battery_percentage":80,"is_battery_present":true,
+            "power_draw_watts":null,"battery_capacity_mwh":null,"remaining_time_minutes":null,"charge_rate_watts":null}"#;
+        let status: BatteryStatus = serde_json::from_str(json).unwrap();
+        assert_eq!(status.provenance, StatusProvenance::default());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_predicted_remaining_time_fills_missing_value() {
+        use crate::power::MockPowerSource;
+
+        // 后端不提供剩余时间，每分钟下降 1%
+        let statuses: Vec<BatteryStatus> = (0..6).map(|i| MockPowerSource::status(false, 60 - i)).collect();
+        let detector = PowerDetector::with_source(Arc::new(MockPowerSource::with_sequence(statuses)));
+
+        let mut status = detector.get_power_status().unwrap();
+        assert_eq!(status.remaining_time_minutes, None);
+        for _ in 0..5 {
+            tokio::time::advance(std::time::Duration::from_secs(60)).await;
+            status = detector.get_power_status().unwrap();
+        }
+
+        let prediction = status.prediction.unwrap();
+        assert_eq!(prediction.direction, PredictionDirection::ToEmpty);
+        assert_eq!(status.remaining_time_minutes, Some(prediction.minutes));
+        assert!((prediction.minutes as i32 - 55).abs() <= 2, "{:?}", prediction);
+        assert_eq!(status.provenance.remaining_time_minutes, Provenance::Derived);
+    }
+
+    #[test]
+    fn test_peripheral_low_battery_detection() {
+        use crate::power::{DeviceKind, DeviceScope, DeviceState};
+
+        let detector = PowerDetector::new();
+        let mouse = |percentage: u8| PowerDevice {
+            id: "hidpp_battery_0".to_string(),
+            kind: DeviceKind::Mouse,
+            scope: DeviceScope::Device,
+            model: Some("MX Master 3".to_string()),
+            percentage: Some(percentage),
+            state: DeviceState::Discharging,
+            is_online: true,
+        };
+        let status_with = |device: PowerDevice| BatteryStatus {
+            is_ac_connected: true,
+            battery_percentage: 90,
+            is_battery_present: true,
+            devices: vec![device],
+            ..Default::default()
+        };
+
+        let events = detector.detect_power_events(&status_with(mouse(25)), &status_with(mouse(18)), &[BatteryLevel::low(20)]);
+        assert_eq!(events, vec![PowerEvent::DeviceBatteryLow {
+            device_id: "hidpp_battery_0".to_string(),
+            name: "MX Master 3".to_string(),
+            percentage: 18,
+        }]);
+
+        let events = detector.detect_power_events(&status_with(mouse(18)), &status_with(mouse(60)), &[BatteryLevel::low(20)]);
+        assert!(matches!(events[0], PowerEvent::DeviceBatteryNormal { percentage: 60, .. }));
+
+        // 系统电池不产生外设事件
+        let mut battery = mouse(10);
+        battery.scope = DeviceScope::System;
+        let events = detector.detect_power_events(&status_with(mouse(50)), &status_with(battery), &[BatteryLevel::low(20)]);
+        assert!(events.is_empty());
+    }
 }
\ No newline at end of file
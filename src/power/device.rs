@@ -0,0 +1,173 @@
+use crate::power::BatteryStatus;
+use serde::{Deserialize, Serialize};
+
+/// 电源设备类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceKind {
+    AcAdapter,
+    Battery,
+    Mouse,
+    Keyboard,
+    Headset,
+    Peripheral,  // 其他无线外设（手柄、触控笔等）
+    Unknown,
+}
+
+impl DeviceKind {
+    /// 根据型号/设备名推测外设类型
+    pub fn guess_peripheral(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("mouse") || name.contains("trackball") {
+            DeviceKind::Mouse
+        } else if name.contains("keyboard") || name.contains("keys") {
+            DeviceKind::Keyboard
+        } else if name.contains("headset") || name.contains("headphone") || name.contains("earbud") {
+            DeviceKind::Headset
+        } else {
+            DeviceKind::Peripheral
+        }
+    }
+}
+
+/// 设备归属：系统电源（笔记本电池、适配器）或外设
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceScope {
+    System,
+    Device,
+}
+
+/// 设备充放电状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceState {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging,
+    Unknown,
+}
+
+impl DeviceState {
+    /// 解析 sysfs/upower 风格的状态字符串
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "charging" => DeviceState::Charging,
+            "discharging" => DeviceState::Discharging,
+            "full" => DeviceState::Full,
+            "not charging" => DeviceState::NotCharging,
+            _ => DeviceState::Unknown,
+        }
+    }
+}
+
+/// 电源后端枚举到的单个设备
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerDevice {
+    pub id: String,
+    pub kind: DeviceKind,
+    pub scope: DeviceScope,
+    pub model: Option<String>,
+    pub percentage: Option<u8>,  // 电量百分比（适配器为 None）
+    pub state: DeviceState,
+    pub is_online: bool,         // 适配器是否接通 / 电池是否存在
+}
+
+impl PowerDevice {
+    /// 创建系统电源适配器
+    pub fn ac_adapter(id: &str, is_online: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            kind: DeviceKind::AcAdapter,
+            scope: DeviceScope::System,
+            model: None,
+            percentage: None,
+            state: DeviceState::Unknown,
+            is_online,
+        }
+    }
+
+    /// 根据汇总状态创建系统电池
+    pub fn system_battery(id: &str, status: &BatteryStatus) -> Self {
+        let state = if status.is_charging {
+            DeviceState::Charging
+        } else if !status.is_ac_connected {
+            DeviceState::Discharging
+        } else if status.battery_percentage >= 100 {
+            DeviceState::Full
+        } else {
+            DeviceState::NotCharging
+        };
+
+        Self {
+            id: id.to_string(),
+            kind: DeviceKind::Battery,
+            scope: DeviceScope::System,
+            model: None,
+            percentage: Some(status.battery_percentage),
+            state,
+            is_online: status.is_battery_present,
+        }
+    }
+
+    /// 只能提供汇总状态的后端使用：一个适配器 + 一块系统电池
+    pub fn from_status(status: &BatteryStatus, ac_id: &str, battery_id: &str) -> Vec<Self> {
+        let mut devices = vec![Self::ac_adapter(ac_id, status.is_ac_connected)];
+        if status.is_battery_present {
+            devices.push(Self::system_battery(battery_id, status));
+        }
+        devices
+    }
+
+    /// 是否为外设电池（鼠标、键盘、耳机等）
+    pub fn is_peripheral(&self) -> bool {
+        self.scope == DeviceScope::Device
+    }
+
+    /// 是否为系统电池
+    #[allow(dead_code)]
+    pub fn is_system_battery(&self) -> bool {
+        self.scope == DeviceScope::System && self.kind == DeviceKind::Battery
+    }
+
+    /// 用于提示的设备名称：优先使用型号
+    pub fn display_name(&self) -> String {
+        self.model.clone().unwrap_or_else(|| self.id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_peripheral_kind() {
+        assert_eq!(DeviceKind::guess_peripheral("MX Master 3 Mouse"), DeviceKind::Mouse);
+        assert_eq!(DeviceKind::guess_peripheral("K380 Multi-Device Keyboard"), DeviceKind::Keyboard);
+        assert_eq!(DeviceKind::guess_peripheral("WH-1000XM4 Headphones"), DeviceKind::Headset);
+        assert_eq!(DeviceKind::guess_peripheral("DualSense"), DeviceKind::Peripheral);
+    }
+
+    #[test]
+    fn test_parse_device_state() {
+        assert_eq!(DeviceState::parse("Charging"), DeviceState::Charging);
+        assert_eq!(DeviceState::parse("Not charging\n"), DeviceState::NotCharging);
+        assert_eq!(DeviceState::parse("Unknown"), DeviceState::Unknown);
+    }
+
+    #[test]
+    fn test_devices_from_status() {
+        let status = BatteryStatus {
+            is_charging: false,
+            is_ac_connected: false,
+            battery_percentage: 42,
+            is_battery_present: true,
+            ..Default::default()
+        };
+
+        let devices = PowerDevice::from_status(&status, "AC", "BAT");
+        assert_eq!(devices.len(), 2);
+        assert!(!devices[0].is_online);
+        assert!(devices[1].is_system_battery());
+        assert_eq!(devices[1].state, DeviceState::Discharging);
+        assert_eq!(devices[1].percentage, Some(42));
+    }
+}
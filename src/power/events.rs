@@ -0,0 +1,415 @@
+use crate::power::{BatteryStatus, PowerEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 耗电速率：优先使用实测功率，没有功率数据时使用电量下降速度
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DrainRate {
+    Watts(f32),
+    PercentPerMinute(f32),
+}
+
+impl fmt::Display for DrainRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrainRate::Watts(watts) => write!(f, "{:.1}W", watts),
+            DrainRate::PercentPerMinute(rate) => write!(f, "{:.1}%/分钟", rate),
+        }
+    }
+}
+
+/// 扩展电源事件的检测参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerEventConfig {
+    pub critical_threshold: u8,             // 电量严重不足阈值（%）
+    pub full_threshold: u8,                 // 视为充满的电量（开启充电上限时可设为 80 等）
+    pub rapid_drain_percent_per_min: f32,   // 电量下降速度超过该值视为耗电过快
+    pub rapid_drain_watts: Option<f32>,     // 实测功耗超过该值视为耗电过快（None 表示不按功耗判断）
+    pub rapid_drain_window_secs: u64,       // 计算电量下降速度的时间窗口
+    pub charging_stalled_secs: u64,         // 接通电源但未充电持续多久视为充电停滞
+    pub charging_stalled_below: u8,         // 电量低于该值才检测充电停滞（接近充满时停止补电、固件限制充电上限都属正常）
+    pub recovery_margin_percent: u8,        // 低电量恢复的回差：电量回升到阈值 + 该值以上才视为恢复
+    pub ac_min_dwell_secs: u64,             // AC 状态变化至少保持多久才报告（0 表示立即报告）
+}
+
+impl Default for PowerEventConfig {
+    fn default() -> Self {
+        Self {
+            critical_threshold: 5,
+            full_threshold: 100,
+            rapid_drain_percent_per_min: 1.0,
+            rapid_drain_watts: None,
+            rapid_drain_window_secs: 180,
+            charging_stalled_secs: 300,
+            charging_stalled_below: 95,
+            recovery_margin_percent: 5,
+            ac_min_dwell_secs: 0,
+        }
+    }
+}
+
+impl PowerEventConfig {
+    /// 验证参数
+    pub fn validate(&self) -> Result<(), String> {
+        if self.critical_threshold > 100 || self.full_threshold > 100 || self.charging_stalled_below > 100 {
+            return Err("电量阈值不能超过100%".to_string());
+        }
+        if self.full_threshold == 0 {
+            return Err("充满阈值必须大于0".to_string());
+        }
+        if self.rapid_drain_percent_per_min <= 0.0 {
+            return Err("耗电过快阈值必须大于0".to_string());
+        }
+        if matches!(self.rapid_drain_watts, Some(watts) if watts <= 0.0) {
+            return Err("耗电过快功耗阈值必须大于0".to_string());
+        }
+        if self.rapid_drain_window_secs == 0 {
+            return Err("耗电速率统计窗口不能为0".to_string());
+        }
+        if self.recovery_margin_percent > 50 {
+            return Err("低电量恢复回差不能超过50%".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 有状态的电源事件检测器
+///
+/// `PowerDetector::detect_power_events` 只比较前后两次状态；
+/// 耗电过快和充电停滞需要一段时间内的历史，由这里负责。
+#[derive(Debug, Clone)]
+pub struct PowerEventTracker {
+    config: PowerEventConfig,
+    drain_samples: VecDeque<(Instant, u8)>,
+    rapid_drain_reported: bool,
+    stalled_since: Option<Instant>,
+    stalled_reported: bool,
+}
+
+impl PowerEventTracker {
+    pub fn new(config: PowerEventConfig) -> Self {
+        Self {
+            config,
+            drain_samples: VecDeque::new(),
+            rapid_drain_reported: false,
+            stalled_since: None,
+            stalled_reported: false,
+        }
+    }
+
+    /// 当前使用的参数
+    #[allow(dead_code)]
+    pub fn config(&self) -> &PowerEventConfig {
+        &self.config
+    }
+
+    /// 根据新状态检测扩展事件，`previous` 为 None 表示首次检测（只记录历史）
+    pub fn detect(&mut self, at: Instant, previous: Option<&BatteryStatus>, current: &BatteryStatus) -> Vec<PowerEvent> {
+        let mut events = Vec::new();
+
+        if let Some(previous) = previous {
+            if previous.is_battery_present && !current.is_battery_present {
+                events.push(PowerEvent::BatteryRemoved);
+            } else if !previous.is_battery_present && current.is_battery_present {
+                events.push(PowerEvent::BatteryInserted);
+            }
+        }
+
+        if !current.is_battery_present {
+            self.reset();
+            return events;
+        }
+
+        if let Some(previous) = previous.filter(|p| p.is_battery_present) {
+            let critical = self.config.critical_threshold;
+            if previous.battery_percentage > critical && current.battery_percentage <= critical {
+                events.push(PowerEvent::CriticalBattery(current.battery_percentage));
+            }
+
+            let full = self.config.full_threshold;
+            let reached_full = previous.battery_percentage < full && current.battery_percentage >= full;
+            let charging_finished = previous.is_charging && !current.is_charging && current.battery_percentage >= full;
+            if current.is_ac_connected && (reached_full || charging_finished) {
+                events.push(PowerEvent::FullyCharged);
+            }
+        }
+
+        if let Some(event) = self.detect_rapid_drain(at, current) {
+            events.push(event);
+        }
+        if let Some(event) = self.detect_charging_stalled(at, current) {
+            events.push(event);
+        }
+
+        events
+    }
+
+    /// 清空历史
+    pub fn reset(&mut self) {
+        self.drain_samples.clear();
+        self.rapid_drain_reported = false;
+        self.stalled_since = None;
+        self.stalled_reported = false;
+    }
+
+    fn detect_rapid_drain(&mut self, at: Instant, status: &BatteryStatus) -> Option<PowerEvent> {
+        if status.is_ac_connected {
+            self.drain_samples.clear();
+            self.rapid_drain_reported = false;
+            return None;
+        }
+
+        let window = Duration::from_secs(self.config.rapid_drain_window_secs);
+        self.drain_samples.push_back((at, status.battery_percentage));
+        while let Some((first_at, _)) = self.drain_samples.front() {
+            if at.saturating_duration_since(*first_at) > window {
+                self.drain_samples.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        // 实测/计算得到的功耗优先，估算值不参与判断
+        let measured_watts = status
+            .power_draw_watts
+            .filter(|_| !status.provenance.power_draw_watts.is_estimated());
+        let rate = match (self.config.rapid_drain_watts, measured_watts) {
+            (Some(limit), Some(watts)) if watts >= limit => Some(DrainRate::Watts(watts)),
+            _ => self.percent_per_minute(window).map(DrainRate::PercentPerMinute),
+        };
+
+        match rate {
+            Some(rate) => {
+                if self.rapid_drain_reported {
+                    return None;
+                }
+                self.rapid_drain_reported = true;
+                Some(PowerEvent::RapidDrain { watts_or_pct_per_min: rate })
+            }
+            None => {
+                self.rapid_drain_reported = false;
+                None
+            }
+        }
+    }
+
+    /// 窗口填满后计算电量下降速度，超过阈值时返回
+    fn percent_per_minute(&self, window: Duration) -> Option<f32> {
+        let (first_at, first_level) = *self.drain_samples.front()?;
+        let (last_at, last_level) = *self.drain_samples.back()?;
+        let span = last_at.saturating_duration_since(first_at);
+        // 窗口未填满时样本太少，电量 1% 的跳变会被放大
+        if span < window.mul_f32(0.9) || span.is_zero() {
+            return None;
+        }
+
+        let rate = (first_level as f32 - last_level as f32) / (span.as_secs_f32() / 60.0);
+        if rate >= self.config.rapid_drain_percent_per_min {
+            Some(rate)
+        } else {
+            None
+        }
+    }
+
+    fn detect_charging_stalled(&mut self, at: Instant, status: &BatteryStatus) -> Option<PowerEvent> {
+        let stalled = status.is_ac_connected
+            && !status.is_charging
+            && status.battery_percentage < self.config.full_threshold.min(self.config.charging_stalled_below);
+
+        if !stalled {
+            self.stalled_since = None;
+            self.stalled_reported = false;
+            return None;
+        }
+
+        let since = *self.stalled_since.get_or_insert(at);
+        let limit = Duration::from_secs(self.config.charging_stalled_secs);
+        if !self.stalled_reported && at.saturating_duration_since(since) >= limit {
+            self.stalled_reported = true;
+            return Some(PowerEvent::ChargingStalled(status.battery_percentage));
+        }
+        None
+    }
+}
+
+impl Default for PowerEventTracker {
+    fn default() -> Self {
+        Self::new(PowerEventConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::status_with_charging;
+    use crate::power::{Provenance, StatusProvenance};
+
+    fn minutes(n: u64) -> Duration {
+        Duration::from_secs(n * 60)
+    }
+
+    #[test]
+    fn test_battery_removed_and_inserted() {
+        let mut tracker = PowerEventTracker::default();
+        let now = Instant::now();
+        let present = status_with_charging(true, false, 80);
+        let absent = BatteryStatus { is_battery_present: false, ..present.clone() };
+
+        assert_eq!(tracker.detect(now, Some(&present), &absent), vec![PowerEvent::BatteryRemoved]);
+        assert_eq!(tracker.detect(now, Some(&absent), &present), vec![PowerEvent::BatteryInserted]);
+    }
+
+    #[test]
+    fn test_critical_battery_crossing() {
+        let mut tracker = PowerEventTracker::new(PowerEventConfig { critical_threshold: 8, ..Default::default() });
+        let now = Instant::now();
+
+        let events = tracker.detect(now, Some(&status_with_charging(false, false, 9)), &status_with_charging(false, false, 8));
+        assert_eq!(events, vec![PowerEvent::CriticalBattery(8)]);
+
+        // 已经低于阈值时不重复触发
+        let events = tracker.detect(now, Some(&status_with_charging(false, false, 8)), &status_with_charging(false, false, 7));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_fully_charged() {
+        let mut tracker = PowerEventTracker::default();
+        let now = Instant::now();
+
+        let events = tracker.detect(now, Some(&status_with_charging(true, true, 99)), &status_with_charging(true, false, 100));
+        assert_eq!(events, vec![PowerEvent::FullyCharged]);
+
+        // 已充满时插入电源不触发
+        let events = tracker.detect(now, Some(&status_with_charging(false, false, 100)), &status_with_charging(true, false, 100));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_fully_charged_with_charge_limit() {
+        let mut tracker = PowerEventTracker::new(PowerEventConfig { full_threshold: 80, ..Default::default() });
+        let now = Instant::now();
+
+        // 达到充电上限后停止充电，既是“充满”也不应被视为充电停滞
+        let events = tracker.detect(now, Some(&status_with_charging(true, true, 80)), &status_with_charging(true, false, 80));
+        assert_eq!(events, vec![PowerEvent::FullyCharged]);
+        let events = tracker.detect(now + minutes(30), Some(&status_with_charging(true, false, 80)), &status_with_charging(true, false, 80));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_charging_stalled() {
+        let mut tracker = PowerEventTracker::new(PowerEventConfig { charging_stalled_secs: 120, ..Default::default() });
+        let start = Instant::now();
+        let stalled = status_with_charging(true, false, 60);
+
+        assert!(tracker.detect(start, None, &stalled).is_empty());
+        assert!(tracker.detect(start + minutes(1), Some(&stalled), &stalled).is_empty());
+        assert_eq!(
+            tracker.detect(start + minutes(2), Some(&stalled), &stalled),
+            vec![PowerEvent::ChargingStalled(60)]
+        );
+        // 只报告一次
+        assert!(tracker.detect(start + minutes(3), Some(&stalled), &stalled).is_empty());
+
+        // 恢复充电后重新计时
+        let charging = status_with_charging(true, true, 61);
+        assert!(tracker.detect(start + minutes(4), Some(&stalled), &charging).is_empty());
+        assert!(tracker.detect(start + minutes(5), Some(&charging), &stalled).is_empty());
+        assert_eq!(
+            tracker.detect(start + minutes(7), Some(&stalled), &stalled),
+            vec![PowerEvent::ChargingStalled(60)]
+        );
+    }
+
+    #[test]
+    fn test_no_charging_stalled_near_full() {
+        // 默认配置下，电池在 99% 停止补电（或固件限制了充电上限）不算充电停滞
+        let mut tracker = PowerEventTracker::default();
+        let start = Instant::now();
+        let topped_off = status_with_charging(true, false, 99);
+
+        assert!(tracker.detect(start, None, &topped_off).is_empty());
+        assert!(tracker.detect(start + minutes(10), Some(&topped_off), &topped_off).is_empty());
+
+        let stalled = status_with_charging(true, false, 94);
+        assert!(tracker.detect(start + minutes(11), Some(&topped_off), &stalled).is_empty());
+        assert_eq!(
+            tracker.detect(start + minutes(16), Some(&stalled), &stalled),
+            vec![PowerEvent::ChargingStalled(94)]
+        );
+    }
+
+    #[test]
+    fn test_rapid_drain_by_percentage() {
+        let mut tracker = PowerEventTracker::new(PowerEventConfig {
+            rapid_drain_percent_per_min: 1.0,
+            rapid_drain_window_secs: 120,
+            ..Default::default()
+        });
+        let start = Instant::now();
+
+        let mut previous = None;
+        let mut events = Vec::new();
+        // 每 30 秒下降 1%，即 2%/分钟
+        for i in 0..6u32 {
+            let current = status_with_charging(false, false, 80 - i as u8);
+            events.extend(tracker.detect(start + Duration::from_secs(30) * i, previous.as_ref(), &current));
+            previous = Some(current);
+        }
+
+        assert_eq!(events.len(), 1, "{:?}", events);
+        match events[0] {
+            PowerEvent::RapidDrain { watts_or_pct_per_min: DrainRate::PercentPerMinute(rate) } => {
+                assert!((rate - 2.0).abs() < 0.01);
+            }
+            ref other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_normal_drain_is_not_rapid() {
+        let mut tracker = PowerEventTracker::default();
+        let start = Instant::now();
+        let mut previous = None;
+        for i in 0..10u32 {
+            // 每分钟下降 0.5%
+            let current = status_with_charging(false, false, 80 - (i / 2) as u8);
+            assert!(tracker.detect(start + minutes(i as u64), previous.as_ref(), &current).is_empty());
+            previous = Some(current);
+        }
+    }
+
+    #[test]
+    fn test_rapid_drain_by_watts_ignores_estimates() {
+        let mut tracker = PowerEventTracker::new(PowerEventConfig {
+            rapid_drain_watts: Some(40.0),
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        let mut heavy = status_with_charging(false, false, 70);
+        heavy.power_draw_watts = Some(55.0);
+        heavy.provenance = StatusProvenance::all(Provenance::Estimated);
+        assert!(tracker.detect(now, Some(&heavy), &heavy).is_empty());
+
+        heavy.provenance = StatusProvenance::all(Provenance::Measured);
+        assert_eq!(
+            tracker.detect(now, Some(&heavy), &heavy),
+            vec![PowerEvent::RapidDrain { watts_or_pct_per_min: DrainRate::Watts(55.0) }]
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(PowerEventConfig::default().validate().is_ok());
+        assert!(PowerEventConfig { full_threshold: 101, ..Default::default() }.validate().is_err());
+        assert!(PowerEventConfig { charging_stalled_below: 101, ..Default::default() }.validate().is_err());
+        assert!(PowerEventConfig { rapid_drain_watts: Some(0.0), ..Default::default() }.validate().is_err());
+        assert!(PowerEventConfig { recovery_margin_percent: 60, ..Default::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// 电池健康信息
+///
+/// 这些数据变化很慢，读取代价也比电源状态高（Windows 下需要 WMI 查询），
+/// 因此不放在 `BatteryStatus` 中随每次轮询读取，而是按需通过 `PowerSource::battery_health` 获取。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatteryHealth {
+    pub design_capacity_mwh: Option<u32>,       // 设计容量（毫瓦时）
+    pub full_charge_capacity_mwh: Option<u32>,  // 当前满充容量（毫瓦时）
+    pub cycle_count: Option<u32>,               // 循环次数
+    pub wear_level_percent: Option<f32>,        // 损耗百分比（0 表示无损耗）
+    pub chemistry: Option<String>,              // 电池化学类型（Li-ion、LiPo 等）
+    pub manufacturer: Option<String>,
+    pub serial: Option<String>,
+}
+
+impl BatteryHealth {
+    /// 根据设计容量和满充容量计算损耗百分比
+    ///
+    /// 满充容量高于设计容量（新电池常见）时视为无损耗。
+    pub fn wear_level(design_capacity_mwh: Option<u32>, full_charge_capacity_mwh: Option<u32>) -> Option<f32> {
+        let design = design_capacity_mwh.filter(|d| *d > 0)? as f32;
+        let full = full_charge_capacity_mwh? as f32;
+        let wear = (1.0 - full / design) * 100.0;
+        Some((wear.max(0.0) * 10.0).round() / 10.0)
+    }
+
+    /// 根据容量字段重新计算 `wear_level_percent`
+    pub fn with_wear_level(mut self) -> Self {
+        self.wear_level_percent = Self::wear_level(self.design_capacity_mwh, self.full_charge_capacity_mwh);
+        self
+    }
+
+    /// 当前满充容量占设计容量的百分比
+    #[allow(dead_code)]
+    pub fn health_percent(&self) -> Option<f32> {
+        self.wear_level_percent.map(|wear| 100.0 - wear)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wear_level() {
+        assert_eq!(BatteryHealth::wear_level(Some(57000), Some(45600)), Some(20.0));
+        assert_eq!(BatteryHealth::wear_level(Some(50000), Some(50000)), Some(0.0));
+        // 满充容量高于设计容量时不报告负损耗
+        assert_eq!(BatteryHealth::wear_level(Some(50000), Some(51000)), Some(0.0));
+        assert_eq!(BatteryHealth::wear_level(None, Some(50000)), None);
+        assert_eq!(BatteryHealth::wear_level(Some(0), Some(50000)), None);
+    }
+
+    #[test]
+    fn test_with_wear_level() {
+        let health = BatteryHealth {
+            design_capacity_mwh: Some(60000),
+            full_charge_capacity_mwh: Some(54000),
+            cycle_count: Some(312),
+            ..Default::default()
+        }
+        .with_wear_level();
+
+        assert_eq!(health.wear_level_percent, Some(10.0));
+        assert_eq!(health.health_percent(), Some(90.0));
+    }
+}
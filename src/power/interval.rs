@@ -0,0 +1,166 @@
+use crate::power::{BatteryLevel, BatteryStatus, PredictionDirection};
+use std::time::Duration;
+
+/// 电量距离下一个阈值不超过该值时使用最短间隔
+const NEAR_THRESHOLD_PERCENT: u8 = 2;
+/// 电量距离下一个阈值不超过该值时间隔减半
+const APPROACHING_THRESHOLD_PERCENT: u8 = 5;
+/// 使用电池供电时，高于该电量放慢检测
+const HIGH_CHARGE_PERCENT: u8 = 80;
+
+/// 监控循环的检测间隔
+///
+/// 固定模式下始终使用 `base`；自适应模式下根据最近一次检测到的状态在 `[min, max]` 内选择：
+/// 接通电源时放慢到 `max`，电量接近下一个提醒阈值或耗电较快时加快。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollingInterval {
+    pub base: Duration,  // 配置的检测间隔（check_interval）
+    pub min: Duration,   // 自适应间隔下限
+    pub max: Duration,   // 自适应间隔上限
+    pub adaptive: bool,
+}
+
+impl PollingInterval {
+    /// 固定间隔
+    #[allow(dead_code)]
+    pub fn fixed(base: Duration) -> Self {
+        Self {
+            base,
+            min: base,
+            max: base,
+            adaptive: false,
+        }
+    }
+
+    /// 根据最近一次检测到的状态选择下一次检测前的等待时间
+    ///
+    /// `critical_threshold` 与电量级别一样作为需要及时发现的阈值。
+    /// `base` 超出 `[min, max]` 时范围扩大到包含 `base`，保证旧配置中较长或较短的
+    /// 检测间隔仍然生效。
+    pub fn next_interval(
+        &self,
+        status: Option<&BatteryStatus>,
+        levels: &[BatteryLevel],
+        critical_threshold: u8,
+    ) -> Duration {
+        if !self.adaptive {
+            return self.base;
+        }
+
+        let min = self.min.min(self.base);
+        let max = self.max.max(self.base);
+
+        let status = match status {
+            Some(status) if status.is_battery_present => status,
+            _ => return self.base,
+        };
+
+        // 接通电源时电量不会越过提醒阈值，只需要发现拔掉电源（推送可用时更不依赖轮询）
+        if status.is_ac_connected {
+            return max;
+        }
+
+        let percentage = status.battery_percentage;
+        // 低于当前电量的最高阈值；0% 作为最后一个阈值，低于所有级别时继续加快
+        let next_threshold = levels
+            .iter()
+            .map(|level| level.threshold)
+            .chain([critical_threshold, 0])
+            .filter(|threshold| *threshold < percentage)
+            .max();
+
+        let mut interval = match next_threshold.map(|threshold| percentage - threshold) {
+            Some(distance) if distance <= NEAR_THRESHOLD_PERCENT => min,
+            Some(distance) if distance <= APPROACHING_THRESHOLD_PERCENT => self.base / 2,
+            _ if percentage >= HIGH_CHARGE_PERCENT => self.base * 2,
+            _ => self.base,
+        };
+
+        // 耗电较快时保证每下降 1% 至少检测一次
+        if let Some(prediction) = status.prediction {
+            let percent_per_minute = -prediction.rate_percent_per_hour / 60.0;
+            if prediction.direction == PredictionDirection::ToEmpty && percent_per_minute > 0.0 {
+                interval = interval.min(Duration::from_secs_f32(60.0 / percent_per_minute));
+            }
+        }
+
+        interval.clamp(min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::RemainingTimePrediction;
+    use crate::power::test_support::status;
+
+    fn adaptive() -> PollingInterval {
+        PollingInterval {
+            base: Duration::from_secs(10),
+            min: Duration::from_secs(2),
+            max: Duration::from_secs(60),
+            adaptive: true,
+        }
+    }
+
+    fn secs(interval: &PollingInterval, status: &BatteryStatus) -> u64 {
+        interval.next_interval(Some(status), &[BatteryLevel::low(20)], 5).as_secs()
+    }
+
+    #[test]
+    fn test_fixed_interval() {
+        let interval = PollingInterval::fixed(Duration::from_secs(10));
+        assert_eq!(secs(&interval, &status(true, 95)), 10);
+        assert_eq!(secs(&interval, &status(false, 21)), 10);
+    }
+
+    #[test]
+    fn test_adaptive_interval_follows_state() {
+        let interval = adaptive();
+        assert_eq!(secs(&interval, &status(true, 95)), 60);
+        assert_eq!(secs(&interval, &status(true, 10)), 60);
+        assert_eq!(secs(&interval, &status(false, 95)), 20);
+        assert_eq!(secs(&interval, &status(false, 50)), 10);
+        // 接近 20% 阈值
+        assert_eq!(secs(&interval, &status(false, 25)), 5);
+        assert_eq!(secs(&interval, &status(false, 22)), 2);
+        // 已低于 20%，下一个阈值是严重低电量 5%
+        assert_eq!(secs(&interval, &status(false, 15)), 10);
+        assert_eq!(secs(&interval, &status(false, 6)), 2);
+        // 低于所有阈值时以 0% 为目标
+        assert_eq!(secs(&interval, &status(false, 2)), 2);
+
+        // 没有电池或尚未检测时使用配置的间隔
+        assert_eq!(interval.next_interval(None, &[], 5), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_adaptive_interval_rapid_drain() {
+        let mut draining = status(false, 60);
+        draining.prediction = Some(RemainingTimePrediction {
+            direction: PredictionDirection::ToEmpty,
+            minutes: 30,
+            lower_minutes: 25,
+            upper_minutes: 35,
+            rate_percent_per_hour: -120.0,
+            sample_count: 10,
+        });
+        // 每分钟下降 2%，每 30 秒下降 1%，但仍不低于最短间隔
+        let interval = PollingInterval {
+            base: Duration::from_secs(60),
+            ..adaptive()
+        };
+        assert_eq!(secs(&interval, &draining), 30);
+        assert_eq!(secs(&adaptive(), &draining), 10);
+    }
+
+    #[test]
+    fn test_base_outside_bounds() {
+        let interval = PollingInterval {
+            base: Duration::from_secs(300),
+            ..adaptive()
+        };
+        assert_eq!(secs(&interval, &status(false, 50)), 300);
+        assert_eq!(secs(&interval, &status(true, 50)), 300);
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 到达电量级别时播放的声音
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelSound {
+    None,
+    #[default]
+    System,        // 系统警告音
+    File(String),  // 自定义音频文件，文件不存在时回退到系统警告音
+}
+
+/// 到达电量级别时的提醒方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelAction {
+    Notify,   // 只显示托盘通知
+    #[default]
+    Alert,    // 弹出置顶提醒窗口并显示托盘通知
+}
+
+/// 一个电量提醒级别
+///
+/// 电量从高于 `threshold` 降到 `threshold` 及以下时触发一次。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatteryLevel {
+    pub name: String,       // 级别名称（如 "warning"、"low"、"critical"）
+    pub threshold: u8,      // 电量阈值（%）
+    pub color: String,      // 提醒窗口背景色
+    pub message: String,    // 提醒文字
+    pub sound: LevelSound,
+    pub action: LevelAction,
+}
+
+impl Default for BatteryLevel {
+    fn default() -> Self {
+        Self::low(20)
+    }
+}
+
+impl BatteryLevel {
+    /// 与旧版单一 `low_battery_threshold` 等价的级别
+    pub fn low(threshold: u8) -> Self {
+        Self {
+            name: "low".to_string(),
+            threshold,
+            color: "#FF0000".to_string(),
+            message: "电池电量不足！请及时充电".to_string(),
+            sound: LevelSound::System,
+            action: LevelAction::Alert,
+        }
+    }
+
+    /// 验证级别列表：阈值必须从高到低排列且不能重复，名称不能重复
+    pub fn validate_levels(levels: &[BatteryLevel]) -> Result<(), String> {
+        let mut names = HashSet::new();
+        for level in levels {
+            if level.threshold > 100 {
+                return Err(format!("电量级别 {} 的阈值不能超过100%", level.name));
+            }
+            if level.name.trim().is_empty() {
+                return Err("电量级别名称不能为空".to_string());
+            }
+            if !names.insert(level.name.as_str()) {
+                return Err(format!("电量级别名称重复: {}", level.name));
+            }
+        }
+
+        for pair in levels.windows(2) {
+            if pair[0].threshold == pair[1].threshold {
+                return Err(format!("电量级别阈值重复: {}%", pair[0].threshold));
+            }
+            if pair[0].threshold < pair[1].threshold {
+                return Err("电量级别必须按阈值从高到低排列".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 电量从 `previous` 变为 `current` 时向下越过的级别（从高到低）
+    pub fn crossed_down(levels: &[BatteryLevel], previous: u8, current: u8) -> impl Iterator<Item = &BatteryLevel> {
+        levels
+            .iter()
+            .filter(move |level| previous > level.threshold && current <= level.threshold)
+    }
+
+    /// 当前电量所处的最低级别，高于所有级别时返回 `None`
+    pub fn current(levels: &[BatteryLevel], percentage: u8) -> Option<&BatteryLevel> {
+        levels.iter().rev().find(|level| percentage <= level.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(name: &str, threshold: u8) -> BatteryLevel {
+        BatteryLevel {
+            name: name.to_string(),
+            threshold,
+            ..Default::default()
+        }
+    }
+
+    fn three_levels() -> Vec<BatteryLevel> {
+        vec![level("warning", 30), level("low", 15), level("critical", 5)]
+    }
+
+    #[test]
+    fn test_validate_levels() {
+        assert!(BatteryLevel::validate_levels(&three_levels()).is_ok());
+        assert!(BatteryLevel::validate_levels(&[]).is_ok());
+
+        let unsorted = vec![level("low", 15), level("warning", 30)];
+        assert!(BatteryLevel::validate_levels(&unsorted).is_err());
+
+        let duplicate = vec![level("warning", 30), level("low", 30)];
+        assert!(BatteryLevel::validate_levels(&duplicate).is_err());
+
+        let same_name = vec![level("low", 30), level("low", 15)];
+        assert!(BatteryLevel::validate_levels(&same_name).is_err());
+
+        assert!(BatteryLevel::validate_levels(&[level("low", 101)]).is_err());
+    }
+
+    #[test]
+    fn test_crossed_down() {
+        let levels = three_levels();
+        let names = |previous, current| -> Vec<String> {
+            BatteryLevel::crossed_down(&levels, previous, current)
+                .map(|level| level.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names(31, 30), vec!["warning"]);
+        assert_eq!(names(30, 29), Vec::<String>::new());
+        // 一次跳过多个级别时每个级别都会触发
+        assert_eq!(names(40, 10), vec!["warning", "low"]);
+        assert_eq!(names(10, 40), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_current_level() {
+        let levels = three_levels();
+        assert_eq!(BatteryLevel::current(&levels, 50), None);
+        assert_eq!(BatteryLevel::current(&levels, 30).unwrap().name, "warning");
+        assert_eq!(BatteryLevel::current(&levels, 12).unwrap().name, "low");
+        assert_eq!(BatteryLevel::current(&levels, 0).unwrap().name, "critical");
+    }
+
+    #[test]
+    fn test_deserialize_level() {
+        let level: BatteryLevel = toml::from_str(
+            r##"
+            name = "warning"
+            threshold = 30
+            color = "#FFA500"
+            sound = "none"
+            action = "notify"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(level.threshold, 30);
+        assert_eq!(level.sound, LevelSound::None);
+        assert_eq!(level.action, LevelAction::Notify);
+        // 未填写的字段使用默认值
+        assert_eq!(level.message, "电池电量不足！请及时充电");
+
+        let level: BatteryLevel = toml::from_str("name = \"low\"\nsound = { file = \"alarm.wav\" }\n").unwrap();
+        assert_eq!(level.sound, LevelSound::File("alarm.wav".to_string()));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::power::{
+    change_channel, BatteryStatus, ChangeReceiver, ChangeSender, PowerChange, PowerDevice, PowerSource,
+    SourceCapabilities,
+};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// 测试用电源后端
+///
+/// 按顺序返回预设的状态序列，序列耗尽后一直返回最后一个状态；
+/// 也可以随时通过 `set_status` 替换当前状态，通过 `notify_change` 模拟系统推送。
+pub struct MockPowerSource {
+    pending: Mutex<VecDeque<BatteryStatus>>,
+    current: Mutex<Result<BatteryStatus, String>>,
+    polls: AtomicUsize,
+    subscribers: Mutex<Vec<ChangeSender>>,
+}
+
+impl MockPowerSource {
+    pub fn new(status: BatteryStatus) -> Self {
+        Self::with_sequence(vec![status])
+    }
+
+    /// 使用状态序列创建，每次查询消耗一个状态
+    pub fn with_sequence(statuses: Vec<BatteryStatus>) -> Self {
+        let mut pending: VecDeque<BatteryStatus> = statuses.into();
+        let first = pending.pop_front().ok_or_else(|| "empty mock sequence".to_string());
+        Self {
+            pending: Mutex::new(pending),
+            current: Mutex::new(first),
+            polls: AtomicUsize::new(0),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 立即替换当前状态（并清空剩余序列）
+    pub fn set_status(&self, status: BatteryStatus) {
+        self.pending.lock().unwrap().clear();
+        *self.current.lock().unwrap() = Ok(status);
+    }
+
+    /// 让后续查询返回错误
+    pub fn set_error(&self, error: &str) {
+        self.pending.lock().unwrap().clear();
+        *self.current.lock().unwrap() = Err(error.to_string());
+    }
+
+    /// 向所有订阅者推送一次电源变化
+    pub fn notify_change(&self, change: PowerChange) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|sender| sender.send(change.clone()).is_ok());
+    }
+
+    /// 已被查询的次数
+    pub fn poll_count(&self) -> usize {
+        self.polls.load(Ordering::SeqCst)
+    }
+
+    /// 构造一个常用的电池状态
+    pub fn status(is_ac_connected: bool, battery_percentage: u8) -> BatteryStatus {
+        crate::power::test_support::status(is_ac_connected, battery_percentage)
+    }
+}
+
+impl PowerSource for MockPowerSource {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn get_power_status(&self) -> Result<BatteryStatus, String> {
+        self.polls.fetch_add(1, Ordering::SeqCst);
+
+        let mut current = self.current.lock().unwrap();
+        let result = current.clone();
+        if let Some(next) = self.pending.lock().unwrap().pop_front() {
+            *current = Ok(next);
+        }
+        result
+    }
+
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        let status = self.current.lock().unwrap().clone()?;
+        if status.devices.is_empty() {
+            Ok(PowerDevice::from_status(&status, "AC", "BAT0"))
+        } else {
+            Ok(status.devices)
+        }
+    }
+
+    fn subscribe_changes(&self) -> Option<ChangeReceiver> {
+        let (tx, rx) = change_channel();
+        self.subscribers.lock().unwrap().push(tx);
+        Some(rx)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            push_notifications: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_then_sticky_last() {
+        let source = MockPowerSource::with_sequence(vec![
+            MockPowerSource::status(true, 80),
+            MockPowerSource::status(false, 79),
+        ]);
+
+        assert!(source.get_power_status().unwrap().is_ac_connected);
+        assert!(!source.get_power_status().unwrap().is_ac_connected);
+        assert_eq!(source.get_power_status().unwrap().battery_percentage, 79);
+        assert_eq!(source.poll_count(), 3);
+    }
+
+    #[test]
+    fn test_error_injection() {
+        let source = MockPowerSource::new(MockPowerSource::status(true, 80));
+        source.set_error("unavailable");
+        assert!(source.get_power_status().is_err());
+
+        source.set_status(MockPowerSource::status(false, 10));
+        assert_eq!(source.get_power_status().unwrap().battery_percentage, 10);
+    }
+}
@@ -1,5 +1,42 @@
+pub mod bus;
+pub mod debounce;
 pub mod detector;
+pub mod device;
+pub mod events;
+pub mod health;
+pub mod interval;
+pub mod levels;
+#[cfg(test)]
+pub mod mock;
 pub mod monitor;
+pub mod notify;
+pub mod predictor;
+pub mod provenance;
+pub mod simulated;
+pub mod source;
+pub mod sysfs;
+#[cfg(test)]
+pub mod test_support;
+#[cfg(target_os = "windows")]
+pub mod win32;
+pub mod wmi;
 
+pub use bus::*;
+pub use debounce::*;
 pub use detector::*;
-pub use monitor::*;
\ No newline at end of file
+pub use device::*;
+pub use events::*;
+pub use health::*;
+pub use interval::*;
+pub use levels::*;
+#[cfg(test)]
+pub use mock::*;
+pub use monitor::*;
+pub use notify::*;
+pub use predictor::*;
+pub use provenance::*;
+pub use simulated::*;
+pub use source::*;
+pub use sysfs::*;
+#[cfg(target_os = "windows")]
+pub use win32::*;
\ No newline at end of file
@@ -1,15 +1,23 @@
-use crate::power::{PowerDetector, BatteryStatus, PowerEvent};
+use crate::config::MonitoringConfig;
+use crate::power::{
+    PowerDetector, PowerSource, BatteryLevel, BatteryStatus, ChangeReceiver, EventBus, EventDebouncer, EventSubscriber,
+    PowerChange, PowerEvent, PollingInterval, PowerEventConfig, PowerEventTracker,
+};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
 use tokio::time;
 
+/// 收到推送后等待一小段时间再检测：系统发出通知时 sysfs/WMI 中的数值可能还没更新，
+/// 同时合并短时间内连续到达的多个通知
+const CHANGE_SETTLE_DELAY: Duration = Duration::from_millis(300);
+
 pub struct PowerMonitor {
     detector: PowerDetector,
-    check_interval: Duration,
-    low_battery_threshold: u8,
-    is_monitoring: Arc<Mutex<bool>>,
-    last_status: Arc<Mutex<Option<BatteryStatus>>>,
+    settings: Mutex<MonitorSettings>,
+    event_bus: EventBus,
+    handle: Mutex<Option<MonitorHandle>>,  // 当前运行的监控循环
 }
 
 #[derive(Debug, Clone)]
@@ -18,159 +26,299 @@ pub struct MonitorEvent {
     pub current_status: BatteryStatus,
 }
 
+/// 监控循环使用的参数，运行中可以整体替换
+#[derive(Debug, Clone, PartialEq)]
+struct MonitorSettings {
+    battery_levels: Vec<BatteryLevel>,  // 按阈值从高到低排列
+    event_config: PowerEventConfig,
+    polling: PollingInterval,
+}
+
+impl MonitorSettings {
+    fn from_config(config: &MonitoringConfig) -> Self {
+        Self {
+            battery_levels: config.effective_battery_levels(),
+            event_config: config.events.clone(),
+            polling: PollingInterval {
+                base: Duration::from_secs(config.check_interval),
+                min: Duration::from_secs(config.min_check_interval),
+                max: Duration::from_secs(config.max_check_interval),
+                adaptive: config.adaptive_interval,
+            },
+        }
+    }
+}
+
+/// 监控循环的运行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorState {
+    Running,
+    Paused,   // 循环仍在运行，但不检测也不发送事件
+    Stopped,
+}
+
+/// 监控循环的状态快照
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct MonitorStatus {
+    pub state: MonitorState,
+    pub checks: u64,                         // 已完成的检测次数
+    pub polling: PollingInterval,            // 当前的检测间隔设置
+    pub last_status: Option<BatteryStatus>,  // 最近一次检测到的电源状态
+}
+
+/// 发送给监控循环的命令
+#[derive(Debug)]
+enum MonitorCommand {
+    Pause,
+    Resume,
+    Update(MonitorSettings),
+}
+
+/// 停止监控循环的取消令牌，所有副本共享同一个状态
+#[derive(Debug, Clone)]
+struct CancellationToken {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl CancellationToken {
+    fn new() -> Self {
+        Self {
+            cancelled: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    async fn cancelled(&self) {
+        let mut receiver = self.cancelled.subscribe();
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// 正在运行的监控循环的控制句柄
+///
+/// 句柄可以克隆，所有副本控制同一个监控循环。停止通过取消令牌立即生效，
+/// 暂停、恢复和更新配置通过命令通道按顺序交给监控循环处理。
+/// 所有句柄都被丢弃后，监控循环自动结束。
+#[derive(Debug, Clone)]
+pub struct MonitorHandle {
+    commands: mpsc::UnboundedSender<MonitorCommand>,
+    cancel: CancellationToken,
+    status: Arc<Mutex<MonitorStatus>>,
+    task: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl MonitorHandle {
+    /// 停止监控循环并等待其结束
+    pub async fn stop(&self) {
+        self.cancel.cancel();
+        let task = self.task.lock().unwrap().take();
+        if let Some(task) = task {
+            let _ = task.await;
+        }
+    }
+
+    /// 发出停止信号，不等待监控循环结束
+    #[allow(dead_code)]
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    /// 暂停检测（不结束监控循环，恢复后继续使用之前的状态）
+    pub fn pause(&self) {
+        self.set_state(MonitorState::Paused);
+        let _ = self.commands.send(MonitorCommand::Pause);
+    }
+
+    /// 恢复检测，恢复后立即检测一次
+    pub fn resume(&self) {
+        self.set_state(MonitorState::Running);
+        let _ = self.commands.send(MonitorCommand::Resume);
+    }
+
+    /// 使用新的监控配置（电量级别、事件参数、检测间隔），无需重启监控循环
+    pub fn update_config(&self, config: MonitoringConfig) {
+        self.update(MonitorSettings::from_config(&config));
+    }
+
+    /// 监控循环的当前状态
+    pub fn status(&self) -> MonitorStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// 监控循环是否仍在运行（包括暂停中）
+    pub fn is_running(&self) -> bool {
+        !self.cancel.is_cancelled() && self.status().state != MonitorState::Stopped
+    }
+
+    fn update(&self, settings: MonitorSettings) {
+        let _ = self.commands.send(MonitorCommand::Update(settings));
+    }
+
+    /// 立即反映在状态快照中；监控循环已结束时保持 `Stopped`
+    fn set_state(&self, state: MonitorState) {
+        let mut status = self.status.lock().unwrap();
+        if status.state != MonitorState::Stopped {
+            status.state = state;
+        }
+    }
+}
+
 impl PowerMonitor {
+    /// 使用当前平台的默认电源后端创建监控器
+    #[allow(dead_code)]
     pub fn new(check_interval_secs: u64, low_battery_threshold: u8) -> Self {
+        Self::with_detector(PowerDetector::new(), check_interval_secs, low_battery_threshold)
+    }
+
+    /// 使用指定的电源后端创建监控器
+    #[allow(dead_code)]
+    pub fn with_source(source: Box<dyn PowerSource>, check_interval_secs: u64, low_battery_threshold: u8) -> Self {
+        Self::with_detector(PowerDetector::with_source(source.into()), check_interval_secs, low_battery_threshold)
+    }
+
+    /// 使用已有的检测器创建监控器（与其他组件共享同一个后端）
+    #[allow(dead_code)]
+    pub fn with_detector(detector: PowerDetector, check_interval_secs: u64, low_battery_threshold: u8) -> Self {
         Self {
-            detector: PowerDetector::new(),
-            check_interval: Duration::from_secs(check_interval_secs),
-            low_battery_threshold,
-            is_monitoring: Arc::new(Mutex::new(false)),
-            last_status: Arc::new(Mutex::new(None)),
+            detector,
+            settings: Mutex::new(MonitorSettings {
+                battery_levels: vec![BatteryLevel::low(low_battery_threshold)],
+                event_config: PowerEventConfig::default(),
+                polling: PollingInterval::fixed(Duration::from_secs(check_interval_secs)),
+            }),
+            event_bus: EventBus::default(),
+            handle: Mutex::new(None),
         }
     }
 
-    /// 开始监控电源状态
-    pub async fn start_monitoring(&self) -> mpsc::Receiver<MonitorEvent> {
-        let (tx, rx) = mpsc::channel(100);
-        
-        {
-            let mut monitoring = self.is_monitoring.lock().unwrap();
-            *monitoring = true;
-        }
-
-        let detector = self.detector.clone();
-        let check_interval = self.check_interval;
-        let low_battery_threshold = self.low_battery_threshold;
-        let is_monitoring = Arc::clone(&self.is_monitoring);
-        let last_status = Arc::clone(&self.last_status);
-
-        tokio::spawn(async move {
-            let mut interval = time::interval(check_interval);
-            
-            loop {
-                interval.tick().await;
-                
-                // 检查是否应该继续监控
-                {
-                    let monitoring = is_monitoring.lock().unwrap();
-                    if !*monitoring {
-                        break;
-                    }
-                }
+    /// 按监控配置创建监控器
+    pub fn from_config(detector: PowerDetector, config: &MonitoringConfig) -> Self {
+        Self {
+            detector,
+            settings: Mutex::new(MonitorSettings::from_config(config)),
+            event_bus: EventBus::default(),
+            handle: Mutex::new(None),
+        }
+    }
 
-                // 获取当前电源状态
-                match detector.get_power_status() {
-                    Ok(current_status) => {
-                        let previous_status = {
-                            let mut last_status_guard = last_status.lock().unwrap();
-                            let prev = last_status_guard.clone();
-                            *last_status_guard = Some(current_status.clone());
-                            prev
-                        };
-                        
-                        if let Some(previous_status) = previous_status {
-                            // 检测状态变化
-                            let events = detector.detect_power_events(
-                                &previous_status,
-                                &current_status,
-                                low_battery_threshold
-                            );
-
-                            // 发送事件
-                            for event in events {
-                                let monitor_event = MonitorEvent {
-                                    power_event: event,
-                                    current_status: current_status.clone(),
-                                };
-
-                                if let Err(_) = tx.send(monitor_event).await {
-                                    // 接收器已关闭，停止监控
-                                    let mut monitoring = is_monitoring.lock().unwrap();
-                                    *monitoring = false;
-                                    break;
-                                }
-                            }
-                            
-                            // 如果电量或功耗发生变化（即使没有触发事件），也发送一个状态更新事件
-                            // 这确保提醒窗口和托盘菜单中的信息始终保持最新
-                            let power_changed = previous_status.power_draw_watts != current_status.power_draw_watts;
-                            let percentage_changed = previous_status.battery_percentage != current_status.battery_percentage;
-                            
-                            if percentage_changed || power_changed {
-                                let status_update_event = MonitorEvent {
-                                    power_event: crate::power::PowerEvent::StatusUpdate,
-                                    current_status: current_status.clone(),
-                                };
-                                
-                                if let Err(_) = tx.send(status_update_event).await {
-                                    let mut monitoring = is_monitoring.lock().unwrap();
-                                    *monitoring = false;
-                                    break;
-                                }
-                            }
-                        } else {
-                            // 首次检测，检查是否需要立即显示提醒
-                            let (should_alert, _, _) = detector.should_show_alert(
-                                &current_status,
-                                low_battery_threshold
-                            );
-
-                            if should_alert {
-                                // 根据状态决定事件类型
-                                let event = if current_status.battery_percentage <= low_battery_threshold {
-                                    PowerEvent::BatteryLow(current_status.battery_percentage)
-                                } else if !current_status.is_ac_connected {
-                                    PowerEvent::AcDisconnected
-                                } else {
-                                    continue;
-                                };
-
-                                let monitor_event = MonitorEvent {
-                                    power_event: event,
-                                    current_status: current_status.clone(),
-                                };
-
-                                if let Err(_) = tx.send(monitor_event).await {
-                                    let mut monitoring = is_monitoring.lock().unwrap();
-                                    *monitoring = false;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Error getting power status: {}", e);
-                    }
-                }
-            }
-        });
+    /// 使用多个电量提醒级别替代单一的低电量阈值（空列表时保留原阈值）
+    #[allow(dead_code)]
+    pub fn with_battery_levels(mut self, battery_levels: Vec<BatteryLevel>) -> Self {
+        if !battery_levels.is_empty() {
+            self.settings.get_mut().unwrap().battery_levels = battery_levels;
+        }
+        self
+    }
+
+    /// 把事件发布到指定的事件总线（重启监控后订阅者无需重新订阅）
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = event_bus;
+        self
+    }
 
-        rx
+    /// 设置扩展事件（严重低电量、耗电过快、充电停滞等）的检测参数
+    #[allow(dead_code)]
+    pub fn with_event_config(mut self, event_config: PowerEventConfig) -> Self {
+        self.settings.get_mut().unwrap().event_config = event_config;
+        self
+    }
+
+    /// 订阅监控事件
+    #[allow(dead_code)]
+    pub fn subscribe(&self, name: &str) -> EventSubscriber {
+        self.event_bus.subscribe(name)
+    }
+
+    /// 启动监控循环，返回控制句柄
+    ///
+    /// 同一个监控器只运行一个监控循环：之前启动的循环会先停止。
+    /// 需要接收首次检测的事件时，应在启动之前订阅。
+    pub async fn start(&self) -> MonitorHandle {
+        let previous = self.handle.lock().unwrap().take();
+        if let Some(previous) = previous {
+            previous.stop().await;
+        }
+
+        let (commands_tx, commands_rx) = mpsc::unbounded_channel();
+        let settings = self.settings.lock().unwrap().clone();
+        let cancel = CancellationToken::new();
+        let status = Arc::new(Mutex::new(MonitorStatus {
+            state: MonitorState::Running,
+            checks: 0,
+            polling: settings.polling,
+            last_status: None,
+        }));
+
+        let task = MonitorTask {
+            detector: self.detector.clone(),
+            event_tracker: PowerEventTracker::new(settings.event_config.clone()),
+            debouncer: EventDebouncer::new(settings.event_config.clone()),
+            settings,
+            last_status: None,
+            paused: false,
+            status: Arc::clone(&status),
+            event_bus: self.event_bus.clone(),
+        };
+        let join = tokio::spawn(task.run(commands_rx, cancel.clone()));
+
+        let handle = MonitorHandle {
+            commands: commands_tx,
+            cancel,
+            status,
+            task: Arc::new(Mutex::new(Some(join))),
+        };
+        *self.handle.lock().unwrap() = Some(handle.clone());
+        handle
+    }
+
+    /// 开始监控电源状态，返回一个新的订阅者
+    #[allow(dead_code)]
+    pub async fn start_monitoring(&self) -> EventSubscriber {
+        let subscriber = self.subscribe("monitor");
+        self.start().await;
+        subscriber
     }
 
     /// 停止监控
     #[allow(dead_code)]
     pub fn stop_monitoring(&self) {
-        let mut monitoring = self.is_monitoring.lock().unwrap();
-        *monitoring = false;
+        if let Some(handle) = self.handle.lock().unwrap().as_ref() {
+            handle.cancel();
+        }
     }
 
     /// 暂停监控
     #[allow(dead_code)]
     pub fn pause_monitoring(&self) {
-        self.stop_monitoring();
+        if let Some(handle) = self.handle.lock().unwrap().as_ref() {
+            handle.pause();
+        }
     }
 
     /// 恢复监控
     #[allow(dead_code)]
-    pub async fn resume_monitoring(&self) -> mpsc::Receiver<MonitorEvent> {
-        self.start_monitoring().await
+    pub fn resume_monitoring(&self) {
+        if let Some(handle) = self.handle.lock().unwrap().as_ref() {
+            handle.resume();
+        }
     }
 
     /// 检查当前是否正在监控
     #[allow(dead_code)]
     pub fn is_monitoring(&self) -> bool {
-        *self.is_monitoring.lock().unwrap()
+        self.handle
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|handle| handle.is_running() && handle.status().state == MonitorState::Running)
     }
 
     /// 获取当前电源状态
@@ -182,31 +330,255 @@ impl PowerMonitor {
     /// 检查是否应该显示提醒
     #[allow(dead_code)]
     pub fn should_show_alert(&self, status: &BatteryStatus) -> (bool, String, String) {
-        self.detector.should_show_alert(status, self.low_battery_threshold)
+        let battery_levels = self.settings.lock().unwrap().battery_levels.clone();
+        self.detector.should_show_alert(status, &battery_levels)
     }
 
     /// 更新低电量阈值
     #[allow(dead_code)]
-    pub fn set_low_battery_threshold(&mut self, threshold: u8) {
-        self.low_battery_threshold = threshold;
+    pub fn set_low_battery_threshold(&self, threshold: u8) {
+        self.update_settings(|settings| settings.battery_levels = vec![BatteryLevel::low(threshold)]);
     }
 
-    /// 更新检测间隔
+    /// 更新检测间隔（正在运行的监控循环立即生效）
     #[allow(dead_code)]
-    pub fn set_check_interval(&mut self, interval_secs: u64) {
-        self.check_interval = Duration::from_secs(interval_secs);
+    pub fn set_check_interval(&self, interval_secs: u64) {
+        self.update_settings(|settings| settings.polling.base = Duration::from_secs(interval_secs));
     }
+
+    /// 启用或关闭自适应检测间隔，并设置间隔范围（正在运行的监控循环立即生效）
+    #[allow(dead_code)]
+    pub fn set_adaptive_interval(&self, adaptive: bool, min_secs: u64, max_secs: u64) {
+        self.update_settings(|settings| {
+            settings.polling.adaptive = adaptive;
+            settings.polling.min = Duration::from_secs(min_secs);
+            settings.polling.max = Duration::from_secs(max_secs);
+        });
+    }
+
+    /// 当前的检测间隔设置
+    #[allow(dead_code)]
+    pub fn polling_interval(&self) -> PollingInterval {
+        self.settings.lock().unwrap().polling
+    }
+
+    /// 修改参数并同步到正在运行的监控循环
+    fn update_settings(&self, update: impl FnOnce(&mut MonitorSettings)) {
+        let mut settings = self.settings.lock().unwrap();
+        update(&mut settings);
+        if let Some(handle) = self.handle.lock().unwrap().as_ref() {
+            handle.update(settings.clone());
+        }
+    }
+}
+
+/// 在后台任务中运行的监控循环
+struct MonitorTask {
+    detector: PowerDetector,
+    settings: MonitorSettings,
+    event_tracker: PowerEventTracker,
+    debouncer: EventDebouncer,
+    last_status: Option<BatteryStatus>,
+    paused: bool,
+    status: Arc<Mutex<MonitorStatus>>,
+    event_bus: EventBus,
 }
 
-impl Clone for PowerDetector {
-    fn clone(&self) -> Self {
-        PowerDetector::new()
+impl MonitorTask {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<MonitorCommand>, cancel: CancellationToken) {
+        // 后端支持推送时，收到通知立即检测；轮询作为兜底
+        let mut changes = self.detector.subscribe_changes();
+        let mut last_check = time::Instant::now();
+        let mut next_check = last_check;
+
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = time::sleep_until(next_check), if !self.paused => {}
+                change = next_change(&mut changes) => match change {
+                    Some(change) => {
+                        if self.paused {
+                            continue;
+                        }
+                        // 由定时分支检测，等待期间仍然响应取消和命令
+                        let settle = time::Instant::now() + CHANGE_SETTLE_DELAY;
+                        if settle < next_check {
+                            crate::log_info!("Power change notification: {:?}", change);
+                            next_check = settle;
+                        }
+                        continue;
+                    }
+                    None => {
+                        crate::log_info!("Power change notifications closed, polling only");
+                        changes = None;
+                        continue;
+                    }
+                },
+                command = commands.recv() => match command {
+                    Some(MonitorCommand::Pause) => {
+                        self.paused = true;
+                        self.status.lock().unwrap().state = MonitorState::Paused;
+                        crate::log_info!("Power monitoring paused");
+                        continue;
+                    }
+                    Some(MonitorCommand::Resume) => {
+                        self.status.lock().unwrap().state = MonitorState::Running;
+                        if !self.paused {
+                            continue;
+                        }
+                        // 暂停期间的变化通过恢复后的第一次检测报告
+                        self.paused = false;
+                        crate::log_info!("Power monitoring resumed");
+                    }
+                    Some(MonitorCommand::Update(settings)) => {
+                        // 按新的设置重新计算下一次检测时间，不额外检测
+                        self.apply(settings);
+                        next_check = last_check + self.next_delay();
+                        continue;
+                    }
+                    None => {
+                        // 所有句柄都已丢弃，无法再控制监控循环
+                        break;
+                    }
+                },
+            }
+
+            // 获取当前电源状态
+            self.check();
+
+            // 根据本次检测到的状态决定下一次检测时间（推送触发的检测同样顺延）
+            last_check = time::Instant::now();
+            next_check = last_check + self.next_delay();
+        }
+
+        cancel.cancel();
+        self.status.lock().unwrap().state = MonitorState::Stopped;
+    }
+
+    fn next_delay(&self) -> Duration {
+        self.settings.polling.next_interval(
+            self.last_status.as_ref(),
+            &self.settings.battery_levels,
+            self.settings.event_config.critical_threshold,
+        )
+    }
+
+    fn apply(&mut self, settings: MonitorSettings) {
+        if settings.event_config != self.settings.event_config {
+            // 事件参数变化后按新参数重新积累历史和去抖状态
+            self.event_tracker = PowerEventTracker::new(settings.event_config.clone());
+            self.debouncer = EventDebouncer::new(settings.event_config.clone());
+        }
+        self.status.lock().unwrap().polling = settings.polling;
+        self.settings = settings;
+    }
+
+    /// 检测一次并把事件发布到事件总线
+    fn check(&mut self) {
+        let current_status = match self.detector.get_power_status() {
+            Ok(status) => status,
+            Err(e) => {
+                eprintln!("Error getting power status: {}", e);
+                return;
+            }
+        };
+
+        let previous_status = self.last_status.replace(current_status.clone());
+        {
+            let mut status = self.status.lock().unwrap();
+            status.checks += 1;
+            status.last_status = Some(current_status.clone());
+        }
+
+        let now = time::Instant::now();
+        let battery_levels = &self.settings.battery_levels;
+
+        // 需要历史数据的扩展事件（首次检测时只记录历史）
+        let tracked_events = self.event_tracker.detect(
+            now,
+            previous_status.as_ref(),
+            &current_status
+        );
+
+        let mut events = match previous_status {
+            Some(ref previous_status) => {
+                // 检测状态变化
+                let mut events = self.detector.detect_power_events(
+                    previous_status,
+                    &current_status,
+                    battery_levels
+                );
+                events.extend(tracked_events);
+                events
+            }
+            None => {
+                // 首次检测，检查是否需要立即显示提醒
+                let (should_alert, _, _) = self.detector.should_show_alert(
+                    &current_status,
+                    battery_levels
+                );
+
+                let mut initial_events = Vec::new();
+                if should_alert {
+                    // 根据状态决定事件类型
+                    match BatteryLevel::current(battery_levels, current_status.battery_percentage) {
+                        Some(level) => initial_events.push(PowerEvent::BatteryLow {
+                            level: level.clone(),
+                            percentage: current_status.battery_percentage,
+                        }),
+                        None if !current_status.is_ac_connected => {
+                            initial_events.push(PowerEvent::AcDisconnected);
+                        }
+                        None => {}
+                    }
+                }
+                initial_events
+            }
+        };
+
+        // 去抖：AC 状态变化需要保持一段时间，低电量恢复需要超过回差
+        // （首次状态同时作为去抖的基准）
+        events = self.debouncer.filter(now, &current_status, battery_levels, events);
+
+        if let Some(previous_status) = previous_status {
+            // 如果电量或功耗发生变化（即使没有触发事件），也发送一个状态更新事件
+            // 这确保提醒窗口和托盘菜单中的信息始终保持最新
+            let power_changed = previous_status.power_draw_watts != current_status.power_draw_watts;
+            let percentage_changed = previous_status.battery_percentage != current_status.battery_percentage;
+            let devices_changed = previous_status.devices != current_status.devices;
+
+            if percentage_changed || power_changed || devices_changed {
+                events.push(PowerEvent::StatusUpdate);
+            }
+        }
+
+        // 发布事件（没有订阅者时直接丢弃，订阅者处理缓慢也不会阻塞检测）
+        for event in events {
+            self.event_bus.publish(MonitorEvent {
+                power_event: event,
+                current_status: current_status.clone(),
+            });
+        }
+    }
+}
+
+/// 等待下一个推送通知；没有订阅时永远等待，只由轮询驱动
+async fn next_change(changes: &mut Option<ChangeReceiver>) -> Option<PowerChange> {
+    match changes {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::power::MockPowerSource;
+    use std::sync::Arc;
+
+    fn mock_monitor(source: &Arc<MockPowerSource>) -> PowerMonitor {
+        PowerMonitor::with_source(Box::new(Arc::clone(source)), 10, 20)
+    }
 
     #[test]
     fn test_power_monitor_creation() {
@@ -229,4 +601,346 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(100)).await;
         assert!(!monitor.is_monitoring());
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_initial_poll_reports_disconnected() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(false, 60)));
+        let monitor = mock_monitor(&source);
+
+        let mut rx = monitor.start_monitoring().await;
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+        assert_eq!(event.current_status.battery_percentage, 60);
+
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_monitor_loop_detects_transitions() {
+        let source = Arc::new(MockPowerSource::with_sequence(vec![
+            MockPowerSource::status(true, 80),
+            MockPowerSource::status(false, 80),
+            MockPowerSource::status(false, 19),
+        ]));
+        let monitor = mock_monitor(&source);
+
+        let mut rx = monitor.start_monitoring().await;
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 19 });
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::StatusUpdate);
+
+        assert_eq!(source.poll_count(), 3);
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_monitor_reports_tracked_events() {
+        let source = Arc::new(MockPowerSource::with_sequence(vec![
+            MockPowerSource::status(false, 8),
+            MockPowerSource::status(false, 4),
+        ]));
+        let monitor = mock_monitor(&source).with_event_config(PowerEventConfig {
+            critical_threshold: 5,
+            ..Default::default()
+        });
+
+        let mut rx = monitor.start_monitoring().await;
+
+        // 首次检测：电量低于低电量阈值
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 8 });
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::CriticalBattery(4));
+        assert_eq!(event.current_status.battery_percentage, 4);
+
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_monitor_debounces_flapping_charger() {
+        let source = Arc::new(MockPowerSource::with_sequence(vec![
+            MockPowerSource::status(true, 80),
+            MockPowerSource::status(false, 80),
+            MockPowerSource::status(true, 80),
+            MockPowerSource::status(false, 80),
+            MockPowerSource::status(false, 80),
+            MockPowerSource::status(false, 80),
+        ]));
+        let monitor = mock_monitor(&source).with_event_config(PowerEventConfig {
+            ac_min_dwell_secs: 15,
+            ..Default::default()
+        });
+
+        let mut rx = monitor.start_monitoring().await;
+
+        // 每 10 秒检测一次，断开状态保持 20 秒后才报告，中间的跳变被忽略
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+        assert_eq!(source.poll_count(), 6);
+
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_monitor_low_battery_hysteresis() {
+        let source = Arc::new(MockPowerSource::with_sequence(
+            [21, 20, 21, 20, 22, 20, 24, 19, 30]
+                .iter()
+                .map(|p| MockPowerSource::status(false, *p))
+                .collect(),
+        ));
+        let monitor = mock_monitor(&source).with_event_config(PowerEventConfig {
+            recovery_margin_percent: 5,
+            ..Default::default()
+        });
+
+        let mut rx = monitor.start_monitoring().await;
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            let event = rx.recv().await.unwrap();
+            if event.power_event != PowerEvent::StatusUpdate {
+                events.push(event.power_event);
+            }
+        }
+        monitor.stop_monitoring();
+
+        // 电量在阈值附近波动时只提醒一次，回升到 25% 以上才恢复正常
+        assert_eq!(events, vec![
+            PowerEvent::AcDisconnected,
+            PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 20 },
+            PowerEvent::BatteryNormal(30),
+        ]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_push_notification_triggers_immediate_check() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        // 轮询间隔很长，只有推送能及时发现变化
+        let monitor = PowerMonitor::with_source(Box::new(Arc::clone(&source)), 3600, 20);
+
+        let mut rx = monitor.start_monitoring().await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(source.poll_count(), 1);
+
+        let started = tokio::time::Instant::now();
+        source.set_status(MockPowerSource::status(false, 80));
+        source.notify_change(PowerChange::Supply("AC".to_string()));
+        source.notify_change(PowerChange::Supply("BAT0".to_string()));
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        // 连续的通知合并为一次检测
+        assert_eq!(source.poll_count(), 2);
+
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_set_check_interval_applies_to_running_loop() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        let monitor = PowerMonitor::with_source(Box::new(Arc::clone(&source)), 3600, 20);
+
+        let _rx = monitor.start_monitoring().await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(source.poll_count(), 1);
+
+        // 不重启监控，从上一次检测起改为每 10 秒一次
+        monitor.set_check_interval(10);
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(source.poll_count(), 4);
+
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_adaptive_interval_speeds_up_near_threshold() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 90)));
+        let monitor = mock_monitor(&source);
+        monitor.set_adaptive_interval(true, 2, 60);
+
+        let mut rx = monitor.start_monitoring().await;
+        // 接通电源时每 60 秒检测一次
+        tokio::time::sleep(Duration::from_secs(121)).await;
+        assert_eq!(source.poll_count(), 3);
+
+        // 拔掉电源且接近 20% 阈值后每 2 秒检测一次
+        source.set_status(MockPowerSource::status(false, 21));
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+        let polled = source.poll_count();
+        tokio::time::sleep(Duration::from_secs(9)).await;
+        assert_eq!(source.poll_count(), polled + 4);
+
+        monitor.stop_monitoring();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_restart_runs_single_loop() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        let monitor = mock_monitor(&source);
+
+        let first = monitor.start().await;
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(source.poll_count(), 1);
+
+        // 再次启动会先停止之前的循环
+        let second = monitor.start().await;
+        assert!(!first.is_running());
+        assert_eq!(first.status().state, MonitorState::Stopped);
+        assert!(second.is_running());
+
+        // t=5 启动的新循环在 5、15、25 秒检测；旧循环如果还在运行会在 10、20 秒额外检测
+        tokio::time::sleep(Duration::from_secs(21)).await;
+        assert_eq!(source.poll_count(), 4);
+        assert_eq!(second.status().checks, 3);
+
+        second.stop().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_subscribers_share_events_across_restart() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(false, 60)));
+        let bus = EventBus::default();
+        let mut tray = bus.subscribe("tray");
+        let mut alerts = bus.subscribe("alerts");
+
+        let monitor = mock_monitor(&source).with_event_bus(bus.clone());
+        monitor.start().await;
+        assert_eq!(tray.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+        assert_eq!(alerts.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+
+        // 新的监控器发布到同一个总线，订阅者无需重新订阅
+        monitor.stop_monitoring();
+        let monitor = mock_monitor(&source).with_event_bus(bus);
+        let handle = monitor.start().await;
+        assert_eq!(tray.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+        assert_eq!(alerts.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+
+        handle.stop().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_handle_pause_and_resume() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        let monitor = mock_monitor(&source);
+
+        let mut rx = monitor.subscribe("test");
+        let handle = monitor.start().await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(source.poll_count(), 1);
+
+        handle.pause();
+        assert_eq!(handle.status().state, MonitorState::Paused);
+        assert!(!monitor.is_monitoring());
+        source.set_status(MockPowerSource::status(false, 80));
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(source.poll_count(), 1);
+        assert!(rx.try_recv().is_none());
+
+        // 恢复后立即检测，暂停期间的变化随之报告
+        handle.resume();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+        assert_eq!(source.poll_count(), 2);
+
+        let status = handle.status();
+        assert_eq!(status.state, MonitorState::Running);
+        assert_eq!(status.checks, 2);
+        assert!(!status.last_status.unwrap().is_ac_connected);
+
+        handle.stop().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_handle_stop_waits_for_loop() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        let monitor = mock_monitor(&source);
+
+        let handle = monitor.start().await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        handle.stop().await;
+        assert_eq!(handle.status().state, MonitorState::Stopped);
+        assert!(!handle.is_running());
+        assert!(!monitor.is_monitoring());
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(source.poll_count(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stop_during_change_settle_delay() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        let monitor = mock_monitor(&source);
+
+        let handle = monitor.start().await;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        source.notify_change(PowerChange::Supply("AC".to_string()));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // 等待合并通知期间停止，不需要等到检测
+        let started = tokio::time::Instant::now();
+        handle.stop().await;
+        assert!(started.elapsed() < CHANGE_SETTLE_DELAY);
+        assert_eq!(source.poll_count(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_handle_update_config() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(false, 50)));
+        let monitor = PowerMonitor::with_source(Box::new(Arc::clone(&source)), 3600, 20);
+
+        let mut rx = monitor.subscribe("test");
+        let handle = monitor.start().await;
+        assert_eq!(rx.recv().await.unwrap().power_event, PowerEvent::AcDisconnected);
+        let started = tokio::time::Instant::now();
+
+        handle.update_config(MonitoringConfig {
+            check_interval: 10,
+            adaptive_interval: false,
+            battery_levels: vec![BatteryLevel {
+                name: "warning".to_string(),
+                threshold: 45,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let polling = handle.status().polling;
+        assert_eq!(polling.base, Duration::from_secs(10));
+        assert!(!polling.adaptive);
+
+        // 新的检测间隔和电量级别立即生效
+        source.set_status(MockPowerSource::status(false, 45));
+        let event = rx.recv().await.unwrap();
+        assert!(matches!(event.power_event, PowerEvent::BatteryLow { ref level, percentage: 45 } if level.name == "warning"));
+        assert_eq!(source.poll_count(), 2);
+        assert!(started.elapsed() <= Duration::from_secs(10));
+
+        handle.stop().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_monitor_survives_source_errors() {
+        let source = Arc::new(MockPowerSource::new(MockPowerSource::status(true, 80)));
+        source.set_error("unavailable");
+        let monitor = mock_monitor(&source);
+
+        let mut rx = monitor.start_monitoring().await;
+        tokio::time::sleep(Duration::from_secs(25)).await;
+        assert!(source.poll_count() >= 3);
+
+        source.set_status(MockPowerSource::status(false, 50));
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.power_event, PowerEvent::AcDisconnected);
+
+        monitor.stop_monitoring();
+    }
 }
\ No newline at end of file
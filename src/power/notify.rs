@@ -0,0 +1,355 @@
+// 系统推送的电源变化通知
+//
+// 轮询间隔内拔掉电源需要等到下一次检测才能发现，Windows 下每次检测还要启动 PowerShell。
+// 系统支持推送时，这里把推送转换为 `PowerChange` 发送给监控循环，监控循环收到后立即检测一次；
+// 轮询仍然保留，作为推送不可用或丢失时的兜底。
+//
+// - Linux：`udevadm monitor` 输出的 power_supply uevent
+// - Windows：`RegisterPowerSettingNotification` 注册的 `WM_POWERBROADCAST` 消息
+
+#![cfg_attr(not(any(target_os = "linux", target_os = "windows")), allow(dead_code))]
+
+use tokio::sync::mpsc;
+
+/// 系统报告的电源变化（只作为“需要重新检测”的提示，具体状态仍然从后端读取）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PowerChange {
+    Supply(String),  // Linux：发生变化的 power_supply 设备名（如 AC、BAT0）
+    AcLine,          // Windows：交流/直流供电切换
+    BatteryLevel,    // Windows：电量百分比变化
+    PowerStatus,     // Windows：PBT_APMPOWERSTATUSCHANGE
+}
+
+pub type ChangeSender = mpsc::UnboundedSender<PowerChange>;
+
+/// 电源变化通知的接收端
+///
+/// 可以附带产生通知的后台资源（如 udevadm 子进程），接收端被丢弃时一起释放，
+/// 不必等到下一条通知才发现没有人接收。
+pub struct ChangeReceiver {
+    receiver: mpsc::UnboundedReceiver<PowerChange>,
+    _guard: Option<Box<dyn Send>>,
+}
+
+impl ChangeReceiver {
+    /// 接收端被丢弃时一起丢弃 `guard`
+    pub fn with_guard<G: Send + 'static>(mut self, guard: G) -> Self {
+        self._guard = Some(Box::new(guard));
+        self
+    }
+
+    pub async fn recv(&mut self) -> Option<PowerChange> {
+        self.receiver.recv().await
+    }
+}
+
+pub fn change_channel() -> (ChangeSender, ChangeReceiver) {
+    let (tx, receiver) = mpsc::unbounded_channel();
+    (tx, ChangeReceiver { receiver, _guard: None })
+}
+
+/// 丢弃时结束并回收子进程
+#[cfg(target_os = "linux")]
+struct ChildGuard(std::process::Child);
+
+#[cfg(target_os = "linux")]
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// 解析 `udevadm monitor --udev --subsystem-match=power_supply` 的一行输出
+///
+/// 事件行的格式为：
+/// `UDEV  [12345.678901] change   /devices/.../power_supply/BAT0 (power_supply)`
+pub fn parse_udev_line(line: &str) -> Option<PowerChange> {
+    let mut parts = line.split_whitespace();
+    // KERNEL 行的时间戳紧跟在来源后面：`KERNEL[12345.600000]`
+    let origin = parts.next()?.split('[').next()?;
+    if origin != "UDEV" && origin != "KERNEL" {
+        return None;
+    }
+
+    let mut parts = parts.skip_while(|part| part.starts_with('['));
+    let _action = parts.next()?;
+    let path = parts.next()?;
+    let subsystem = parts.next()?;
+    if subsystem != "(power_supply)" {
+        return None;
+    }
+
+    let name = path.rsplit('/').next().filter(|name| !name.is_empty())?;
+    Some(PowerChange::Supply(name.to_string()))
+}
+
+/// 启动 `udevadm monitor` 监听 power_supply 事件
+///
+/// 返回的接收端持有 udevadm 子进程，接收端被丢弃时结束子进程，读取线程随之读到 EOF 退出；
+/// udevadm 不可用或退出时接收端会关闭，监控循环随之退回纯轮询。
+#[cfg(target_os = "linux")]
+pub fn watch_udev_power_supply() -> Result<ChangeReceiver, String> {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("udevadm")
+        .args(["monitor", "--udev", "--subsystem-match=power_supply"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start udevadm monitor: {}", e))?;
+
+    let stdout = child.stdout.take();
+    // 先交给 guard，之后任何一步失败都会结束子进程
+    let guard = ChildGuard(child);
+    let stdout = stdout.ok_or_else(|| "udevadm monitor has no stdout".to_string())?;
+    let (tx, rx) = change_channel();
+
+    std::thread::Builder::new()
+        .name("udev-power-supply".to_string())
+        .spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                if let Some(change) = parse_udev_line(&line) {
+                    if tx.send(change).is_err() {
+                        break;
+                    }
+                }
+            }
+            crate::log_info!("udev power_supply watcher stopped");
+        })
+        .map_err(|e| format!("Failed to spawn udev watcher thread: {}", e))?;
+
+    Ok(rx.with_guard(guard))
+}
+
+/// 创建隐藏窗口接收 `WM_POWERBROADCAST`
+///
+/// 注册 AC/DC 切换和电量变化两个电源设置通知；隐藏的顶层窗口同时会收到系统广播的
+/// `PBT_APMPOWERSTATUSCHANGE`。消息循环运行在独立线程中，接收端被丢弃时向该线程发送
+/// `WM_QUIT`，消息循环退出并销毁窗口。
+#[cfg(target_os = "windows")]
+pub fn watch_power_broadcasts() -> Result<ChangeReceiver, String> {
+    let (tx, rx) = change_channel();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+
+    let thread = std::thread::Builder::new()
+        .name("power-broadcast".to_string())
+        .spawn(move || power_broadcast::run(tx, ready_tx))
+        .map_err(|e| format!("Failed to spawn power broadcast thread: {}", e))?;
+
+    let thread_id = ready_rx
+        .recv()
+        .map_err(|_| "Power broadcast thread exited".to_string())??;
+    Ok(rx.with_guard(power_broadcast::QuitGuard { thread_id, thread: Some(thread) }))
+}
+
+#[cfg(target_os = "windows")]
+mod power_broadcast {
+    use super::{ChangeSender, PowerChange};
+    use std::cell::RefCell;
+    use std::sync::OnceLock;
+    use windows::core::{w, GUID};
+    use windows::Win32::Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::System::Power::{
+        RegisterPowerSettingNotification, UnregisterPowerSettingNotification, POWERBROADCAST_SETTING,
+    };
+    use windows::Win32::System::SystemServices::{GUID_ACDC_POWER_SOURCE, GUID_BATTERY_PERCENTAGE_REMAINING};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW, PostQuitMessage,
+        PostThreadMessageW, RegisterClassW, TranslateMessage, DEVICE_NOTIFY_WINDOW_HANDLE, MSG,
+        PBT_APMPOWERSTATUSCHANGE, PBT_POWERSETTINGCHANGE, WINDOW_EX_STYLE, WM_POWERBROADCAST, WM_QUIT, WNDCLASSW,
+        WS_OVERLAPPED,
+    };
+
+    static CLASS_REGISTERED: OnceLock<bool> = OnceLock::new();
+
+    thread_local! {
+        // 窗口过程与消息循环在同一线程中运行
+        static SENDER: RefCell<Option<ChangeSender>> = RefCell::new(None);
+    }
+
+    /// 丢弃时向消息循环所在线程发送 `WM_QUIT`，并等待线程销毁窗口后退出
+    pub struct QuitGuard {
+        pub thread_id: u32,
+        pub thread: Option<std::thread::JoinHandle<()>>,
+    }
+
+    impl Drop for QuitGuard {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+
+    /// 运行消息循环，窗口创建成功后通过 `ready` 报告线程 ID（用于发送 `WM_QUIT`）
+    pub fn run(tx: ChangeSender, ready: std::sync::mpsc::Sender<Result<u32, String>>) {
+        SENDER.with(|sender| *sender.borrow_mut() = Some(tx));
+
+        unsafe {
+            let instance = match GetModuleHandleW(None) {
+                Ok(instance) => instance,
+                Err(e) => {
+                    let _ = ready.send(Err(format!("GetModuleHandleW failed: {}", e)));
+                    return;
+                }
+            };
+
+            let class_name = w!("isBatteryPowerBroadcast");
+            let class = WNDCLASSW {
+                hInstance: instance.into(),
+                lpszClassName: class_name,
+                lpfnWndProc: Some(window_proc),
+                ..Default::default()
+            };
+            // 监控循环结束后重新启动时会再创建一个窗口，窗口类只注册一次
+            if !*CLASS_REGISTERED.get_or_init(|| RegisterClassW(&class) != 0) {
+                let _ = ready.send(Err("RegisterClassW failed".to_string()));
+                return;
+            }
+
+            // 不使用 HWND_MESSAGE：仅消息窗口收不到广播消息
+            let window = CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                class_name,
+                w!("isBattery power broadcast"),
+                WS_OVERLAPPED,
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                instance,
+                None,
+            );
+            if window.0 == 0 {
+                let _ = ready.send(Err("CreateWindowExW failed".to_string()));
+                return;
+            }
+
+            let mut registrations = Vec::new();
+            for guid in [&GUID_ACDC_POWER_SOURCE, &GUID_BATTERY_PERCENTAGE_REMAINING] {
+                match RegisterPowerSettingNotification(HANDLE(window.0), guid, DEVICE_NOTIFY_WINDOW_HANDLE.0) {
+                    Ok(handle) => registrations.push(handle),
+                    Err(e) => crate::log_error!("RegisterPowerSettingNotification failed: {}", e),
+                }
+            }
+            // 窗口创建后线程已有消息队列，此后发送的 WM_QUIT 不会丢失
+            let _ = ready.send(Ok(GetCurrentThreadId()));
+
+            let mut message = MSG::default();
+            while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                let _ = TranslateMessage(&message);
+                DispatchMessageW(&message);
+            }
+
+            for handle in registrations {
+                let _ = UnregisterPowerSettingNotification(handle);
+            }
+            let _ = DestroyWindow(window);
+        }
+
+        crate::log_info!("Power broadcast watcher stopped");
+    }
+
+    extern "system" fn window_proc(window: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if message == WM_POWERBROADCAST {
+            let change = match wparam.0 as u32 {
+                PBT_APMPOWERSTATUSCHANGE => Some(PowerChange::PowerStatus),
+                PBT_POWERSETTINGCHANGE if lparam.0 != 0 => {
+                    let setting = unsafe { &*(lparam.0 as *const POWERBROADCAST_SETTING) };
+                    Some(setting_change(&setting.PowerSetting))
+                }
+                _ => None,
+            };
+
+            if let Some(change) = change {
+                let sent = SENDER.with(|sender| match sender.borrow().as_ref() {
+                    Some(sender) => sender.send(change).is_ok(),
+                    None => false,
+                });
+                if !sent {
+                    unsafe { PostQuitMessage(0) };
+                }
+            }
+            return LRESULT(1);
+        }
+
+        unsafe { DefWindowProcW(window, message, wparam, lparam) }
+    }
+
+    fn setting_change(guid: &GUID) -> PowerChange {
+        if *guid == GUID_ACDC_POWER_SOURCE {
+            PowerChange::AcLine
+        } else if *guid == GUID_BATTERY_PERCENTAGE_REMAINING {
+            PowerChange::BatteryLevel
+        } else {
+            PowerChange::PowerStatus
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_udev_line() {
+        let line = "UDEV  [12345.678901] change   /devices/LNXSYSTM:00/LNXSYBUS:00/PNP0C0A:00/power_supply/BAT0 (power_supply)";
+        assert_eq!(parse_udev_line(line), Some(PowerChange::Supply("BAT0".to_string())));
+
+        let line = "KERNEL[12345.600000] change   /devices/platform/ACPI0003:00/power_supply/AC (power_supply)";
+        assert_eq!(parse_udev_line(line), Some(PowerChange::Supply("AC".to_string())));
+    }
+
+    #[test]
+    fn test_parse_udev_ignores_other_lines() {
+        assert_eq!(parse_udev_line("monitor will print the received events for:"), None);
+        assert_eq!(parse_udev_line("UDEV - the event which udev sends out after rule processing"), None);
+        assert_eq!(parse_udev_line(""), None);
+        assert_eq!(
+            parse_udev_line("UDEV  [1.0] change   /devices/pci0000:00/usb1/1-1 (usb)"),
+            None
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_dropping_receiver_stops_child() {
+        let child = std::process::Command::new("sleep").arg("60").spawn().unwrap();
+        let pid = child.id();
+        let (_tx, rx) = change_channel();
+        let rx = rx.with_guard(ChildGuard(child));
+        assert!(std::path::Path::new(&format!("/proc/{}", pid)).exists());
+
+        // 丢弃接收端时立即结束并回收子进程，不需要等到下一条通知
+        drop(rx);
+        assert!(!std::path::Path::new(&format!("/proc/{}", pid)).exists());
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_dropping_receiver_stops_power_broadcast_loop() {
+        let rx = watch_power_broadcasts().unwrap();
+
+        // 丢弃接收端会等待消息循环退出；没有电源广播时也不能一直阻塞
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            drop(rx);
+            let _ = done_tx.send(());
+        });
+        assert!(done_rx.recv_timeout(std::time::Duration::from_secs(5)).is_ok());
+    }
+}
@@ -0,0 +1,356 @@
+use crate::power::BatteryStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 置信区间使用的 z 值（约 95%）
+const CONFIDENCE_Z: f64 = 1.96;
+/// 低于该速率（%/小时）视为电量没有变化，不做预测
+const MIN_RATE_PER_HOUR: f64 = 0.1;
+/// 预测结果的上限，避免速率接近 0 时给出无意义的超长时间
+const MAX_PREDICTION_MINUTES: f64 = 48.0 * 60.0;
+
+/// 预测方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PredictionDirection {
+    ToEmpty,  // 放电：预计多久耗尽
+    ToFull,   // 充电：预计多久充满
+}
+
+/// 剩余时间预测结果
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RemainingTimePrediction {
+    pub direction: PredictionDirection,
+    pub minutes: u32,              // 预测值（分钟）
+    pub lower_minutes: u32,        // 置信区间下限（分钟）
+    pub upper_minutes: u32,        // 置信区间上限（分钟）
+    pub rate_percent_per_hour: f32, // 平滑后的电量变化速率（放电为负）
+    pub sample_count: usize,
+}
+
+/// 预测器参数
+#[derive(Debug, Clone)]
+pub struct PredictorConfig {
+    pub window: Duration,        // 参与拟合的样本时间窗口
+    pub max_samples: usize,      // 窗口内最多保留的样本数
+    pub min_samples: usize,      // 开始预测所需的最少样本数
+    pub min_span: Duration,      // 开始预测所需的最短观测时长
+    pub min_interval: Duration,  // 两个样本的最小间隔，更密集的查询不会记录新样本
+    pub smoothing: f64,          // 指数平滑系数（0~1，越大越偏向最新速率）
+}
+
+impl Default for PredictorConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(30 * 60),
+            max_samples: 360,
+            min_samples: 3,
+            min_span: Duration::from_secs(60),
+            min_interval: Duration::from_secs(1),
+            smoothing: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChargeMode {
+    Charging,
+    Discharging,
+    Idle,  // 接通电源但未充电（已充满或充电暂停）
+}
+
+impl ChargeMode {
+    fn of(status: &BatteryStatus) -> Self {
+        if status.is_charging {
+            ChargeMode::Charging
+        } else if !status.is_ac_connected {
+            ChargeMode::Discharging
+        } else {
+            ChargeMode::Idle
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    percentage: f64,
+}
+
+/// 基于电量历史的剩余时间预测器
+///
+/// 在滑动时间窗口内对 (时间, 电量百分比) 做最小二乘拟合得到电量变化速率，
+/// 再对速率做指数平滑，并结合拟合误差和平滑方差给出置信区间。
+/// 只依赖电量百分比，因此对任何后端都可用。充放电状态改变时重新开始统计。
+#[derive(Debug, Clone)]
+pub struct RemainingTimePredictor {
+    config: PredictorConfig,
+    samples: VecDeque<Sample>,
+    mode: Option<ChargeMode>,
+    smoothed_rate: Option<f64>,  // %/小时
+    rate_variance: f64,
+}
+
+impl RemainingTimePredictor {
+    pub fn new(config: PredictorConfig) -> Self {
+        Self {
+            config,
+            samples: VecDeque::new(),
+            mode: None,
+            smoothed_rate: None,
+            rate_variance: 0.0,
+        }
+    }
+
+    /// 清空历史（例如恢复监控或系统唤醒后）
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.mode = None;
+        self.smoothed_rate = None;
+        self.rate_variance = 0.0;
+    }
+
+    /// 当前窗口内的样本数
+    #[allow(dead_code)]
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// 记录一个电源状态样本并返回最新预测，数据不足或电量没有变化时返回 None
+    pub fn observe(&mut self, at: Instant, status: &BatteryStatus) -> Option<RemainingTimePrediction> {
+        if !status.is_battery_present {
+            self.reset();
+            return None;
+        }
+
+        let mode = ChargeMode::of(status);
+        if self.mode != Some(mode) {
+            self.reset();
+            self.mode = Some(mode);
+        }
+        if mode == ChargeMode::Idle {
+            return None;
+        }
+
+        let percentage = status.battery_percentage as f64;
+        let too_close = self
+            .samples
+            .back()
+            .is_some_and(|last| at.saturating_duration_since(last.at) < self.config.min_interval);
+
+        if !too_close {
+            self.samples.push_back(Sample { at, percentage });
+            while self.samples.len() > self.config.max_samples {
+                self.samples.pop_front();
+            }
+            while let Some(first) = self.samples.front() {
+                if at.saturating_duration_since(first.at) > self.config.window {
+                    self.samples.pop_front();
+                } else {
+                    break;
+                }
+            }
+            self.update_rate();
+        }
+
+        self.predict(mode, percentage)
+    }
+
+    /// 用窗口内的样本拟合速率并更新平滑值
+    fn update_rate(&mut self) {
+        if self.samples.len() < self.config.min_samples.max(2) {
+            return;
+        }
+        let (first, last) = (self.samples[0].at, self.samples[self.samples.len() - 1].at);
+        if last.saturating_duration_since(first) < self.config.min_span {
+            return;
+        }
+
+        let (slope, slope_variance) = match self.fit() {
+            Some(fit) => fit,
+            None => return,
+        };
+
+        match self.smoothed_rate {
+            None => {
+                self.smoothed_rate = Some(slope);
+                self.rate_variance = slope_variance;
+            }
+            Some(smoothed) => {
+                let alpha = self.config.smoothing;
+                let diff = slope - smoothed;
+                self.smoothed_rate = Some(smoothed + alpha * diff);
+                self.rate_variance = (1.0 - alpha) * (self.rate_variance + alpha * diff * diff)
+                    + alpha * slope_variance;
+            }
+        }
+    }
+
+    /// 最小二乘拟合，返回 (斜率 %/小时, 斜率方差)
+    fn fit(&self) -> Option<(f64, f64)> {
+        let origin = self.samples[0].at;
+        let points: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .map(|s| (s.at.saturating_duration_since(origin).as_secs_f64() / 3600.0, s.percentage))
+            .collect();
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        if sxx <= 0.0 {
+            return None;
+        }
+        let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let slope = sxy / sxx;
+
+        let slope_variance = if points.len() > 2 {
+            let intercept = mean_y - slope * mean_x;
+            let residuals: f64 = points.iter().map(|p| (p.1 - intercept - slope * p.0).powi(2)).sum();
+            residuals / (n - 2.0) / sxx
+        } else {
+            0.0
+        };
+
+        Some((slope, slope_variance))
+    }
+
+    fn predict(&self, mode: ChargeMode, percentage: f64) -> Option<RemainingTimePrediction> {
+        let rate = self.smoothed_rate?;
+        let (direction, remaining, speed) = match mode {
+            ChargeMode::Discharging if rate < -MIN_RATE_PER_HOUR => (PredictionDirection::ToEmpty, percentage, -rate),
+            ChargeMode::Charging if rate > MIN_RATE_PER_HOUR => (PredictionDirection::ToFull, 100.0 - percentage, rate),
+            _ => return None,
+        };
+
+        let margin = CONFIDENCE_Z * self.rate_variance.max(0.0).sqrt();
+        let minutes_at = |speed: f64| -> u32 {
+            if speed <= 0.0 {
+                MAX_PREDICTION_MINUTES as u32
+            } else {
+                (remaining.max(0.0) / speed * 60.0).min(MAX_PREDICTION_MINUTES).round() as u32
+            }
+        };
+
+        Some(RemainingTimePrediction {
+            direction,
+            minutes: minutes_at(speed),
+            lower_minutes: minutes_at(speed + margin),
+            upper_minutes: minutes_at((speed - margin).max(0.0)),
+            rate_percent_per_hour: rate as f32,
+            sample_count: self.samples.len(),
+        })
+    }
+}
+
+impl Default for RemainingTimePredictor {
+    fn default() -> Self {
+        Self::new(PredictorConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::status;
+
+    /// 按固定间隔喂入线性变化的电量，返回最后一次预测
+    fn feed(
+        predictor: &mut RemainingTimePredictor,
+        start: Instant,
+        is_ac_connected: bool,
+        levels: &[u8],
+        step: Duration,
+    ) -> Option<RemainingTimePrediction> {
+        let mut prediction = None;
+        for (i, level) in levels.iter().enumerate() {
+            prediction = predictor.observe(start + step * i as u32, &status(is_ac_connected, *level));
+        }
+        prediction
+    }
+
+    #[test]
+    fn test_time_to_empty() {
+        let mut predictor = RemainingTimePredictor::default();
+        // 每 3 分钟下降 1%，即 20%/小时
+        let levels: Vec<u8> = (0..10).map(|i| 80 - i).collect();
+        let prediction = feed(&mut predictor, Instant::now(), false, &levels, Duration::from_secs(180)).unwrap();
+
+        assert_eq!(prediction.direction, PredictionDirection::ToEmpty);
+        assert!((prediction.rate_percent_per_hour + 20.0).abs() < 0.5);
+        // 剩余 71%，约 213 分钟
+        assert!((prediction.minutes as i32 - 213).abs() <= 3, "{:?}", prediction);
+        assert!(prediction.lower_minutes <= prediction.minutes);
+        assert!(prediction.upper_minutes >= prediction.minutes);
+    }
+
+    #[test]
+    fn test_time_to_full() {
+        let mut predictor = RemainingTimePredictor::default();
+        // 每分钟上升 1%，即 60%/小时
+        let levels: Vec<u8> = (40..50).collect();
+        let prediction = feed(&mut predictor, Instant::now(), true, &levels, Duration::from_secs(60)).unwrap();
+
+        assert_eq!(prediction.direction, PredictionDirection::ToFull);
+        // 剩余 51%，约 51 分钟
+        assert!((prediction.minutes as i32 - 51).abs() <= 2, "{:?}", prediction);
+    }
+
+    #[test]
+    fn test_needs_enough_history() {
+        let mut predictor = RemainingTimePredictor::default();
+        let start = Instant::now();
+        assert!(predictor.observe(start, &status(false, 80)).is_none());
+        assert!(predictor.observe(start + Duration::from_secs(20), &status(false, 80)).is_none());
+        // 样本数足够但观测时长不足 1 分钟
+        assert!(predictor.observe(start + Duration::from_secs(40), &status(false, 79)).is_none());
+    }
+
+    #[test]
+    fn test_mode_change_resets_history() {
+        let mut predictor = RemainingTimePredictor::default();
+        let start = Instant::now();
+        let levels: Vec<u8> = (0..10).map(|i| 80 - i).collect();
+        assert!(feed(&mut predictor, start, false, &levels, Duration::from_secs(60)).is_some());
+
+        // 插上电源后放电历史不再适用
+        assert!(predictor.observe(start + Duration::from_secs(700), &status(true, 71)).is_none());
+        assert_eq!(predictor.sample_count(), 1);
+    }
+
+    #[test]
+    fn test_flat_level_gives_no_prediction() {
+        let mut predictor = RemainingTimePredictor::default();
+        let prediction = feed(&mut predictor, Instant::now(), false, &[60; 10], Duration::from_secs(60));
+        assert!(prediction.is_none());
+    }
+
+    #[test]
+    fn test_noisy_samples_widen_interval() {
+        let start = Instant::now();
+        let steady: Vec<u8> = (0..12).map(|i| 90 - i).collect();
+        let noisy: Vec<u8> = (0..12).map(|i| 90 - i + if i % 2 == 0 { 2 } else { 0 }).collect();
+
+        let mut predictor = RemainingTimePredictor::default();
+        let steady = feed(&mut predictor, start, false, &steady, Duration::from_secs(60)).unwrap();
+        let mut predictor = RemainingTimePredictor::default();
+        let noisy = feed(&mut predictor, start, false, &noisy, Duration::from_secs(60)).unwrap();
+
+        let width = |p: &RemainingTimePrediction| p.upper_minutes - p.lower_minutes;
+        assert!(width(&noisy) > width(&steady));
+    }
+
+    #[test]
+    fn test_old_samples_leave_window() {
+        let config = PredictorConfig {
+            window: Duration::from_secs(600),
+            ..Default::default()
+        };
+        let mut predictor = RemainingTimePredictor::new(config);
+        let levels: Vec<u8> = (0..30).map(|i| 90 - i / 2).collect();
+        feed(&mut predictor, Instant::now(), false, &levels, Duration::from_secs(60));
+        assert_eq!(predictor.sample_count(), 11);
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// 数值的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Provenance {
+    Measured,   // 硬件/系统直接报告
+    Derived,    // 由实测值计算得到（如 电流 × 电压、剩余能量 ÷ 功率）
+    Estimated,  // 数据缺失时使用的经验值
+    #[default]
+    Unknown,
+}
+
+impl Provenance {
+    pub fn is_estimated(self) -> bool {
+        self == Provenance::Estimated
+    }
+
+    /// 有值时返回自身，没有值时返回 `Unknown`
+    pub fn if_some<T>(self, value: &Option<T>) -> Self {
+        if value.is_some() { self } else { Provenance::Unknown }
+    }
+
+    /// 显示数值时使用的前缀，估算值显示为 "≈15.0W"
+    pub fn marker(self) -> &'static str {
+        if self.is_estimated() { "≈" } else { "" }
+    }
+
+    /// 中文说明
+    pub fn label(self) -> &'static str {
+        match self {
+            Provenance::Measured => "实测",
+            Provenance::Derived => "计算",
+            Provenance::Estimated => "估算",
+            Provenance::Unknown => "未知",
+        }
+    }
+}
+
+/// `BatteryStatus` 中各数值字段的来源
+///
+/// 单独放在一个结构中，保持 `BatteryStatus` 原有字段的 JSON 格式不变。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusProvenance {
+    pub power_draw_watts: Provenance,
+    pub battery_capacity_mwh: Provenance,
+    pub remaining_time_minutes: Provenance,
+    pub charge_rate_watts: Provenance,
+}
+
+impl StatusProvenance {
+    /// 所有字段使用同一来源
+    pub fn all(provenance: Provenance) -> Self {
+        Self {
+            power_draw_watts: provenance,
+            battery_capacity_mwh: provenance,
+            remaining_time_minutes: provenance,
+            charge_rate_watts: provenance,
+        }
+    }
+
+    /// 是否有任意字段为估算值
+    pub fn has_estimates(&self) -> bool {
+        [
+            self.power_draw_watts,
+            self.battery_capacity_mwh,
+            self.remaining_time_minutes,
+            self.charge_rate_watts,
+        ]
+        .iter()
+        .any(|p| p.is_estimated())
+    }
+}
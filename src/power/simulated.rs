@@ -0,0 +1,400 @@
+use crate::power::{BatteryStatus, PowerDevice, PowerSource, Provenance, SourceCapabilities, StatusProvenance};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 模拟场景的初始状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulatedState {
+    pub is_ac_connected: bool,
+    pub is_charging: bool,
+    pub is_battery_present: bool,
+    pub battery_percentage: f32,
+    pub power_draw_watts: Option<f32>,
+    pub battery_capacity_mwh: Option<u32>,
+    pub rate_pct_per_sec: f32,  // 电量变化速率（负数为放电）
+}
+
+impl Default for SimulatedState {
+    fn default() -> Self {
+        Self {
+            is_ac_connected: true,
+            is_charging: false,
+            is_battery_present: true,
+            battery_percentage: 100.0,
+            power_draw_watts: None,
+            battery_capacity_mwh: None,
+            rate_pct_per_sec: 0.0,
+        }
+    }
+}
+
+/// 场景中的一个步骤
+///
+/// 触发条件为 `at_secs`（距场景开始的秒数）或 `at_percentage`（电量到达该值），
+/// 触发后用给出的字段覆盖当前状态，未给出的字段保持不变。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioStep {
+    pub at_secs: Option<f64>,
+    pub at_percentage: Option<u8>,
+    pub is_ac_connected: Option<bool>,
+    pub is_charging: Option<bool>,
+    pub is_battery_present: Option<bool>,
+    pub battery_percentage: Option<f32>,
+    pub power_draw_watts: Option<f32>,
+    pub rate_pct_per_sec: Option<f32>,
+}
+
+/// 模拟场景：初始状态 + 按顺序执行的步骤
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scenario {
+    pub name: String,
+    pub initial: SimulatedState,
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    /// 从 TOML 或 JSON 文件加载场景（按扩展名判断，默认为 TOML）
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read scenario {}: {}", path.display(), e))?;
+
+        let is_json = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("json"))
+            .unwrap_or(false);
+
+        if is_json {
+            Self::from_json(&content)
+        } else {
+            Self::from_toml(&content)
+        }
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let scenario: Scenario = toml::from_str(content).map_err(|e| format!("Invalid scenario: {}", e))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let scenario: Scenario = serde_json::from_str(content).map_err(|e| format!("Invalid scenario: {}", e))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// 验证每个步骤有且只有一个触发条件
+    pub fn validate(&self) -> Result<(), String> {
+        for (index, step) in self.steps.iter().enumerate() {
+            match (step.at_secs, step.at_percentage) {
+                (Some(_), Some(_)) | (None, None) => {
+                    return Err(format!("步骤 {} 必须且只能指定 at_secs 或 at_percentage 之一", index + 1));
+                }
+                (Some(secs), None) if secs < 0.0 => {
+                    return Err(format!("步骤 {} 的 at_secs 不能为负数", index + 1));
+                }
+                (None, Some(percentage)) if percentage > 100 => {
+                    return Err(format!("步骤 {} 的 at_percentage 不能超过100", index + 1));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// 计算场景开始 `elapsed` 之后的状态
+    pub fn state_at(&self, elapsed: Duration) -> SimulatedState {
+        let target = elapsed.as_secs_f64();
+        let mut state = self.initial.clone();
+        let mut now = 0.0f64;
+
+        for step in &self.steps {
+            let trigger = match (step.at_secs, step.at_percentage) {
+                (Some(secs), _) => Some(secs.max(now)),
+                (None, Some(percentage)) => {
+                    time_to_reach(&state, percentage as f32).map(|secs| now + secs)
+                }
+                (None, None) => None,
+            };
+
+            // 步骤永远不会触发（例如电量不再变化），后续步骤也不会执行
+            let Some(trigger) = trigger else { break };
+            if trigger > target {
+                break;
+            }
+
+            advance(&mut state, trigger - now);
+            now = trigger;
+            apply_step(&mut state, step);
+        }
+
+        advance(&mut state, target - now);
+        state
+    }
+
+    /// 将某个时刻的模拟状态转换为 `BatteryStatus`
+    pub fn status_at(&self, elapsed: Duration) -> BatteryStatus {
+        let state = self.state_at(elapsed);
+        let battery_percentage = state.battery_percentage.round().clamp(0.0, 100.0) as u8;
+
+        let remaining_time_minutes = match (state.is_ac_connected, state.power_draw_watts, state.battery_capacity_mwh) {
+            (false, Some(watts), Some(capacity)) if watts > 0.0 => {
+                let remaining_wh = capacity as f32 / 1000.0 * state.battery_percentage / 100.0;
+                Some((remaining_wh / watts * 60.0) as u32)
+            }
+            _ => None,
+        };
+
+        let mut status = BatteryStatus {
+            is_charging: state.is_charging,
+            is_ac_connected: state.is_ac_connected,
+            battery_percentage,
+            is_battery_present: state.is_battery_present,
+            power_draw_watts: state.power_draw_watts,
+            battery_capacity_mwh: state.battery_capacity_mwh,
+            remaining_time_minutes,
+            charge_rate_watts: state.power_draw_watts,
+            devices: Vec::new(),
+            // 场景中给出的数值视为实测值，剩余时间由其计算得到
+            provenance: StatusProvenance {
+                power_draw_watts: Provenance::Measured.if_some(&state.power_draw_watts),
+                battery_capacity_mwh: Provenance::Measured.if_some(&state.battery_capacity_mwh),
+                remaining_time_minutes: Provenance::Derived.if_some(&remaining_time_minutes),
+                charge_rate_watts: Provenance::Measured.if_some(&state.power_draw_watts),
+            },
+            prediction: None,
+        };
+        status.devices = PowerDevice::from_status(&status, "SIM_AC", "SIM_BAT");
+        status
+    }
+}
+
+/// 以当前速率到达指定电量所需的秒数，速率方向不对时返回 None
+fn time_to_reach(state: &SimulatedState, percentage: f32) -> Option<f64> {
+    let delta = percentage - state.battery_percentage;
+    if delta == 0.0 {
+        return Some(0.0);
+    }
+    if state.rate_pct_per_sec == 0.0 || delta.signum() != state.rate_pct_per_sec.signum() {
+        return None;
+    }
+    Some((delta / state.rate_pct_per_sec) as f64)
+}
+
+fn advance(state: &mut SimulatedState, secs: f64) {
+    if secs <= 0.0 {
+        return;
+    }
+    let percentage = state.battery_percentage + state.rate_pct_per_sec * secs as f32;
+    state.battery_percentage = percentage.clamp(0.0, 100.0);
+}
+
+fn apply_step(state: &mut SimulatedState, step: &ScenarioStep) {
+    if let Some(value) = step.is_ac_connected {
+        state.is_ac_connected = value;
+    }
+    if let Some(value) = step.is_charging {
+        state.is_charging = value;
+    }
+    if let Some(value) = step.is_battery_present {
+        state.is_battery_present = value;
+    }
+    if let Some(value) = step.battery_percentage {
+        state.battery_percentage = value.clamp(0.0, 100.0);
+    }
+    if let Some(value) = step.power_draw_watts {
+        state.power_draw_watts = Some(value);
+    }
+    if let Some(value) = step.rate_pct_per_sec {
+        state.rate_pct_per_sec = value;
+    }
+}
+
+/// 按时间线回放场景的电源后端，用于演示和确定性测试
+pub struct SimulatedPowerSource {
+    scenario: Scenario,
+    started: Instant,
+}
+
+impl SimulatedPowerSource {
+    /// 创建模拟后端，时间线从创建时刻开始
+    pub fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            started: Instant::now(),
+        }
+    }
+
+    /// 从场景文件创建模拟后端
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        Ok(Self::new(Scenario::from_file(path)?))
+    }
+
+    /// 场景描述
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(self.started)
+    }
+}
+
+impl PowerSource for SimulatedPowerSource {
+    fn name(&self) -> &'static str {
+        "simulated"
+    }
+
+    fn get_power_status(&self) -> Result<BatteryStatus, String> {
+        Ok(self.scenario.status_at(self.elapsed()))
+    }
+
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        Ok(self.get_power_status()?.devices)
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            power_draw: true,
+            remaining_time: true,
+            capacity: true,
+            device_enumeration: false,
+            health: false,
+            push_notifications: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::{BatteryLevel, PowerEvent, PowerMonitor};
+    use std::sync::Arc;
+
+    const UNPLUG_SCENARIO: &str = r#"
+name = "unplug"
+
+[initial]
+is_ac_connected = true
+battery_percentage = 80
+
+[[steps]]
+at_secs = 5
+is_ac_connected = false
+rate_pct_per_sec = -1.0
+
+[[steps]]
+at_percentage = 20
+rate_pct_per_sec = 0.0
+
+[[steps]]
+at_secs = 70
+is_ac_connected = true
+is_charging = true
+rate_pct_per_sec = 0.5
+"#;
+
+    #[test]
+    fn test_timeline_from_toml() {
+        let scenario = Scenario::from_toml(UNPLUG_SCENARIO).unwrap();
+
+        let status = scenario.status_at(Duration::from_secs(4));
+        assert!(status.is_ac_connected);
+        assert_eq!(status.battery_percentage, 80);
+
+        let status = scenario.status_at(Duration::from_secs(15));
+        assert!(!status.is_ac_connected);
+        assert_eq!(status.battery_percentage, 70);
+
+        // 5s 拔电 + 60s 放电到 20% 后停止变化，70s 插电并以 0.5%/s 充电
+        let status = scenario.status_at(Duration::from_secs(67));
+        assert!(!status.is_ac_connected);
+        assert_eq!(status.battery_percentage, 20);
+
+        let status = scenario.status_at(Duration::from_secs(90));
+        assert!(status.is_ac_connected);
+        assert!(status.is_charging);
+        assert_eq!(status.battery_percentage, 30);
+
+        let status = scenario.status_at(Duration::from_secs(10_000));
+        assert_eq!(status.battery_percentage, 100);
+    }
+
+    #[test]
+    fn test_timeline_from_json() {
+        let json = r#"{
+            "initial": { "is_ac_connected": false, "battery_percentage": 50, "rate_pct_per_sec": -2.0 },
+            "steps": [ { "at_percentage": 10, "rate_pct_per_sec": 0.0 } ]
+        }"#;
+        let scenario = Scenario::from_json(json).unwrap();
+
+        assert_eq!(scenario.status_at(Duration::from_secs(10)).battery_percentage, 30);
+        assert_eq!(scenario.status_at(Duration::from_secs(60)).battery_percentage, 10);
+    }
+
+    #[test]
+    fn test_unreachable_percentage_step_never_fires() {
+        let scenario = Scenario {
+            initial: SimulatedState { battery_percentage: 50.0, rate_pct_per_sec: 1.0, ..Default::default() },
+            steps: vec![ScenarioStep { at_percentage: Some(20), is_ac_connected: Some(false), ..Default::default() }],
+            ..Default::default()
+        };
+
+        let status = scenario.status_at(Duration::from_secs(100));
+        assert!(status.is_ac_connected);
+        assert_eq!(status.battery_percentage, 100);
+    }
+
+    #[test]
+    fn test_remaining_time_is_derived() {
+        let scenario = Scenario {
+            initial: SimulatedState {
+                is_ac_connected: false,
+                battery_percentage: 50.0,
+                power_draw_watts: Some(10.0),
+                battery_capacity_mwh: Some(40000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(scenario.status_at(Duration::ZERO).remaining_time_minutes, Some(120));
+    }
+
+    #[test]
+    fn test_invalid_steps_rejected() {
+        let both = "[[steps]]\nat_secs = 1\nat_percentage = 10\n";
+        assert!(Scenario::from_toml(both).is_err());
+
+        let neither = "[[steps]]\nis_ac_connected = false\n";
+        assert!(Scenario::from_toml(neither).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scenario_drives_monitor() {
+        let scenario = Scenario::from_toml(UNPLUG_SCENARIO).unwrap();
+        let source = Arc::new(SimulatedPowerSource::new(scenario));
+        let monitor = PowerMonitor::with_source(Box::new(source), 1, 20);
+
+        let mut rx = monitor.start_monitoring().await;
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            let event = rx.recv().await.unwrap();
+            if event.power_event != PowerEvent::StatusUpdate {
+                events.push(event.power_event);
+            }
+        }
+        monitor.stop_monitoring();
+
+        assert_eq!(events, vec![
+            PowerEvent::AcDisconnected,
+            PowerEvent::BatteryLow { level: BatteryLevel::low(20), percentage: 20 },
+            PowerEvent::AcConnected,
+        ]);
+    }
+}
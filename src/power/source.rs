@@ -0,0 +1,148 @@
+use crate::power::{BatteryHealth, BatteryStatus, ChangeReceiver, PowerDevice, StatusProvenance};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 电源后端能够提供的数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SourceCapabilities {
+    pub power_draw: bool,          // 能否报告实时功耗
+    pub remaining_time: bool,      // 能否报告剩余时间
+    pub capacity: bool,            // 能否报告电池容量
+    pub device_enumeration: bool,  // 能否区分多个电源设备
+    #[serde(default)]
+    pub health: bool,              // 能否报告电池健康信息
+    #[serde(default)]
+    pub push_notifications: bool,  // 能否推送电源变化通知（否则只能轮询）
+}
+
+/// 电源信息后端
+///
+/// 平台相关的读取逻辑都实现在各自的后端中，`PowerDetector` 与 `PowerMonitor`
+/// 只依赖该 trait，便于注入模拟数据源进行测试。
+pub trait PowerSource: Send + Sync {
+    /// 后端名称（用于日志）
+    fn name(&self) -> &'static str;
+
+    /// 获取当前汇总的电源状态
+    fn get_power_status(&self) -> Result<BatteryStatus, String>;
+
+    /// 列出后端识别到的电源设备
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String>;
+
+    /// 获取电池健康信息，没有电池或后端不支持时返回 `Ok(None)`
+    fn battery_health(&self) -> Result<Option<BatteryHealth>, String> {
+        Ok(None)
+    }
+
+    /// 订阅系统推送的电源变化通知，后端不支持或订阅失败时返回 `None`（只依靠轮询）
+    ///
+    /// 每次调用创建一个新的订阅，接收端被丢弃后订阅随之结束。
+    fn subscribe_changes(&self) -> Option<ChangeReceiver> {
+        None
+    }
+
+    /// 后端能力描述
+    fn capabilities(&self) -> SourceCapabilities;
+}
+
+impl<T: PowerSource + ?Sized> PowerSource for Arc<T> {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn get_power_status(&self) -> Result<BatteryStatus, String> {
+        (**self).get_power_status()
+    }
+
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        (**self).list_devices()
+    }
+
+    fn battery_health(&self) -> Result<Option<BatteryHealth>, String> {
+        (**self).battery_health()
+    }
+
+    fn subscribe_changes(&self) -> Option<ChangeReceiver> {
+        (**self).subscribe_changes()
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        (**self).capabilities()
+    }
+}
+
+/// 没有可用后端的平台：始终报告外接电源供电、无电池
+#[cfg_attr(any(target_os = "windows", target_os = "linux"), allow(dead_code))]
+#[derive(Debug, Clone, Default)]
+pub struct NullPowerSource;
+
+impl PowerSource for NullPowerSource {
+    fn name(&self) -> &'static str {
+        "null"
+    }
+
+    fn get_power_status(&self) -> Result<BatteryStatus, String> {
+        Ok(BatteryStatus {
+            is_charging: false,
+            is_ac_connected: true,
+            battery_percentage: 100,
+            is_battery_present: false,
+            power_draw_watts: None,
+            battery_capacity_mwh: None,
+            remaining_time_minutes: None,
+            charge_rate_watts: None,
+            devices: Vec::new(),
+            provenance: StatusProvenance::default(),
+            prediction: None,
+        })
+    }
+
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        Ok(Vec::new())
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities::default()
+    }
+}
+
+/// 根据当前平台创建默认的电源后端
+pub fn default_power_source() -> Arc<dyn PowerSource> {
+    #[cfg(target_os = "windows")]
+    {
+        Arc::new(crate::power::WindowsPowerSource::new())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Arc::new(crate::power::SysfsPowerSource::default())
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        Arc::new(NullPowerSource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_source_reports_ac_power() {
+        let source = NullPowerSource;
+        let status = source.get_power_status().unwrap();
+        assert!(status.is_ac_connected);
+        assert!(!status.is_battery_present);
+        assert!(source.list_devices().unwrap().is_empty());
+        assert_eq!(source.capabilities(), SourceCapabilities::default());
+    }
+
+    #[test]
+    fn test_shared_source_delegates() {
+        let source: Arc<dyn PowerSource> = Arc::new(NullPowerSource);
+        let boxed: Box<dyn PowerSource> = Box::new(Arc::clone(&source));
+        assert_eq!(boxed.name(), "null");
+        assert_eq!(boxed.get_power_status(), source.get_power_status());
+    }
+}
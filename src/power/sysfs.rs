@@ -0,0 +1,703 @@
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+use crate::power::{
+    BatteryHealth, BatteryStatus, DeviceKind, DeviceScope, DeviceState, PowerDevice, PowerSource, Provenance,
+    SourceCapabilities, StatusProvenance,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Linux 下 power_supply 设备的默认 sysfs 目录
+pub const DEFAULT_SYSFS_ROOT: &str = "/sys/class/power_supply";
+
+/// 基于 sysfs 的电源信息读取器（Linux）
+///
+/// 读取 `<root>/*/` 下的 type、online、status、capacity、energy_*、charge_*、
+/// power_now、current_now、voltage_now 等属性，汇总为一个 `BatteryStatus`；
+/// cycle_count、technology、manufacturer、serial_number 用于电池健康信息。
+/// 根目录可配置，便于测试时指向伪造的目录树。
+#[derive(Debug, Clone)]
+pub struct SysfsPowerSource {
+    root: PathBuf,
+}
+
+/// 单个 power_supply 节点的原始属性（单位与 sysfs 保持一致：µWh、µAh、µW、µA、µV）
+#[derive(Debug, Clone, Default)]
+struct SupplyInfo {
+    name: String,
+    supply_type: String,
+    scope: Option<String>,
+    model_name: Option<String>,
+    online: Option<bool>,
+    present: Option<bool>,
+    status: Option<String>,
+    capacity: Option<u8>,
+    energy_now: Option<u64>,
+    energy_full: Option<u64>,
+    energy_full_design: Option<u64>,
+    charge_now: Option<u64>,
+    charge_full: Option<u64>,
+    charge_full_design: Option<u64>,
+    power_now: Option<u64>,
+    current_now: Option<u64>,
+    voltage_now: Option<u64>,
+    voltage_min_design: Option<u64>,
+    cycle_count: Option<u64>,
+    technology: Option<String>,
+    manufacturer: Option<String>,
+    serial_number: Option<String>,
+}
+
+impl SupplyInfo {
+    fn is_battery(&self) -> bool {
+        self.supply_type.eq_ignore_ascii_case("Battery")
+    }
+
+    /// 是否为系统电池/电源（scope=Device 表示鼠标、键盘等外设）
+    fn is_system_scope(&self) -> bool {
+        !matches!(self.scope.as_deref(), Some(s) if s.eq_ignore_ascii_case("Device"))
+    }
+
+    /// 能量换算时使用的电压（µV）
+    fn reference_voltage(&self) -> Option<u64> {
+        self.voltage_min_design.or(self.voltage_now)
+    }
+
+    /// 将电荷量（µAh）换算为能量（µWh）
+    fn charge_to_energy(&self, charge_uah: Option<u64>) -> Option<u64> {
+        let voltage = self.reference_voltage()?;
+        Some((charge_uah? as u128 * voltage as u128 / 1_000_000) as u64)
+    }
+
+    fn energy_now_uwh(&self) -> Option<u64> {
+        self.energy_now.or_else(|| self.charge_to_energy(self.charge_now))
+    }
+
+    fn energy_full_uwh(&self) -> Option<u64> {
+        self.energy_full.or_else(|| self.charge_to_energy(self.charge_full))
+    }
+
+    fn energy_full_design_uwh(&self) -> Option<u64> {
+        self.energy_full_design.or_else(|| self.charge_to_energy(self.charge_full_design))
+    }
+
+    /// 当前功率（瓦特），优先使用 power_now，否则用 current_now × voltage_now 计算
+    fn power_watts(&self) -> Option<f32> {
+        if let Some(power_uw) = self.power_now {
+            return Some(power_uw as f32 / 1_000_000.0);
+        }
+        let current = self.current_now?;
+        let voltage = self.voltage_now?;
+        Some((current as f64 * voltage as f64 / 1e12) as f32)
+    }
+
+    fn status_is(&self, value: &str) -> bool {
+        matches!(self.status.as_deref(), Some(s) if s.eq_ignore_ascii_case(value))
+    }
+
+    /// 单个设备的电量百分比：优先使用 capacity，否则按能量计算
+    fn percentage(&self) -> Option<u8> {
+        self.capacity.or_else(|| {
+            let now = self.energy_now_uwh()?;
+            let full = self.energy_full_uwh().filter(|full| *full > 0)?;
+            Some(((now as f64 / full as f64) * 100.0).round().min(100.0) as u8)
+        })
+    }
+
+    /// 转换为通用的设备描述
+    fn to_device(&self) -> PowerDevice {
+        let scope = if self.is_system_scope() { DeviceScope::System } else { DeviceScope::Device };
+
+        let kind = if self.is_battery() {
+            match scope {
+                DeviceScope::System => DeviceKind::Battery,
+                DeviceScope::Device => {
+                    DeviceKind::guess_peripheral(self.model_name.as_deref().unwrap_or(&self.name))
+                }
+            }
+        } else if self.online.is_some() {
+            DeviceKind::AcAdapter
+        } else {
+            DeviceKind::Unknown
+        };
+
+        let is_online = if self.is_battery() {
+            self.present != Some(false)
+        } else {
+            self.online == Some(true)
+        };
+
+        PowerDevice {
+            id: self.name.clone(),
+            kind,
+            scope,
+            model: self.model_name.clone(),
+            percentage: if self.is_battery() { self.percentage() } else { None },
+            state: self.status.as_deref().map(DeviceState::parse).unwrap_or(DeviceState::Unknown),
+            is_online,
+        }
+    }
+}
+
+impl SysfsPowerSource {
+    /// 使用指定的 sysfs 根目录创建读取器
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// 获取当前使用的 sysfs 根目录
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 读取并汇总当前电源状态
+    pub fn read_status(&self) -> Result<BatteryStatus, String> {
+        let supplies = self.read_supplies()?;
+        Ok(Self::aggregate(&supplies))
+    }
+
+    /// 读取根目录下所有 power_supply 节点
+    fn read_supplies(&self) -> Result<Vec<SupplyInfo>, String> {
+        let entries = fs::read_dir(&self.root)
+            .map_err(|e| format!("Failed to read {}: {}", self.root.display(), e))?;
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        // 保证多电池时的顺序稳定（BAT0、BAT1...）
+        paths.sort();
+
+        Ok(paths.iter().filter_map(|path| Self::read_supply(path)).collect())
+    }
+
+    /// 读取单个 power_supply 节点，没有 type 属性的目录会被忽略
+    fn read_supply(dir: &Path) -> Option<SupplyInfo> {
+        let supply_type = read_attr(dir, "type")?;
+        let name = dir.file_name()?.to_string_lossy().to_string();
+
+        Some(SupplyInfo {
+            name,
+            supply_type,
+            scope: read_attr(dir, "scope"),
+            model_name: read_attr(dir, "model_name"),
+            online: read_flag(dir, "online"),
+            present: read_flag(dir, "present"),
+            status: read_attr(dir, "status"),
+            capacity: read_number(dir, "capacity").map(|v| v.min(100) as u8),
+            energy_now: read_number(dir, "energy_now"),
+            energy_full: read_number(dir, "energy_full"),
+            energy_full_design: read_number(dir, "energy_full_design"),
+            charge_now: read_number(dir, "charge_now"),
+            charge_full: read_number(dir, "charge_full"),
+            charge_full_design: read_number(dir, "charge_full_design"),
+            power_now: read_number(dir, "power_now"),
+            current_now: read_number(dir, "current_now"),
+            voltage_now: read_number(dir, "voltage_now"),
+            voltage_min_design: read_number(dir, "voltage_min_design"),
+            cycle_count: read_number(dir, "cycle_count"),
+            technology: read_attr(dir, "technology").filter(|t| t != "Unknown"),
+            manufacturer: read_attr(dir, "manufacturer"),
+            serial_number: read_attr(dir, "serial_number"),
+        })
+    }
+
+    /// 读取系统电池的健康信息
+    pub fn read_health(&self) -> Result<Option<BatteryHealth>, String> {
+        let supplies = self.read_supplies()?;
+        Ok(Self::aggregate_health(&supplies))
+    }
+
+    /// 汇总系统电池的健康信息：容量相加，循环次数取最大值，
+    /// 型号类字段取第一块电池的值
+    fn aggregate_health(supplies: &[SupplyInfo]) -> Option<BatteryHealth> {
+        let batteries: Vec<&SupplyInfo> = supplies
+            .iter()
+            .filter(|s| s.is_battery() && s.is_system_scope() && s.present != Some(false))
+            .collect();
+        if batteries.is_empty() {
+            return None;
+        }
+
+        let to_mwh = |uwh: Option<u64>| uwh.map(|v| (v / 1000) as u32);
+        let first = |field: fn(&SupplyInfo) -> Option<&String>| {
+            batteries.iter().find_map(|b| field(b)).cloned()
+        };
+
+        let health = BatteryHealth {
+            design_capacity_mwh: to_mwh(sum_all(batteries.iter().map(|b| b.energy_full_design_uwh()))),
+            full_charge_capacity_mwh: to_mwh(sum_all(batteries.iter().map(|b| b.energy_full_uwh()))),
+            // 部分驱动不支持时报告 0
+            cycle_count: batteries.iter().filter_map(|b| b.cycle_count).filter(|c| *c > 0).max().map(|c| c as u32),
+            wear_level_percent: None,
+            chemistry: first(|b| b.technology.as_ref()),
+            manufacturer: first(|b| b.manufacturer.as_ref()),
+            serial: first(|b| b.serial_number.as_ref()),
+        };
+
+        Some(health.with_wear_level())
+    }
+
+    /// 将多个 power_supply 节点汇总为系统电源状态
+    ///
+    /// 顶层字段只统计系统电池（多块电池按能量合并），外设电池仅出现在 `devices` 中。
+    fn aggregate(supplies: &[SupplyInfo]) -> BatteryStatus {
+        let devices: Vec<PowerDevice> = supplies.iter().map(SupplyInfo::to_device).collect();
+
+        let batteries: Vec<&SupplyInfo> = supplies
+            .iter()
+            .filter(|s| s.is_battery() && s.is_system_scope() && s.present != Some(false))
+            .collect();
+        let adapters: Vec<&SupplyInfo> = supplies
+            .iter()
+            .filter(|s| !s.is_battery() && s.is_system_scope())
+            .collect();
+
+        // 没有电池（台式机等）：视为外接电源供电
+        if batteries.is_empty() {
+            return BatteryStatus {
+                is_charging: false,
+                is_ac_connected: adapters.is_empty() || adapters.iter().any(|a| a.online == Some(true)),
+                battery_percentage: 100,
+                is_battery_present: false,
+                power_draw_watts: None,
+                battery_capacity_mwh: None,
+                remaining_time_minutes: None,
+                charge_rate_watts: None,
+                devices,
+                provenance: StatusProvenance::default(),
+                prediction: None,
+            };
+        }
+
+        let is_charging = batteries.iter().any(|b| b.status_is("Charging"));
+        let is_discharging = batteries.iter().any(|b| b.status_is("Discharging"));
+
+        // 优先使用适配器的 online 属性，没有适配器节点时根据电池状态推断
+        let is_ac_connected = if adapters.iter().any(|a| a.online.is_some()) {
+            adapters.iter().any(|a| a.online == Some(true))
+        } else {
+            !is_discharging
+        };
+
+        let energy_now = sum_all(batteries.iter().map(|b| b.energy_now_uwh()));
+        let energy_full = sum_all(batteries.iter().map(|b| b.energy_full_uwh()));
+        let energy_design = sum_all(batteries.iter().map(|b| b.energy_full_design_uwh()));
+
+        // 电量百分比：优先按总能量计算，否则取各电池 capacity 的平均值
+        let battery_percentage = match (energy_now, energy_full) {
+            (Some(now), Some(full)) if full > 0 => {
+                ((now as f64 / full as f64) * 100.0).round().min(100.0) as u8
+            }
+            _ => {
+                let capacities: Vec<u32> = batteries.iter().filter_map(|b| b.capacity.map(u32::from)).collect();
+                if capacities.is_empty() {
+                    100
+                } else {
+                    (capacities.iter().sum::<u32>() / capacities.len() as u32) as u8
+                }
+            }
+        };
+
+        let power_watts = {
+            let values: Vec<f32> = batteries.iter().filter_map(|b| b.power_watts()).collect();
+            if values.is_empty() { None } else { Some(values.iter().sum::<f32>()) }
+        };
+
+        // 放电时电池功率即系统功耗
+        let power_draw_watts = if is_discharging { power_watts } else { None };
+
+        let battery_capacity_mwh = energy_design.or(energy_full).map(|uwh| (uwh / 1000) as u32);
+
+        let remaining_time_minutes = match (is_discharging, energy_now, power_watts) {
+            (true, Some(now), Some(watts)) if watts > 0.0 => {
+                let remaining_wh = now as f64 / 1_000_000.0;
+                Some((remaining_wh / watts as f64 * 60.0) as u32)
+            }
+            _ => None,
+        };
+
+        // power_now、energy_* 为驱动直接报告；由电流×电压或电荷量换算得到的视为计算值
+        let power_provenance = if batteries.iter().all(|b| b.power_now.is_some()) {
+            Provenance::Measured
+        } else {
+            Provenance::Derived
+        };
+        let capacity_provenance = if batteries.iter().all(|b| b.energy_full_design.or(b.energy_full).is_some()) {
+            Provenance::Measured
+        } else {
+            Provenance::Derived
+        };
+
+        BatteryStatus {
+            is_charging,
+            is_ac_connected,
+            battery_percentage,
+            is_battery_present: true,
+            power_draw_watts,
+            battery_capacity_mwh,
+            remaining_time_minutes,
+            charge_rate_watts: power_watts,
+            devices,
+            provenance: StatusProvenance {
+                power_draw_watts: power_provenance.if_some(&power_draw_watts),
+                battery_capacity_mwh: capacity_provenance.if_some(&battery_capacity_mwh),
+                remaining_time_minutes: Provenance::Derived.if_some(&remaining_time_minutes),
+                charge_rate_watts: power_provenance.if_some(&power_watts),
+            },
+            prediction: None,
+        }
+    }
+}
+
+impl PowerSource for SysfsPowerSource {
+    fn name(&self) -> &'static str {
+        "sysfs"
+    }
+
+    fn get_power_status(&self) -> Result<BatteryStatus, String> {
+        self.read_status()
+    }
+
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        Ok(self.read_supplies()?.iter().map(SupplyInfo::to_device).collect())
+    }
+
+    fn battery_health(&self) -> Result<Option<BatteryHealth>, String> {
+        self.read_health()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn subscribe_changes(&self) -> Option<crate::power::ChangeReceiver> {
+        // 测试用的伪造目录没有对应的 uevent
+        if self.root != Path::new(DEFAULT_SYSFS_ROOT) {
+            return None;
+        }
+        match crate::power::watch_udev_power_supply() {
+            Ok(receiver) => Some(receiver),
+            Err(e) => {
+                crate::log_error!("{}, falling back to polling", e);
+                None
+            }
+        }
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            power_draw: true,
+            remaining_time: true,
+            capacity: true,
+            device_enumeration: true,
+            health: true,
+            push_notifications: cfg!(target_os = "linux"),
+        }
+    }
+}
+
+impl Default for SysfsPowerSource {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYSFS_ROOT)
+    }
+}
+
+/// 读取属性文件并去除首尾空白，文件不存在或为空时返回 None
+fn read_attr(dir: &Path, name: &str) -> Option<String> {
+    let value = fs::read_to_string(dir.join(name)).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// 读取数值属性；部分驱动放电时会报告负的 current_now/power_now，这里取绝对值
+fn read_number(dir: &Path, name: &str) -> Option<u64> {
+    read_attr(dir, name)?.parse::<i64>().ok().map(|v| v.unsigned_abs())
+}
+
+fn read_flag(dir: &Path, name: &str) -> Option<bool> {
+    read_number(dir, name).map(|v| v != 0)
+}
+
+/// 所有值都存在时求和，任意一个缺失则返回 None
+fn sum_all<I: Iterator<Item = Option<u64>>>(values: I) -> Option<u64> {
+    let mut total = 0u64;
+    let mut any = false;
+    for value in values {
+        total += value?;
+        any = true;
+    }
+    if any { Some(total) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在临时目录中构造 sysfs 目录树
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir()
+                .join(format!("isbattery-sysfs-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        fn supply(&self, name: &str, attrs: &[(&str, &str)]) -> &Self {
+            let dir = self.root.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            for (key, value) in attrs {
+                fs::write(dir.join(key), format!("{}\n", value)).unwrap();
+            }
+            self
+        }
+
+        fn source(&self) -> SysfsPowerSource {
+            SysfsPowerSource::new(&self.root)
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn test_discharging_energy_battery() {
+        let fixture = Fixture::new("discharging");
+        fixture
+            .supply("AC", &[("type", "Mains"), ("online", "0")])
+            .supply("BAT0", &[
+                ("type", "Battery"),
+                ("present", "1"),
+                ("status", "Discharging"),
+                ("capacity", "50"),
+                ("energy_now", "25000000"),
+                ("energy_full", "50000000"),
+                ("energy_full_design", "57000000"),
+                ("power_now", "12500000"),
+            ]);
+
+        let status = fixture.source().read_status().unwrap();
+        assert!(status.is_battery_present);
+        assert!(!status.is_ac_connected);
+        assert!(!status.is_charging);
+        assert_eq!(status.battery_percentage, 50);
+        assert_eq!(status.power_draw_watts, Some(12.5));
+        assert_eq!(status.charge_rate_watts, Some(12.5));
+        assert_eq!(status.battery_capacity_mwh, Some(57000));
+        assert_eq!(status.remaining_time_minutes, Some(120));
+        assert_eq!(status.provenance.power_draw_watts, Provenance::Measured);
+        assert_eq!(status.provenance.battery_capacity_mwh, Provenance::Measured);
+        assert_eq!(status.provenance.remaining_time_minutes, Provenance::Derived);
+    }
+
+    #[test]
+    fn test_charging_charge_based_battery() {
+        let fixture = Fixture::new("charging");
+        fixture
+            .supply("ADP1", &[("type", "Mains"), ("online", "1")])
+            .supply("BAT1", &[
+                ("type", "Battery"),
+                ("status", "Charging"),
+                ("capacity", "80"),
+                ("charge_now", "4000000"),
+                ("charge_full", "5000000"),
+                ("charge_full_design", "5000000"),
+                ("current_now", "-2000000"),
+                ("voltage_now", "10000000"),
+                ("voltage_min_design", "10000000"),
+            ]);
+
+        let status = fixture.source().read_status().unwrap();
+        assert!(status.is_ac_connected);
+        assert!(status.is_charging);
+        assert_eq!(status.battery_percentage, 80);
+        assert_eq!(status.power_draw_watts, None);
+        assert_eq!(status.charge_rate_watts, Some(20.0));
+        assert_eq!(status.battery_capacity_mwh, Some(50000));
+        assert_eq!(status.remaining_time_minutes, None);
+        assert_eq!(status.provenance.power_draw_watts, Provenance::Unknown);
+        assert_eq!(status.provenance.charge_rate_watts, Provenance::Derived);
+        assert_eq!(status.provenance.battery_capacity_mwh, Provenance::Derived);
+        assert!(!status.provenance.has_estimates());
+    }
+
+    #[test]
+    fn test_multiple_batteries_are_aggregated() {
+        let fixture = Fixture::new("dual");
+        fixture
+            .supply("AC", &[("type", "Mains"), ("online", "0")])
+            .supply("BAT0", &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("energy_now", "10000000"),
+                ("energy_full", "20000000"),
+                ("power_now", "5000000"),
+            ])
+            .supply("BAT1", &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("energy_now", "20000000"),
+                ("energy_full", "20000000"),
+                ("power_now", "5000000"),
+            ]);
+
+        let status = fixture.source().read_status().unwrap();
+        assert_eq!(status.battery_percentage, 75);
+        assert_eq!(status.power_draw_watts, Some(10.0));
+        assert_eq!(status.remaining_time_minutes, Some(180));
+    }
+
+    #[test]
+    fn test_peripheral_batteries_are_ignored() {
+        let fixture = Fixture::new("peripheral");
+        fixture
+            .supply("AC", &[("type", "Mains"), ("online", "1")])
+            .supply("hidpp_battery_0", &[
+                ("type", "Battery"),
+                ("scope", "Device"),
+                ("status", "Discharging"),
+                ("capacity", "10"),
+            ]);
+
+        let status = fixture.source().read_status().unwrap();
+        assert!(!status.is_battery_present);
+        assert!(status.is_ac_connected);
+        assert_eq!(status.battery_percentage, 100);
+
+        // 外设仍然出现在设备列表中
+        let mouse = status.devices.iter().find(|d| d.id == "hidpp_battery_0").unwrap();
+        assert!(mouse.is_peripheral());
+        assert_eq!(mouse.percentage, Some(10));
+    }
+
+    #[test]
+    fn test_ac_inferred_without_adapter_node() {
+        let fixture = Fixture::new("no-adapter");
+        fixture.supply("BAT0", &[("type", "Battery"), ("status", "Full"), ("capacity", "100")]);
+
+        let status = fixture.source().read_status().unwrap();
+        assert!(status.is_ac_connected);
+        assert!(!status.is_charging);
+        assert_eq!(status.battery_percentage, 100);
+    }
+
+    #[test]
+    fn test_list_devices() {
+        let fixture = Fixture::new("devices");
+        fixture
+            .supply("AC", &[("type", "Mains"), ("online", "1")])
+            .supply("BAT0", &[("type", "Battery"), ("present", "1"), ("capacity", "64")]);
+
+        let devices = fixture.source().list_devices().unwrap();
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].id, "AC");
+        assert_eq!(devices[0].kind, DeviceKind::AcAdapter);
+        assert!(devices[0].is_online);
+        assert_eq!(devices[1].id, "BAT0");
+        assert_eq!(devices[1].kind, DeviceKind::Battery);
+        assert_eq!(devices[1].percentage, Some(64));
+    }
+
+    #[test]
+    fn test_dual_batteries_and_peripherals() {
+        let fixture = Fixture::new("fleet");
+        fixture
+            .supply("AC", &[("type", "Mains"), ("online", "0")])
+            .supply("BAT0", &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("model_name", "01AV421"),
+                ("energy_now", "5000000"),
+                ("energy_full", "20000000"),
+            ])
+            .supply("BAT1", &[
+                ("type", "Battery"),
+                ("status", "Unknown"),
+                ("energy_now", "20000000"),
+                ("energy_full", "20000000"),
+            ])
+            .supply("hid-00:1f:20:aa:bb:cc-battery", &[
+                ("type", "Battery"),
+                ("scope", "Device"),
+                ("model_name", "Logitech Wireless Keyboard"),
+                ("status", "Discharging"),
+                ("capacity", "35"),
+            ])
+            .supply("hidpp_battery_1", &[
+                ("type", "Battery"),
+                ("scope", "Device"),
+                ("model_name", "MX Anywhere 3 Mouse"),
+                ("status", "Charging"),
+                ("capacity", "90"),
+            ]);
+
+        let status = fixture.source().read_status().unwrap();
+        // 汇总视图只包含两块系统电池
+        assert_eq!(status.battery_percentage, 63);
+        assert_eq!(status.devices.len(), 5);
+
+        let batteries: Vec<&PowerDevice> = status.devices.iter().filter(|d| d.is_system_battery()).collect();
+        assert_eq!(batteries.len(), 2);
+        assert_eq!(batteries[0].model.as_deref(), Some("01AV421"));
+        assert_eq!(batteries[0].percentage, Some(25));
+        assert_eq!(batteries[0].state, DeviceState::Discharging);
+        assert_eq!(batteries[1].percentage, Some(100));
+
+        let keyboard = status.devices.iter().find(|d| d.kind == DeviceKind::Keyboard).unwrap();
+        assert!(keyboard.is_peripheral());
+        assert_eq!(keyboard.percentage, Some(35));
+
+        let mouse = status.devices.iter().find(|d| d.kind == DeviceKind::Mouse).unwrap();
+        assert_eq!(mouse.state, DeviceState::Charging);
+        assert_eq!(mouse.percentage, Some(90));
+    }
+
+    #[test]
+    fn test_battery_health() {
+        let fixture = Fixture::new("health");
+        fixture
+            .supply("AC", &[("type", "Mains"), ("online", "1")])
+            .supply("BAT0", &[
+                ("type", "Battery"),
+                ("status", "Full"),
+                ("technology", "Li-poly"),
+                ("manufacturer", "SMP"),
+                ("serial_number", "  1234"),
+                ("cycle_count", "312"),
+                ("energy_full", "45600000"),
+                ("energy_full_design", "57000000"),
+            ])
+            .supply("hidpp_battery_0", &[("type", "Battery"), ("scope", "Device"), ("capacity", "50")]);
+
+        let health = fixture.source().battery_health().unwrap().unwrap();
+        assert_eq!(health.design_capacity_mwh, Some(57000));
+        assert_eq!(health.full_charge_capacity_mwh, Some(45600));
+        assert_eq!(health.wear_level_percent, Some(20.0));
+        assert_eq!(health.cycle_count, Some(312));
+        assert_eq!(health.chemistry.as_deref(), Some("Li-poly"));
+        assert_eq!(health.manufacturer.as_deref(), Some("SMP"));
+        assert_eq!(health.serial.as_deref(), Some("1234"));
+    }
+
+    #[test]
+    fn test_battery_health_without_battery() {
+        let fixture = Fixture::new("health-desktop");
+        fixture.supply("AC", &[("type", "Mains"), ("online", "1")]);
+
+        assert_eq!(fixture.source().battery_health().unwrap(), None);
+    }
+
+    #[test]
+    fn test_missing_root_is_error() {
+        let source = SysfsPowerSource::new("/nonexistent/isbattery/power_supply");
+        assert!(source.read_status().is_err());
+    }
+}
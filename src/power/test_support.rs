@@ -0,0 +1,33 @@
+// 测试共用的构造函数：固定日期的本地时间、常用的电池状态和监控事件
+
+use crate::power::{BatteryStatus, MonitorEvent, PowerEvent};
+use chrono::{DateTime, Local, TimeZone};
+
+/// 2025 年 9 月 `day` 日的本地时间
+pub fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, 9, day, hour, minute, 0).unwrap()
+}
+
+/// 有电池的电源状态，接通电源且未充满时视为正在充电
+pub fn status(is_ac_connected: bool, battery_percentage: u8) -> BatteryStatus {
+    status_with_charging(is_ac_connected, is_ac_connected && battery_percentage < 100, battery_percentage)
+}
+
+/// 有电池的电源状态，单独指定是否正在充电（如接通电源但未充电）
+pub fn status_with_charging(is_ac_connected: bool, is_charging: bool, battery_percentage: u8) -> BatteryStatus {
+    BatteryStatus {
+        is_charging,
+        is_ac_connected,
+        battery_percentage,
+        is_battery_present: true,
+        ..Default::default()
+    }
+}
+
+/// 监控循环发布的事件，`current_status` 为 `status` 构造的状态
+pub fn event(power_event: PowerEvent, is_ac_connected: bool, battery_percentage: u8) -> MonitorEvent {
+    MonitorEvent {
+        power_event,
+        current_status: status(is_ac_connected, battery_percentage),
+    }
+}
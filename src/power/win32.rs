@@ -0,0 +1,455 @@
+use crate::power::wmi::{parse_wmi_list, BatterySummary, PortableBattery, Win32Battery, WmiBatteryHealth};
+use crate::power::{
+    BatteryHealth, BatteryStatus, ChangeReceiver, PowerDevice, PowerSource, Provenance, SourceCapabilities, StatusProvenance,
+};
+
+/// WMI 查询或估算得到的高级电池信息，附带各字段的来源
+#[derive(Debug, Clone, Default)]
+struct AdvancedBatteryInfo {
+    power_draw_watts: Option<f32>,
+    battery_capacity_mwh: Option<u32>,
+    remaining_time_minutes: Option<u32>,
+    charge_rate_watts: Option<f32>,
+    provenance: StatusProvenance,
+}
+
+/// Windows 电源后端：GetSystemPowerStatus + WMI 查询
+#[derive(Debug, Clone, Default)]
+pub struct WindowsPowerSource;
+
+impl WindowsPowerSource {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 获取高级电池信息（功率、容量等）
+    fn get_advanced_battery_info(&self) -> AdvancedBatteryInfo {
+        crate::log_info!("获取高级电池信息...");
+        
+        // 使用WMI获取详细的电池信息
+        match self.query_wmi_battery_info() {
+            Ok(info) => {
+                crate::log_info!("WMI查询成功 - 功耗: {:?}W, 容量: {:?}mWh, 剩余: {:?}分钟, 来源: {:?}", 
+                               info.power_draw_watts, info.battery_capacity_mwh, info.remaining_time_minutes, info.provenance);
+                info
+            }
+            Err(e) => {
+                crate::log_error!("WMI查询失败: {}, 使用估算方法", e);
+                // 如果WMI查询失败，尝试使用简单的计算方法
+                let info = self.estimate_power_info();
+                if let (Some(power), Some(cap), Some(time)) = (info.power_draw_watts, info.battery_capacity_mwh, info.remaining_time_minutes) {
+                    crate::log_info!("估算结果 - 功耗: {:.1}W, 容量: {}mWh, 剩余: {}分钟", 
+                                   power, cap, time);
+                }
+                info
+            }
+        }
+    }
+
+    /// 通过WMI查询电池信息
+    fn query_wmi_battery_info(&self) -> Result<AdvancedBatteryInfo, String> {
+        use std::process::{Command, Stdio};
+        use std::os::windows::process::CommandExt;
+        
+        crate::log_info!("开始WMI电池信息查询...");
+        
+        // 尝试多个WMI查询获取更准确的数据
+        let queries = [
+            // 查询1: 基础电池信息
+            "Get-WmiObject -Class Win32_Battery | Select-Object DeviceID,EstimatedChargeRemaining,DesignCapacity,EstimatedRunTime,DischargeRate | ConvertTo-Json",
+            // 查询2: 更详细的电池状态
+            "Get-WmiObject -Class Win32_PortableBattery | Select-Object DesignCapacity,MaxRechargeTime,EstimatedRunTime,Chemistry | ConvertTo-Json",
+            // 查询3: 系统电源设置
+            "powercfg /batteryreport /output temp_battery_report.xml 2>$null; if($?){Get-Content temp_battery_report.xml -Raw; Remove-Item temp_battery_report.xml -Force 2>$null}"
+        ];
+        
+        // 先尝试基础查询
+        let output = Command::new("powershell")
+            .args(&[
+                "-WindowStyle", "Hidden",  // 隐藏窗口
+                "-NoProfile",              // 不加载配置文件
+                "-NonInteractive",         // 非交互模式
+                "-ExecutionPolicy", "Bypass", // 绕过执行策略
+                "-Command",
+                queries[0]
+            ])
+            .stdin(Stdio::null())       // 不需要输入
+            .stdout(Stdio::piped())     // 捕获输出
+            .stderr(Stdio::piped())     // 捕获错误输出以便调试
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW flag for Windows
+            .output()
+            .map_err(|e| {
+                crate::log_error!("PowerShell执行失败: {}", e);
+                format!("Failed to execute PowerShell: {}", e)
+            })?;
+
+        if !output.status.success() {
+            crate::log_error!("PowerShell命令执行失败，状态码: {:?}", output.status.code());
+            return Err("PowerShell command failed".to_string());
+        }
+
+        let json_str = String::from_utf8(output.stdout)
+            .map_err(|e| {
+                crate::log_error!("输出解析失败: {}", e);
+                format!("Failed to parse output: {}", e)
+            })?;
+        
+        crate::log_info!("WMI输出: {}", json_str.trim());
+
+        // 解析JSON输出
+        let mut info = self.parse_battery_json(&json_str)?;
+        
+        // 如果基础查询返回的功耗为默认值，尝试获取真实功耗
+        if info.provenance.power_draw_watts.is_estimated() {
+            if let Ok((real_power, provenance)) = self.get_real_power_consumption() {
+                crate::log_info!("获取到真实功耗: {:.1}W ({})", real_power, provenance.label());
+                info.power_draw_watts = Some(real_power);
+                info.provenance.power_draw_watts = provenance;
+            }
+        }
+        
+        Ok(info)
+    }
+
+    /// 解析电池JSON数据（Win32_Battery，可能包含多块电池）
+    fn parse_battery_json(&self, json_str: &str) -> Result<AdvancedBatteryInfo, String> {
+        crate::log_info!("开始解析JSON数据...");
+
+        let batteries: Vec<Win32Battery> = parse_wmi_list(json_str)?;
+        if batteries.is_empty() {
+            return Err("No Win32_Battery instance found".to_string());
+        }
+        let summary = BatterySummary::from_batteries(&batteries);
+
+        let mut provenance = StatusProvenance::all(Provenance::Measured);
+
+        let capacity = match summary.design_capacity_mwh.or_else(|| self.query_portable_design_capacity()) {
+            Some(capacity) => capacity,
+            None => {
+                provenance.battery_capacity_mwh = Provenance::Estimated;
+                50000 // 默认值50Wh
+            }
+        };
+        
+        let discharge_rate = match summary.discharge_rate_mw {
+            Some(rate) => rate as f64,
+            None => {
+                provenance.charge_rate_watts = Provenance::Estimated;
+                15000.0 // 默认放电速率15000mW (15W)
+            }
+        };
+        
+        // Windows 根据放电速率计算剩余时间
+        provenance.remaining_time_minutes = Provenance::Derived;
+        let estimated_runtime = match summary.run_time_minutes {
+            // 检查值是否合理，如果超过1440分钟（24小时），则使用默认值
+            Some(v) if v <= 1440 => v,
+            _ => {
+                provenance.remaining_time_minutes = Provenance::Estimated;
+                240 // 默认剩余4小时
+            }
+        };
+
+        crate::log_info!("解析结果 - 电池数: {}, 容量: {}mWh, 放电率: {:.1}mW, 剩余时间: {}分钟", 
+                        batteries.len(), capacity, discharge_rate, estimated_runtime);
+
+        // 计算当前功耗
+        provenance.power_draw_watts = provenance.charge_rate_watts;
+        let power_draw = if discharge_rate > 0.0 {
+            let watts = (discharge_rate / 1000.0) as f32; // 转换为瓦特
+            if watts < 0.1 { // 如果太小，使用默认值
+                provenance.power_draw_watts = Provenance::Estimated;
+                15.0
+            } else {
+                watts
+            }
+        } else {
+            // 如果没有放电率数据，估算一个值
+            provenance.power_draw_watts = Provenance::Estimated;
+            15.0 // 默认估算15W
+        };
+
+        crate::log_info!("计算功耗: {:.1}W", power_draw);
+        Ok(AdvancedBatteryInfo {
+            power_draw_watts: Some(power_draw),
+            battery_capacity_mwh: Some(capacity),
+            remaining_time_minutes: Some(estimated_runtime),
+            charge_rate_watts: Some((discharge_rate / 1000.0) as f32),
+            provenance,
+        })
+    }
+
+    /// Win32_Battery 经常不提供 DesignCapacity，此时从 Win32_PortableBattery 读取
+    fn query_portable_design_capacity(&self) -> Option<u32> {
+        let script = "Get-WmiObject -Class Win32_PortableBattery | Select-Object DeviceID,DesignCapacity,DesignVoltage,Chemistry,Manufacturer | ConvertTo-Json";
+        let output = self.run_powershell(script).ok()?;
+        let batteries: Vec<PortableBattery> = parse_wmi_list(&output).ok()?;
+        let capacities: Vec<u32> = batteries.iter().filter_map(|b| b.design_capacity).collect();
+        if capacities.is_empty() { None } else { Some(capacities.iter().sum()) }
+    }
+
+    /// 估算电源信息（备用方法）
+    fn estimate_power_info(&self) -> AdvancedBatteryInfo {
+        // 获取当前电池状态
+        if let Ok(status) = self.get_basic_power_status() {
+            let estimated_power = if status.is_charging {
+                // 充电时估算功耗
+                match status.battery_percentage {
+                    0..=20 => 25.0,   // 低电量快充
+                    21..=80 => 20.0,  // 正常充电
+                    _ => 10.0,        // 慢充模式
+                }
+            } else {
+                // 放电时估算功耗（基于典型笔记本功耗）
+                15.0 // 典型笔记本在正常使用下的功耗
+            };
+
+            let estimated_capacity = 50000u32; // 50Wh 典型笔记本电池容量
+            
+            // 估算剩余时间
+            let remaining_time = if !status.is_charging && status.battery_percentage > 0 {
+                let remaining_capacity = (estimated_capacity as f32) * (status.battery_percentage as f32) / 100.0;
+                ((remaining_capacity / estimated_power) * 60.0) as u32 // 转换为分钟
+            } else {
+                0u32
+            };
+
+            AdvancedBatteryInfo {
+                power_draw_watts: Some(estimated_power),
+                battery_capacity_mwh: Some(estimated_capacity),
+                remaining_time_minutes: Some(remaining_time),
+                charge_rate_watts: Some(estimated_power),
+                provenance: StatusProvenance::all(Provenance::Estimated),
+            }
+        } else {
+            AdvancedBatteryInfo::default()
+        }
+    }
+
+    /// 获取真实的系统功耗（通过性能计数器）
+    ///
+    /// 只有第一条命令（电池放电率计数器）的结果是实测值，其余命令得到的只是近似值。
+    fn get_real_power_consumption(&self) -> Result<(f32, Provenance), String> {
+        use std::process::{Command, Stdio};
+        use std::os::windows::process::CommandExt;
+        
+        crate::log_info!("尝试获取真实系统功耗...");
+        
+        // 使用Windows性能计数器获取功耗信息
+        let commands = [
+            // 命令1: 获取电池放电率
+            "(Get-Counter '\\Battery(*)\\Battery Discharge Rate' -ErrorAction SilentlyContinue).CounterSamples.CookedValue",
+            // 命令2: 获取处理器功耗
+            "(Get-Counter '\\Processor(_Total)\\% Processor Time' -ErrorAction SilentlyContinue).CounterSamples.CookedValue",
+            // 命令3: 通过powercfg获取电池信息
+            "powercfg /energy /output temp_energy.html /duration 5 2>$null; if($?){Select-String -Path temp_energy.html -Pattern 'Battery.*[0-9]+.*W' | Select-Object -First 1; Remove-Item temp_energy.html -Force 2>$null}"
+        ];
+        
+        for (i, cmd) in commands.iter().enumerate() {
+            crate::log_info!("执行功耗检测命令 {}: {}", i+1, cmd);
+            
+            let output = Command::new("powershell")
+                .args(&[
+                    "-WindowStyle", "Hidden",
+                    "-NoProfile",
+                    "-NonInteractive",
+                    "-ExecutionPolicy", "Bypass",
+                    "-Command",
+                    cmd
+                ])
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .creation_flags(0x08000000)
+                .output()
+                .map_err(|e| format!("PowerShell执行失败: {}", e))?;
+            
+            if output.status.success() {
+                let output_str = String::from_utf8_lossy(&output.stdout);
+                crate::log_info!("命令 {} 输出: {}", i+1, output_str.trim());
+                
+                // 尝试解析数值
+                if let Some(power) = self.extract_power_from_output(&output_str) {
+                    if power > 0.0 && power < 200.0 { // 合理范围内的功耗值
+                        crate::log_info!("从命令 {} 获取到功耗: {:.1}W", i+1, power);
+                        let provenance = if i == 0 { Provenance::Measured } else { Provenance::Estimated };
+                        return Ok((power, provenance));
+                    }
+                }
+            }
+        }
+        
+        Err("无法获取真实功耗数据".to_string())
+    }
+    
+    /// 从命令输出中提取功耗数值
+    fn extract_power_from_output(&self, output: &str) -> Option<f32> {
+        // 查找数字模式
+        for line in output.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            
+            // 尝试解析为数字
+            if let Ok(value) = line.parse::<f64>() {
+                if value > 0.0 && value < 200.0 {
+                    return Some(value as f32);
+                }
+            }
+            
+            // 查找包含"W"的行
+            if line.contains('W') || line.contains("watt") {
+                for word in line.split_whitespace() {
+                    let clean_word = word.trim_matches(|c: char| !c.is_numeric() && c != '.');
+                    if let Ok(value) = clean_word.parse::<f64>() {
+                        if value > 0.0 && value < 200.0 {
+                            return Some(value as f32);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// 通过 WMI（root\wmi 命名空间）查询电池健康信息
+    fn query_battery_health(&self) -> Result<Option<BatteryHealth>, String> {
+        crate::log_info!("开始查询电池健康信息...");
+
+        // BatteryStaticData 提供设计容量和厂商信息，满充容量和循环次数分别在单独的类中
+        let script = "$s = Get-CimInstance -Namespace root\\wmi -ClassName BatteryStaticData -ErrorAction SilentlyContinue | Select-Object -First 1; \
+            $f = Get-CimInstance -Namespace root\\wmi -ClassName BatteryFullChargedCapacity -ErrorAction SilentlyContinue | Select-Object -First 1; \
+            $c = Get-CimInstance -Namespace root\\wmi -ClassName BatteryCycleCount -ErrorAction SilentlyContinue | Select-Object -First 1; \
+            if ($s -or $f) { [PSCustomObject]@{ DesignedCapacity = $s.DesignedCapacity; FullChargedCapacity = $f.FullChargedCapacity; \
+            CycleCount = $c.CycleCount; Chemistry = $s.Chemistry; ManufactureName = $s.ManufactureName; SerialNumber = $s.SerialNumber } | ConvertTo-Json }";
+
+        let output = self.run_powershell(script)?;
+        crate::log_info!("电池健康WMI输出: {}", output.trim());
+
+        let records: Vec<WmiBatteryHealth> = parse_wmi_list(&output)?;
+        Ok(records.into_iter().next().map(WmiBatteryHealth::into_health))
+    }
+
+    /// 在隐藏窗口中执行 PowerShell 脚本并返回标准输出
+    fn run_powershell(&self, script: &str) -> Result<String, String> {
+        use std::process::{Command, Stdio};
+        use std::os::windows::process::CommandExt;
+
+        let output = Command::new("powershell")
+            .args(&[
+                "-WindowStyle", "Hidden",
+                "-NoProfile",
+                "-NonInteractive",
+                "-ExecutionPolicy", "Bypass",
+                "-Command",
+                script
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .creation_flags(0x08000000)
+            .output()
+            .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("PowerShell command failed: {:?}", output.status.code()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// 获取基础电源状态（不包含高级信息）
+    fn get_basic_power_status(&self) -> Result<BatteryStatus, String> {
+        use windows::Win32::System::Power::{
+            GetSystemPowerStatus, SYSTEM_POWER_STATUS
+        };
+
+        unsafe {
+            let mut status = SYSTEM_POWER_STATUS::default();
+            if GetSystemPowerStatus(&mut status).is_err() {
+                return Err("Failed to get system power status".to_string());
+            }
+            
+            let is_ac_connected = status.ACLineStatus == 1;
+            let is_charging = status.BatteryFlag & 8 != 0;
+            let is_battery_present = status.BatteryFlag != 128;
+            
+            let battery_percentage = if status.BatteryLifePercent == 255 {
+                100
+            } else {
+                status.BatteryLifePercent as u8
+            };
+
+            Ok(BatteryStatus {
+                is_charging,
+                is_ac_connected,
+                battery_percentage,
+                is_battery_present,
+                power_draw_watts: None,
+                battery_capacity_mwh: None,
+                remaining_time_minutes: None,
+                charge_rate_watts: None,
+                devices: Vec::new(),
+                provenance: StatusProvenance::default(),
+                prediction: None,
+            })
+        }
+    }
+}
+
+impl PowerSource for WindowsPowerSource {
+    fn name(&self) -> &'static str {
+        "windows"
+    }
+
+    fn get_power_status(&self) -> Result<BatteryStatus, String> {
+        let mut status = self.get_basic_power_status()?;
+
+        // 获取详细的电池信息
+        let info = self.get_advanced_battery_info();
+
+        status.power_draw_watts = info.power_draw_watts;
+        status.battery_capacity_mwh = info.battery_capacity_mwh;
+        status.remaining_time_minutes = info.remaining_time_minutes;
+        status.charge_rate_watts = info.charge_rate_watts;
+        status.provenance = info.provenance;
+        status.devices = PowerDevice::from_status(&status, "AC", "BAT");
+
+        Ok(status)
+    }
+
+    fn list_devices(&self) -> Result<Vec<PowerDevice>, String> {
+        // GetSystemPowerStatus 只提供一个汇总电池和AC状态
+        let status = self.get_basic_power_status()?;
+        Ok(PowerDevice::from_status(&status, "AC", "BAT"))
+    }
+
+    fn battery_health(&self) -> Result<Option<BatteryHealth>, String> {
+        if !self.get_basic_power_status()?.is_battery_present {
+            return Ok(None);
+        }
+        self.query_battery_health()
+    }
+
+    fn subscribe_changes(&self) -> Option<ChangeReceiver> {
+        match crate::power::watch_power_broadcasts() {
+            Ok(receiver) => Some(receiver),
+            Err(e) => {
+                crate::log_error!("{}, falling back to polling", e);
+                None
+            }
+        }
+    }
+
+    fn capabilities(&self) -> SourceCapabilities {
+        SourceCapabilities {
+            power_draw: true,
+            remaining_time: true,
+            capacity: true,
+            device_enumeration: false,
+            health: true,
+            push_notifications: true,
+        }
+    }
+}
@@ -0,0 +1,285 @@
+// WMI / PowerShell 输出解析
+//
+// 只负责把 `ConvertTo-Json` 的输出解析为强类型结构，不依赖 Windows API，
+// 因此可以在任意平台上用抓取的输出做单元测试。
+
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+use crate::power::BatteryHealth;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Win32_Battery.EstimatedRunTime 在接通电源或无法估算时返回的值
+pub const WMI_UNKNOWN_RUN_TIME: u32 = 71_582_788;
+
+/// `Win32_Battery` 中使用到的字段
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Win32Battery {
+    #[serde(rename = "DeviceID")]
+    pub device_id: Option<String>,
+    pub name: Option<String>,
+    pub estimated_charge_remaining: Option<u16>,  // 电量百分比
+    pub design_capacity: Option<u32>,             // 设计容量（毫瓦时）
+    pub full_charge_capacity: Option<u32>,        // 满充容量（毫瓦时）
+    pub estimated_run_time: Option<u32>,          // 剩余时间（分钟）
+    pub discharge_rate: Option<i64>,              // 放电速率（毫瓦），部分驱动会报告负值
+    pub battery_status: Option<u16>,
+}
+
+impl Win32Battery {
+    /// 剩余时间（分钟），过滤掉表示“未知”的特殊值
+    pub fn run_time_minutes(&self) -> Option<u32> {
+        self.estimated_run_time.filter(|t| *t != WMI_UNKNOWN_RUN_TIME)
+    }
+
+    /// 放电速率（毫瓦），取绝对值，0 视为未知
+    pub fn discharge_rate_mw(&self) -> Option<u32> {
+        self.discharge_rate
+            .map(|rate| rate.unsigned_abs() as u32)
+            .filter(|rate| *rate > 0)
+    }
+}
+
+/// `Win32_PortableBattery` 中使用到的字段
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PortableBattery {
+    #[serde(rename = "DeviceID")]
+    pub device_id: Option<String>,
+    pub name: Option<String>,
+    pub manufacturer: Option<String>,
+    pub design_capacity: Option<u32>,    // 设计容量（毫瓦时）
+    pub design_voltage: Option<u32>,     // 设计电压（毫伏）
+    pub max_recharge_time: Option<u32>,
+    pub chemistry: Option<u16>,          // CIM 化学类型编码
+}
+
+impl PortableBattery {
+    /// CIM_Battery.Chemistry 编码对应的名称
+    pub fn chemistry_name(&self) -> Option<&'static str> {
+        match self.chemistry? {
+            3 => Some("Lead Acid"),
+            4 => Some("NiCd"),
+            5 => Some("NiMH"),
+            6 => Some("Li-ion"),
+            7 => Some("Zinc air"),
+            8 => Some("Li-poly"),
+            _ => None,
+        }
+    }
+}
+
+/// root\wmi 命名空间下 BatteryStaticData / BatteryFullChargedCapacity / BatteryCycleCount
+/// 合并后的输出
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct WmiBatteryHealth {
+    pub designed_capacity: Option<u32>,
+    pub full_charged_capacity: Option<u32>,
+    pub cycle_count: Option<u32>,
+    pub chemistry: Option<u32>,  // 按小端序打包的 4 个 ASCII 字符（如 "LION"）
+    pub manufacture_name: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+impl WmiBatteryHealth {
+    /// 转换为通用的电池健康信息，0 和空字符串视为未知
+    pub fn into_health(self) -> BatteryHealth {
+        BatteryHealth {
+            design_capacity_mwh: self.designed_capacity.filter(|c| *c > 0),
+            full_charge_capacity_mwh: self.full_charged_capacity.filter(|c| *c > 0),
+            cycle_count: self.cycle_count.filter(|c| *c > 0),
+            wear_level_percent: None,
+            chemistry: self.chemistry.and_then(decode_chemistry),
+            manufacturer: non_empty(self.manufacture_name),
+            serial: non_empty(self.serial_number),
+        }
+        .with_wear_level()
+    }
+}
+
+/// 多块电池汇总后的 Win32_Battery 数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatterySummary {
+    pub design_capacity_mwh: Option<u32>,
+    pub discharge_rate_mw: Option<u32>,
+    pub run_time_minutes: Option<u32>,
+    pub charge_percentage: Option<u8>,
+}
+
+impl BatterySummary {
+    /// 容量和放电速率相加，剩余时间取最大值（Windows 已按整机计算），电量取平均值
+    pub fn from_batteries(batteries: &[Win32Battery]) -> Self {
+        let sum = |values: Vec<u32>| if values.is_empty() { None } else { Some(values.iter().sum()) };
+
+        let percentages: Vec<u32> = batteries
+            .iter()
+            .filter_map(|b| b.estimated_charge_remaining.map(u32::from))
+            .collect();
+
+        Self {
+            design_capacity_mwh: sum(batteries.iter().filter_map(|b| b.design_capacity).collect()),
+            discharge_rate_mw: sum(batteries.iter().filter_map(|b| b.discharge_rate_mw()).collect()),
+            run_time_minutes: batteries.iter().filter_map(|b| b.run_time_minutes()).max(),
+            charge_percentage: if percentages.is_empty() {
+                None
+            } else {
+                Some((percentages.iter().sum::<u32>() / percentages.len() as u32).min(100) as u8)
+            },
+        }
+    }
+}
+
+/// `ConvertTo-Json` 对单个对象输出 `{...}`，对多个对象输出 `[...]`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    Many(Vec<T>),
+    One(T),
+}
+
+/// 解析 `ConvertTo-Json` 的输出，统一返回列表；没有任何实例时输出为空
+pub fn parse_wmi_list<T: DeserializeOwned>(output: &str) -> Result<Vec<T>, String> {
+    let output = output.trim().trim_start_matches('\u{feff}');
+    if output.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match serde_json::from_str::<OneOrMany<T>>(output) {
+        Ok(OneOrMany::Many(items)) => Ok(items),
+        Ok(OneOrMany::One(item)) => Ok(vec![item]),
+        Err(e) => Err(format!("Failed to parse WMI output: {}", e)),
+    }
+}
+
+/// 解码 BatteryStaticData.Chemistry
+pub fn decode_chemistry(code: u32) -> Option<String> {
+    let text: String = code
+        .to_le_bytes()
+        .iter()
+        .filter(|b| b.is_ascii_alphanumeric())
+        .map(|b| *b as char)
+        .collect();
+    if text.is_empty() { None } else { Some(text) }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 以下输出与 PowerShell 5.1 中 ConvertTo-Json 的格式一致（缩进、null、BOM）
+
+    const SINGLE_BATTERY: &str = r#"{
+    "EstimatedChargeRemaining":  87,
+    "DesignCapacity":  null,
+    "EstimatedRunTime":  212,
+    "DischargeRate":  null
+}
+"#;
+
+    const DUAL_BATTERY: &str = r#"[
+    {
+        "DeviceID":  "1234SMPLGC01",
+        "EstimatedChargeRemaining":  40,
+        "DesignCapacity":  24000,
+        "EstimatedRunTime":  71582788,
+        "DischargeRate":  -6200
+    },
+    {
+        "DeviceID":  "5678SMPLGC02",
+        "EstimatedChargeRemaining":  60,
+        "DesignCapacity":  48000,
+        "EstimatedRunTime":  185,
+        "DischargeRate":  -4800
+    }
+]
+"#;
+
+    const PORTABLE_BATTERY: &str = r#"{
+    "DesignCapacity":  57000,
+    "MaxRechargeTime":  null,
+    "EstimatedRunTime":  null,
+    "Chemistry":  6,
+    "Manufacturer":  "SMP"
+}"#;
+
+    const HEALTH: &str = "\u{feff}{
+    \"DesignedCapacity\":  57000,
+    \"FullChargedCapacity\":  45600,
+    \"CycleCount\":  0,
+    \"Chemistry\":  1313818956,
+    \"ManufactureName\":  \"SMP \",
+    \"SerialNumber\":  \"\"
+}";
+
+    #[test]
+    fn test_parse_single_object() {
+        let batteries: Vec<Win32Battery> = parse_wmi_list(SINGLE_BATTERY).unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].estimated_charge_remaining, Some(87));
+        assert_eq!(batteries[0].design_capacity, None);
+        assert_eq!(batteries[0].run_time_minutes(), Some(212));
+        assert_eq!(batteries[0].discharge_rate_mw(), None);
+    }
+
+    #[test]
+    fn test_parse_array() {
+        let batteries: Vec<Win32Battery> = parse_wmi_list(DUAL_BATTERY).unwrap();
+        assert_eq!(batteries.len(), 2);
+        assert_eq!(batteries[0].device_id.as_deref(), Some("1234SMPLGC01"));
+        assert_eq!(batteries[0].run_time_minutes(), None);
+        assert_eq!(batteries[1].discharge_rate_mw(), Some(4800));
+
+        let summary = BatterySummary::from_batteries(&batteries);
+        assert_eq!(summary.design_capacity_mwh, Some(72000));
+        assert_eq!(summary.discharge_rate_mw, Some(11000));
+        assert_eq!(summary.run_time_minutes, Some(185));
+        assert_eq!(summary.charge_percentage, Some(50));
+    }
+
+    #[test]
+    fn test_parse_empty_output() {
+        let batteries: Vec<Win32Battery> = parse_wmi_list("\r\n").unwrap();
+        assert!(batteries.is_empty());
+        assert_eq!(BatterySummary::from_batteries(&batteries), BatterySummary::default());
+    }
+
+    #[test]
+    fn test_parse_invalid_output() {
+        assert!(parse_wmi_list::<Win32Battery>("Get-WmiObject : Invalid class").is_err());
+    }
+
+    #[test]
+    fn test_prefix_keys_are_not_confused() {
+        // 手写解析时 "DesignCapacity" 会匹配到 "DesignCapacityLevel" 之类的前缀字段
+        let output = r#"{"DesignCapacityLevel": 3, "DesignCapacity": 50000, "Nested": {"DesignCapacity": 1}}"#;
+        let batteries: Vec<Win32Battery> = parse_wmi_list(output).unwrap();
+        assert_eq!(batteries[0].design_capacity, Some(50000));
+    }
+
+    #[test]
+    fn test_parse_portable_battery() {
+        let batteries: Vec<PortableBattery> = parse_wmi_list(PORTABLE_BATTERY).unwrap();
+        assert_eq!(batteries[0].design_capacity, Some(57000));
+        assert_eq!(batteries[0].chemistry_name(), Some("Li-ion"));
+        assert_eq!(batteries[0].manufacturer.as_deref(), Some("SMP"));
+    }
+
+    #[test]
+    fn test_parse_health() {
+        let records: Vec<WmiBatteryHealth> = parse_wmi_list(HEALTH).unwrap();
+        let health = records.into_iter().next().unwrap().into_health();
+        assert_eq!(health.design_capacity_mwh, Some(57000));
+        assert_eq!(health.full_charge_capacity_mwh, Some(45600));
+        assert_eq!(health.wear_level_percent, Some(20.0));
+        assert_eq!(health.cycle_count, None);
+        assert_eq!(health.chemistry.as_deref(), Some("LION"));
+        assert_eq!(health.manufacturer.as_deref(), Some("SMP"));
+        assert_eq!(health.serial, None);
+    }
+}
@@ -1,6 +1,9 @@
 use tauri::{AppHandle, Manager, Window, WindowBuilder, WindowUrl};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use chrono::{DateTime, Local};
+use crate::policy::{CriticalAction, EscalationConfig, EscalationStep, EscalationTracker};
+use crate::power::{BatteryLevel, PowerEvent};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertConfig {
@@ -16,6 +19,12 @@ pub struct AlertConfig {
     pub power_draw_watts: Option<f32>,
     pub remaining_time_minutes: Option<u32>,
     pub charge_rate_watts: Option<f32>,
+    #[serde(default)]
+    pub provenance: crate::power::StatusProvenance,  // 数值来源，估算值在窗口中标注
+    #[serde(default)]
+    pub fullscreen: bool,  // 全屏显示（电量严重不足）
+    #[serde(default)]
+    pub countdown_secs: Option<u32>,  // 自动操作倒计时（秒），窗口中显示倒计时和取消按钮
 }
 
 impl Default for AlertConfig {
@@ -32,6 +41,9 @@ impl Default for AlertConfig {
             power_draw_watts: None,
             remaining_time_minutes: None,
             charge_rate_watts: None,
+            provenance: Default::default(),
+            fullscreen: false,
+            countdown_secs: None,
         }
     }
 }
@@ -39,6 +51,9 @@ impl Default for AlertConfig {
 pub struct AlertManager {
     app_handle: Option<AppHandle>,
     active_alerts: HashMap<String, Window>,
+    low_battery_level: BatteryLevel,  // 当前低电量提醒窗口对应的级别
+    low_battery_fullscreen: bool,     // 当前低电量提醒窗口是否全屏
+    escalations: EscalationTracker,   // 低电量提醒的重复提醒与确认状态
 }
 
 impl AlertManager {
@@ -46,6 +61,9 @@ impl AlertManager {
         Self {
             app_handle: None,
             active_alerts: HashMap::new(),
+            low_battery_level: BatteryLevel::default(),
+            low_battery_fullscreen: false,
+            escalations: EscalationTracker::default(),
         }
     }
 
@@ -63,23 +81,69 @@ impl AlertManager {
         config.power_draw_watts = battery_status.power_draw_watts;
         config.remaining_time_minutes = battery_status.remaining_time_minutes;
         config.charge_rate_watts = battery_status.charge_rate_watts;
+        config.provenance = battery_status.provenance;
         
         self.show_alert("power_disconnected", config)
     }
 
-    /// 显示低电量提醒
-    pub fn show_low_battery_alert(&mut self, battery_status: &crate::power::BatteryStatus) -> Result<(), Box<dyn std::error::Error>> {
+    /// 显示电量严重不足提醒，按 `critical_fullscreen` 设置全屏显示
+    pub fn show_critical_battery_alert(&mut self, battery_status: &crate::power::BatteryStatus) -> Result<(), Box<dyn std::error::Error>> {
+        let mut level = BatteryLevel::default();
+        level.message = "电池电量严重不足！请立即连接电源".to_string();
+        let fullscreen = self.escalations.config().critical_fullscreen;
+        self.show_level_alert(battery_status, &level, fullscreen)
+    }
+
+    /// 显示指定电量级别的提醒，使用该级别的文字和颜色
+    ///
+    /// 所有级别共用同一个提醒窗口，更低的级别会替换之前的窗口。
+    pub fn show_battery_level_alert(&mut self, battery_status: &crate::power::BatteryStatus, level: &BatteryLevel) -> Result<(), Box<dyn std::error::Error>> {
+        self.show_level_alert(battery_status, level, false)
+    }
+
+    fn show_level_alert(&mut self, battery_status: &crate::power::BatteryStatus, level: &BatteryLevel, fullscreen: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.low_battery_level = level.clone();
+        self.low_battery_fullscreen = fullscreen;
+
         let mut config = AlertConfig::default();
-        config.message = "电池电量不足！请及时充电".to_string();
-        config.background_color = "#FF0000".to_string();
+        config.message = level.message.clone();
+        config.background_color = level.color.clone();
         config.battery_percentage = battery_status.battery_percentage;
         config.power_draw_watts = battery_status.power_draw_watts;
         config.remaining_time_minutes = battery_status.remaining_time_minutes;
         config.charge_rate_watts = battery_status.charge_rate_watts;
+        config.provenance = battery_status.provenance;
+        config.fullscreen = fullscreen;
         
         self.show_alert("low_battery", config)
     }
 
+    /// 显示提醒规则触发的其他事件提醒，每种事件使用单独的窗口
+    pub fn show_event_alert(&mut self, alert_id: &str, battery_status: &crate::power::BatteryStatus, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = AlertConfig::default();
+        config.message = message.to_string();
+        config.battery_percentage = battery_status.battery_percentage;
+        config.power_draw_watts = battery_status.power_draw_watts;
+        config.remaining_time_minutes = battery_status.remaining_time_minutes;
+        config.charge_rate_watts = battery_status.charge_rate_watts;
+        config.provenance = battery_status.provenance;
+
+        self.show_alert(alert_id, config)
+    }
+
+    /// 显示自动操作（休眠、关机等）倒计时，用户可以在窗口中取消
+    pub fn show_critical_action_countdown(&mut self, battery_status: &crate::power::BatteryStatus, action: &CriticalAction, countdown_secs: u32) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = AlertConfig::default();
+        config.message = format!("电量即将耗尽，将自动{}", action.label());
+        config.background_color = "#FF0000".to_string();
+        config.battery_percentage = battery_status.battery_percentage;
+        config.remaining_time_minutes = battery_status.remaining_time_minutes;
+        config.provenance = battery_status.provenance;
+        config.countdown_secs = Some(countdown_secs);
+
+        self.show_alert("critical_action", config)
+    }
+
     /// 显示通用提醒窗口
     pub fn show_alert(&mut self, alert_id: &str, config: AlertConfig) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(ref app_handle) = self.app_handle {
@@ -92,7 +156,7 @@ impl AlertManager {
             let window_title = "电源提醒";
 
             // 创建提醒窗口，初始状态为隐藏
-            let window = WindowBuilder::new(
+            let builder = WindowBuilder::new(
                 app_handle,
                 &window_label,
                 WindowUrl::App("alert.html".into())
@@ -104,11 +168,17 @@ impl AlertManager {
             .decorations(false)
             .always_on_top(config.always_on_top)
             .skip_taskbar(true)
-            .visible(false) // 初始状态为隐藏，避免闪现
-            .build()?;
+            .visible(false); // 初始状态为隐藏，避免闪现
+            let window = if config.fullscreen {
+                builder.fullscreen(true).build()?
+            } else {
+                builder.build()?
+            };
 
             // 设置窗口位置到屏幕右下角（托盘区域附近）
-            if let Ok(monitor) = window.primary_monitor() {
+            if config.fullscreen {
+                // 全屏窗口不需要调整位置
+            } else if let Ok(monitor) = window.primary_monitor() {
                 if let Some(monitor) = monitor {
                     let size = monitor.size();
                     let window_width = 400.0;
@@ -156,9 +226,10 @@ impl AlertManager {
                     ..AlertConfig::default()
                 },
                 "low_battery" => AlertConfig {
-                    message: "电池电量不足！请及时充电".to_string(),
-                    background_color: "#FF0000".to_string(),
+                    message: self.low_battery_level.message.clone(),
+                    background_color: self.low_battery_level.color.clone(),
                     battery_percentage,
+                    fullscreen: self.low_battery_fullscreen,
                     ..AlertConfig::default()
                 },
                 _ => continue,
@@ -169,8 +240,53 @@ impl AlertManager {
         Ok(())
     }
 
+    /// 设置提醒升级参数
+    pub fn set_escalation_config(&mut self, config: EscalationConfig) {
+        self.escalations.set_config(config);
+    }
+
+    /// 提醒窗口弹出后开始重复提醒，直到用户确认、接通电源或窗口被关闭
+    pub fn escalate(&mut self, alert_id: &str, event: &PowerEvent) {
+        if self.active_alerts.contains_key(alert_id) {
+            self.escalations.start(alert_id, event, Local::now());
+        }
+    }
+
+    /// 用户在提醒窗口中确认提醒：停止重复提醒并关闭窗口
+    pub fn acknowledge_alert(&mut self, alert_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.escalations.acknowledge(alert_id) {
+            crate::log_info!("Alert acknowledged: {}", alert_id);
+        }
+        self.close_alert(alert_id)
+    }
+
+    /// 该提醒是否已被用户确认（再次弹出时重置）
+    #[allow(dead_code)]
+    pub fn is_acknowledged(&self, alert_id: &str) -> bool {
+        self.escalations.is_acknowledged(alert_id)
+    }
+
+    /// 停止所有重复提醒（接通电源）
+    pub fn stop_escalations(&mut self) {
+        self.escalations.stop_all();
+    }
+
+    /// 取出到期的重复提醒
+    pub fn due_escalations(&mut self, now: DateTime<Local>) -> Vec<EscalationStep> {
+        self.escalations.due(now)
+    }
+
+    /// 把提醒窗口提到最前
+    pub fn focus_alert(&self, alert_id: &str) {
+        if let Some(window) = self.active_alerts.get(alert_id) {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+
     /// 关闭指定的提醒窗口
     pub fn close_alert(&mut self, alert_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.escalations.stop(alert_id);
         if let Some(window) = self.active_alerts.remove(alert_id) {
             window.close()?;
         }
@@ -179,6 +295,7 @@ impl AlertManager {
 
     /// 关闭所有提醒窗口
     pub fn close_all_alerts(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.escalations.stop_all();
         for (_, window) in self.active_alerts.drain() {
             let _ = window.close();
         }
@@ -208,6 +325,18 @@ pub fn pause_monitoring_from_alert(app_handle: AppHandle) -> Result<(), String>
     Ok(())
 }
 
+#[tauri::command]
+pub fn acknowledge_alert(app_handle: AppHandle, alert_id: String) -> Result<(), String> {
+    app_handle.emit_all("acknowledge-alert", alert_id).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn cancel_critical_action(app_handle: AppHandle) -> Result<(), String> {
+    app_handle.emit_all("cancel-critical-action", ()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_alert_config(alert_type: String) -> Result<AlertConfig, String> {
     match alert_type.as_str() {
@@ -254,4 +383,13 @@ mod tests {
         assert_eq!(config.message, deserialized.message);
         assert_eq!(config.background_color, deserialized.background_color);
     }
+
+    #[test]
+    fn test_acknowledge_alert() {
+        let mut alert_manager = AlertManager::new();
+        assert!(!alert_manager.is_acknowledged("low_battery"));
+
+        alert_manager.acknowledge_alert("low_battery").unwrap();
+        assert!(alert_manager.is_acknowledged("low_battery"));
+    }
 }
\ No newline at end of file
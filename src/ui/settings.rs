@@ -1,4 +1,5 @@
 use crate::config::{AppConfig, ConfigManager, MonitoringConfig, UiConfig, SystemConfig};
+use crate::history::HistoryConfig;
 use tauri::{AppHandle, Manager};
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +8,8 @@ pub struct SettingsData {
     pub monitoring: MonitoringConfig,
     pub ui: UiConfig,
     pub system: SystemConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
 }
 
 impl From<AppConfig> for SettingsData {
@@ -15,6 +18,7 @@ impl From<AppConfig> for SettingsData {
             monitoring: config.monitoring,
             ui: config.ui,
             system: config.system,
+            history: config.history,
         }
     }
 }
@@ -25,6 +29,7 @@ impl Into<AppConfig> for SettingsData {
             monitoring: self.monitoring,
             ui: self.ui,
             system: self.system,
+            history: self.history,
         }
     }
 }
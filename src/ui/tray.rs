@@ -1,8 +1,11 @@
-use crate::power::BatteryStatus;
+use crate::history::PowerSession;
+use crate::power::{BatteryStatus, PredictionDirection};
+use crate::policy::{Snooze, SnoozeDuration};
 use crate::{log_info, log_error};
+use chrono::{DateTime, Local};
 use tauri::{
     AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
-    SystemTrayMenuItem,
+    SystemTrayMenuItem, SystemTraySubmenu,
 };
 use std::sync::{Arc, Mutex};
 
@@ -11,6 +14,7 @@ pub struct TrayManager {
     app_handle: Option<AppHandle>,
     current_status: Arc<Mutex<Option<BatteryStatus>>>,
     is_monitoring: Arc<Mutex<bool>>,
+    snooze: Arc<Mutex<Option<Snooze>>>,  // 当前的暂停提醒，显示在状态项中
 }
 
 impl TrayManager {
@@ -19,6 +23,7 @@ impl TrayManager {
             app_handle: None,
             current_status: Arc::new(Mutex::new(None)),
             is_monitoring: Arc::new(Mutex::new(false)),
+            snooze: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -30,20 +35,30 @@ impl TrayManager {
     /// 创建系统托盘
     pub fn create_system_tray() -> SystemTray {
         let status_item = CustomMenuItem::new("status".to_string(), "获取状态中...");
+        let last_session_item = CustomMenuItem::new("last_session".to_string(), Self::format_last_session_text(None));
         let settings_item = CustomMenuItem::new("settings".to_string(), "设置");
         let pause_item = CustomMenuItem::new("pause".to_string(), "暂停监控");
         let resume_item = CustomMenuItem::new("resume".to_string(), "恢复监控");
+        let snooze_menu = SystemTrayMenu::new()
+            .add_item(CustomMenuItem::new("snooze_15m".to_string(), "15 分钟"))
+            .add_item(CustomMenuItem::new("snooze_1h".to_string(), "1 小时"))
+            .add_item(CustomMenuItem::new("snooze_plug_in".to_string(), "直到接通电源"))
+            .add_item(CustomMenuItem::new("snooze_tomorrow".to_string(), "直到明天"))
+            .add_native_item(SystemTrayMenuItem::Separator)
+            .add_item(CustomMenuItem::new("snooze_cancel".to_string(), "取消暂停").disabled());
         let startup_item = CustomMenuItem::new("startup".to_string(), "开机启动");
         let about_item = CustomMenuItem::new("about".to_string(), "关于");
         let quit_item = CustomMenuItem::new("quit".to_string(), "退出");
 
         let tray_menu = SystemTrayMenu::new()
             .add_item(status_item.disabled())
+            .add_item(last_session_item.disabled())
             .add_native_item(SystemTrayMenuItem::Separator)
             .add_item(settings_item)
             .add_native_item(SystemTrayMenuItem::Separator)
             .add_item(pause_item)
             .add_item(resume_item.disabled())
+            .add_submenu(SystemTraySubmenu::new("暂停提醒", snooze_menu))
             .add_native_item(SystemTrayMenuItem::Separator)
             .add_item(startup_item)
             .add_item(about_item)
@@ -61,7 +76,10 @@ impl TrayManager {
         }
 
         if let Some(ref app_handle) = self.app_handle {
-            let status_text = self.format_status_text(status);
+            let mut status_text = self.format_status_text(status);
+            if let Some(snooze) = *self.snooze.lock().unwrap() {
+                status_text.push_str(&format!(" | {}", Self::format_snooze_text(&snooze, Local::now())));
+            }
             let _ = app_handle.tray_handle().get_item("status").set_title(&status_text);
             
             // 更新托盘图标（暂时禁用）
@@ -92,6 +110,50 @@ impl TrayManager {
         }
     }
 
+    /// 更新暂停提醒状态（状态项中显示剩余时间）
+    pub fn update_snooze(&self, snooze: Option<Snooze>) {
+        *self.snooze.lock().unwrap() = snooze;
+
+        if let Some(ref app_handle) = self.app_handle {
+            let _ = app_handle.tray_handle().get_item("snooze_cancel").set_enabled(snooze.is_some());
+        }
+
+        let current_status = self.current_status.lock().unwrap().clone();
+        if let Some(status) = current_status {
+            self.update_status(&status);
+        }
+    }
+
+    /// 更新最近一次充放电摘要
+    pub fn update_last_session(&self, session: Option<&PowerSession>) {
+        if let Some(ref app_handle) = self.app_handle {
+            let _ = app_handle.tray_handle().get_item("last_session").set_title(Self::format_last_session_text(session));
+        }
+    }
+
+    /// 格式化最近一次充放电文本
+    fn format_last_session_text(session: Option<&PowerSession>) -> String {
+        session.map_or_else(|| "上次充放电：暂无记录".to_string(), PowerSession::summary)
+    }
+
+    /// 格式化暂停提醒文本
+    fn format_snooze_text(snooze: &Snooze, now: DateTime<Local>) -> String {
+        match snooze.remaining(now) {
+            Some(remaining) => {
+                // 向上取整，剩余不足 1 分钟时仍显示 1m
+                let total_minutes = (remaining.num_seconds() + 59) / 60;
+                let hours = total_minutes / 60;
+                let minutes = total_minutes % 60;
+                if hours > 0 {
+                    format!("暂停提醒：剩余 {}h{}m", hours, minutes)
+                } else {
+                    format!("暂停提醒：剩余 {}m", minutes)
+                }
+            }
+            None => "暂停提醒：直到接通电源".to_string(),
+        }
+    }
+
     /// 格式化状态文本
     fn format_status_text(&self, status: &BatteryStatus) -> String {
         let power_source = if status.is_ac_connected {
@@ -109,9 +171,9 @@ impl TrayManager {
         if status.is_battery_present {
             let mut status_text = format!("电源: {} | 电量: {}%{}", power_source, status.battery_percentage, charging_status);
             
-            // 添加功率信息
+            // 添加功率信息（估算值以 ≈ 标记）
             if let Some(power_watts) = status.power_draw_watts {
-                status_text.push_str(&format!(" | 功耗: {:.1}W", power_watts));
+                status_text.push_str(&format!(" | 功耗: {}{:.1}W", status.provenance.power_draw_watts.marker(), power_watts));
             }
             
             // 添加剩余时间
@@ -119,10 +181,24 @@ impl TrayManager {
                 if remaining_minutes > 0 && !status.is_charging {
                     let hours = remaining_minutes / 60;
                     let minutes = remaining_minutes % 60;
+                    let marker = status.provenance.remaining_time_minutes.marker();
+                    if hours > 0 {
+                        status_text.push_str(&format!(" | 剩余: {}{}h{}m", marker, hours, minutes));
+                    } else {
+                        status_text.push_str(&format!(" | 剩余: {}{}m", marker, minutes));
+                    }
+                }
+            }
+
+            // 充电时显示预计充满时间
+            if let Some(prediction) = status.prediction {
+                if status.is_charging && prediction.direction == PredictionDirection::ToFull {
+                    let hours = prediction.minutes / 60;
+                    let minutes = prediction.minutes % 60;
                     if hours > 0 {
-                        status_text.push_str(&format!(" | 剩余: {}h{}m", hours, minutes));
+                        status_text.push_str(&format!(" | 充满: {}h{}m", hours, minutes));
                     } else {
-                        status_text.push_str(&format!(" | 剩余: {}m", minutes));
+                        status_text.push_str(&format!(" | 充满: {}m", minutes));
                     }
                 }
             }
@@ -151,6 +227,21 @@ impl TrayManager {
                     "resume" => {
                         app_handle.emit_all("resume-monitoring", ()).unwrap();
                     }
+                    "snooze_15m" => {
+                        app_handle.emit_all("snooze-alerts", SnoozeDuration::Minutes(15)).unwrap();
+                    }
+                    "snooze_1h" => {
+                        app_handle.emit_all("snooze-alerts", SnoozeDuration::Minutes(60)).unwrap();
+                    }
+                    "snooze_plug_in" => {
+                        app_handle.emit_all("snooze-alerts", SnoozeDuration::UntilPlugIn).unwrap();
+                    }
+                    "snooze_tomorrow" => {
+                        app_handle.emit_all("snooze-alerts", SnoozeDuration::UntilTomorrow).unwrap();
+                    }
+                    "snooze_cancel" => {
+                        app_handle.emit_all("cancel-snooze", ()).unwrap();
+                    }
                     "startup" => {
                         // 直接在此处处理，避免生命周期问题
                         let app_state: tauri::State<crate::AppState> = app_handle.state();
@@ -261,6 +352,9 @@ mod tests {
             battery_capacity_mwh: Some(50000),
             remaining_time_minutes: Some(240),
             charge_rate_watts: Some(0.0),
+            devices: Vec::new(),
+            provenance: Default::default(),
+            prediction: None,
         };
         
         let text = tray_manager.format_status_text(&status_ac);
@@ -276,6 +370,9 @@ mod tests {
             battery_capacity_mwh: Some(50000),
             remaining_time_minutes: Some(120),
             charge_rate_watts: Some(20.0),
+            devices: Vec::new(),
+            provenance: Default::default(),
+            prediction: None,
         };
         
         let text = tray_manager.format_status_text(&status_battery);
@@ -284,6 +381,56 @@ mod tests {
         assert!(text.contains("充电中"));
     }
 
+    #[test]
+    fn test_estimated_values_are_marked() {
+        use crate::power::{Provenance, StatusProvenance};
+
+        let tray_manager = TrayManager::new();
+        let status = BatteryStatus {
+            is_charging: false,
+            is_ac_connected: false,
+            battery_percentage: 60,
+            is_battery_present: true,
+            power_draw_watts: Some(15.0),
+            remaining_time_minutes: Some(90),
+            provenance: StatusProvenance {
+                power_draw_watts: Provenance::Estimated,
+                remaining_time_minutes: Provenance::Derived,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let text = tray_manager.format_status_text(&status);
+        assert!(text.contains("功耗: ≈15.0W"));
+        assert!(text.contains("剩余: 1h30m"));
+    }
+
+    #[test]
+    fn test_time_to_full_shown_while_charging() {
+        use crate::power::RemainingTimePrediction;
+
+        let tray_manager = TrayManager::new();
+        let status = BatteryStatus {
+            is_charging: true,
+            is_ac_connected: true,
+            battery_percentage: 70,
+            is_battery_present: true,
+            prediction: Some(RemainingTimePrediction {
+                direction: PredictionDirection::ToFull,
+                minutes: 45,
+                lower_minutes: 40,
+                upper_minutes: 52,
+                rate_percent_per_hour: 40.0,
+                sample_count: 12,
+            }),
+            ..Default::default()
+        };
+
+        let text = tray_manager.format_status_text(&status);
+        assert!(text.contains("充满: 45m"));
+    }
+
     #[test]
     fn test_icon_selection() {
         let tray_manager = TrayManager::new();
@@ -297,6 +444,9 @@ mod tests {
             battery_capacity_mwh: Some(50000),
             remaining_time_minutes: Some(240),
             charge_rate_watts: Some(0.0),
+            devices: Vec::new(),
+            provenance: Default::default(),
+            prediction: None,
         };
         
         let icon_path = tray_manager.get_icon_for_status(&status_ac);
@@ -311,9 +461,52 @@ mod tests {
             battery_capacity_mwh: Some(50000),
             remaining_time_minutes: Some(120),
             charge_rate_watts: Some(0.0),
+            devices: Vec::new(),
+            provenance: Default::default(),
+            prediction: None,
         };
         
         let icon_path = tray_manager.get_icon_for_status(&status_battery);
         assert!(icon_path.contains("battery"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_last_session_formatting() {
+        use crate::history::SessionKind;
+        use crate::power::test_support::at;
+
+        assert_eq!(TrayManager::format_last_session_text(None), "上次充放电：暂无记录");
+
+        let start = at(1, 9, 0);
+        let session = PowerSession {
+            kind: SessionKind::Discharge,
+            start,
+            end: start + chrono::Duration::minutes(192),
+            start_percentage: 100,
+            end_percentage: 45,
+            duration_minutes: 192,
+            energy_wh: Some(27.2),
+            average_watts: Some(8.5),
+            peak_watts: Some(21.0),
+            percent_per_hour: Some(17.2),
+            ongoing: false,
+        };
+        assert_eq!(TrayManager::format_last_session_text(Some(&session)), "上次放电：100% → 45%，3h12m，平均 8.5W");
+    }
+
+    #[test]
+    fn test_snooze_formatting() {
+        use crate::power::test_support::at;
+
+        let now = at(1, 10, 0);
+
+        let snooze = Snooze::new(SnoozeDuration::Minutes(65), now);
+        assert_eq!(TrayManager::format_snooze_text(&snooze, now), "暂停提醒：剩余 1h5m");
+
+        let snooze = Snooze::new(SnoozeDuration::Minutes(15), now);
+        assert_eq!(TrayManager::format_snooze_text(&snooze, now + chrono::Duration::seconds(30)), "暂停提醒：剩余 15m");
+
+        let snooze = Snooze::new(SnoozeDuration::UntilPlugIn, now);
+        assert_eq!(TrayManager::format_snooze_text(&snooze, now), "暂停提醒：直到接通电源");
+    }
+}
@@ -0,0 +1,216 @@
+use crate::history::{ExportColumn, ExportFormat};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use std::path::PathBuf;
+
+/// 命令行参数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliOptions {
+    /// `--simulate <file>`：使用场景文件驱动整个应用，而不是读取真实电源状态
+    pub simulate: Option<PathBuf>,
+    /// `--export <file>`：导出电源历史后退出，不启动界面
+    pub export: Option<ExportRequest>,
+    /// `--report <file>`：生成电池报告（HTML 和 JSON）后退出，不启动界面
+    pub report: Option<ReportRequest>,
+}
+
+/// 命令行导出请求
+///
+/// `--export <file|->` 指定输出文件（`-` 表示标准输出），`--from`/`--to` 指定时间范围
+/// （`YYYY-MM-DD`、`YYYY-MM-DD HH:MM` 或 RFC 3339，不包含结束时间），`--format csv|jsonl`
+/// 指定格式（默认按文件扩展名，否则为 CSV），`--columns percentage,watts,...` 指定导出的列。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRequest {
+    pub path: PathBuf,
+    pub from: Option<DateTime<Local>>,
+    pub to: Option<DateTime<Local>>,
+    pub format: Option<ExportFormat>,
+    pub columns: Vec<ExportColumn>,  // 为空时使用默认列
+}
+
+impl ExportRequest {
+    /// 是否输出到标准输出
+    pub fn is_stdout(&self) -> bool {
+        self.path.as_os_str() == "-"
+    }
+
+    /// 实际使用的格式
+    pub fn format(&self) -> ExportFormat {
+        self.format
+            .or_else(|| ExportFormat::from_path(&self.path))
+            .unwrap_or(ExportFormat::Csv)
+    }
+}
+
+/// 命令行电池报告请求
+///
+/// `--report <file>` 指定 HTML 报告路径，同名的 `.json` 文件写在同一目录；
+/// `--from`/`--to` 指定统计范围，格式与导出相同。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRequest {
+    pub path: PathBuf,
+    pub from: Option<DateTime<Local>>,
+    pub to: Option<DateTime<Local>>,
+}
+
+impl CliOptions {
+    /// 解析当前进程的命令行参数
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// 解析参数列表（不包含程序名），未识别的参数会被忽略
+    pub fn parse<I, S>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut options = Self::default();
+        let mut export_path = None;
+        let mut report_path = None;
+        let (mut from, mut to, mut format, mut columns) = (None, None, None, Vec::new());
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            // 同时支持 "--name value" 和 "--name=value"
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg, None),
+            };
+            let mut value = |message: &str| inline_value.clone().or_else(|| args.next()).ok_or(message.to_string());
+
+            match name.as_str() {
+                "--simulate" => options.simulate = Some(PathBuf::from(value("--simulate 需要指定场景文件路径")?)),
+                "--export" => export_path = Some(PathBuf::from(value("--export 需要指定输出文件路径")?)),
+                "--report" => report_path = Some(PathBuf::from(value("--report 需要指定报告文件路径")?)),
+                "--from" => from = Some(parse_time(&value("--from 需要指定开始时间")?)?),
+                "--to" => to = Some(parse_time(&value("--to 需要指定结束时间")?)?),
+                "--format" => format = Some(ExportFormat::parse(&value("--format 需要指定 csv 或 jsonl")?)?),
+                "--columns" => columns = ExportColumn::parse_list(&value("--columns 需要指定导出的列")?)?,
+                _ => {}
+            }
+        }
+
+        if export_path.is_some() && report_path.is_some() {
+            return Err("--export 和 --report 不能同时使用".to_string());
+        }
+        match (export_path, report_path) {
+            (Some(path), _) => options.export = Some(ExportRequest { path, from, to, format, columns }),
+            (None, Some(_)) if format.is_some() || !columns.is_empty() => {
+                return Err("--format、--columns 需要与 --export 一起使用".to_string());
+            }
+            (None, Some(path)) => options.report = Some(ReportRequest { path, from, to }),
+            (None, None) if from.is_some() || to.is_some() || format.is_some() || !columns.is_empty() => {
+                return Err("--from、--to、--format、--columns 需要与 --export 或 --report 一起使用".to_string());
+            }
+            (None, None) => {}
+        }
+
+        Ok(options)
+    }
+}
+
+/// 解析本地时间：`YYYY-MM-DD`（当天 0 点）、`YYYY-MM-DD HH:MM` 或 RFC 3339
+fn parse_time(text: &str) -> Result<DateTime<Local>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time.with_timezone(&Local));
+    }
+    let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M")
+        .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d").map(|date| date.and_time(Default::default())))
+        .map_err(|_| format!("无效的时间 \"{}\"，应为 YYYY-MM-DD、YYYY-MM-DD HH:MM 或 RFC 3339 格式", text))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| format!("本地时间 \"{}\" 不存在", text))
+}
+
+/// 连接到启动本进程的终端的控制台，返回标准输出是否可用
+///
+/// Windows 发布版使用 GUI 子系统，进程没有控制台，`--export -` 的输出和命令行提示会被丢弃。
+/// 从终端运行时连接到父进程的控制台；标准输出已被重定向到文件或管道时直接使用重定向的句柄。
+/// 从资源管理器等没有终端的地方启动时返回 `false`。
+#[cfg(windows)]
+pub fn attach_parent_console() -> bool {
+    use windows::Win32::System::Console::{AttachConsole, GetStdHandle, ATTACH_PARENT_PROCESS, STD_OUTPUT_HANDLE};
+
+    unsafe {
+        // 已有控制台（调试版）或没有父控制台时失败，忽略即可
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+        GetStdHandle(STD_OUTPUT_HANDLE).is_ok_and(|handle| !handle.is_invalid())
+    }
+}
+
+/// 其他平台的命令行程序总是继承终端的标准输出
+#[cfg(not(windows))]
+pub fn attach_parent_console() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::test_support::at;
+
+    #[test]
+    fn test_no_arguments() {
+        let options = CliOptions::parse(Vec::<String>::new()).unwrap();
+        assert_eq!(options, CliOptions::default());
+    }
+
+    #[test]
+    fn test_simulate_argument() {
+        let options = CliOptions::parse(["--simulate", "demo.toml"]).unwrap();
+        assert_eq!(options.simulate, Some(PathBuf::from("demo.toml")));
+
+        let options = CliOptions::parse(["--simulate=demo.json"]).unwrap();
+        assert_eq!(options.simulate, Some(PathBuf::from("demo.json")));
+    }
+
+    #[test]
+    fn test_simulate_requires_path() {
+        assert!(CliOptions::parse(["--simulate"]).is_err());
+    }
+
+    #[test]
+    fn test_export_arguments() {
+        let options = CliOptions::parse([
+            "--export", "battery.jsonl", "--from", "2025-09-01", "--to=2025-09-02 12:30", "--columns", "percentage,watts",
+        ])
+        .unwrap();
+        let export = options.export.unwrap();
+        assert_eq!(export.path, PathBuf::from("battery.jsonl"));
+        assert_eq!(export.from, Some(at(1, 0, 0)));
+        assert_eq!(export.to, Some(at(2, 12, 30)));
+        assert_eq!(export.columns, vec![ExportColumn::Percentage, ExportColumn::Watts]);
+        assert_eq!(export.format(), ExportFormat::Jsonl);
+
+        let export = CliOptions::parse(["--export", "-", "--format", "csv"]).unwrap().export.unwrap();
+        assert!(export.is_stdout());
+        assert_eq!(export.format(), ExportFormat::Csv);
+    }
+
+    #[test]
+    fn test_invalid_export_arguments() {
+        assert!(CliOptions::parse(["--export", "a.csv", "--from", "yesterday"]).is_err());
+        assert!(CliOptions::parse(["--export", "a.csv", "--format", "xlsx"]).is_err());
+        assert!(CliOptions::parse(["--from", "2025-09-01"]).is_err());
+    }
+
+    #[test]
+    fn test_report_arguments() {
+        let options = CliOptions::parse(["--report", "battery-report.html", "--from=2025-09-01"]).unwrap();
+        let report = options.report.unwrap();
+        assert_eq!(report.path, PathBuf::from("battery-report.html"));
+        assert_eq!(report.from, Some(at(1, 0, 0)));
+        assert_eq!(report.to, None);
+        assert!(options.export.is_none());
+
+        assert!(CliOptions::parse(["--report", "r.html", "--columns", "watts"]).is_err());
+        assert!(CliOptions::parse(["--report", "r.html", "--export", "a.csv"]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_arguments_ignored() {
+        let options = CliOptions::parse(["--minimized", "--simulate", "a.toml"]).unwrap();
+        assert_eq!(options.simulate, Some(PathBuf::from("a.toml")));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod startup;
 pub mod logger;
+pub mod cli;
 
 pub use startup::*;
-pub use logger::*;
\ No newline at end of file
+pub use logger::*;
+pub use cli::*;
\ No newline at end of file